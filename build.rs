@@ -0,0 +1,26 @@
+fn main() {
+    // Generates the C header of the `ffi` feature from the `extern "C"` functions, the ffi
+    // tests check that the committed `include/thefuck_rs.h` is the same
+    #[cfg(feature = "ffi")]
+    {
+        let dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+        println!("cargo:rerun-if-changed=src/ffi");
+        cbindgen::Builder::new()
+            .with_src(
+                std::path::Path::new(&dir)
+                    .join("src")
+                    .join("ffi")
+                    .join("mod.rs"),
+            )
+            .with_language(cbindgen::Language::C)
+            .with_include_guard("THEFUCK_RS_H")
+            .with_documentation(true)
+            .with_header("/* Generated by cbindgen from src/ffi, do not edit. */")
+            .generate()
+            .expect("the C header can be generated")
+            .write_to_file(
+                std::path::Path::new(&std::env::var("OUT_DIR").expect("set by cargo"))
+                    .join("thefuck_rs.h"),
+            );
+    }
+}
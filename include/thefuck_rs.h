@@ -0,0 +1,40 @@
+/* Generated by cbindgen from src/ffi, do not edit. */
+
+#ifndef THEFUCK_RS_H
+#define THEFUCK_RS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Corrects a failed command from its standard error.
+ *
+ * Returns a JSON array of corrections, most likely first, each with a `text`, the `rule` that
+ * suggested it, its `priority` and its `side_effects`, e.g.
+ * `[{"text":"cargo build","rule":"cargo_no_command","priority":900,"side_effects":[]}]`.
+ * Returns NULL if an argument is NULL or not UTF-8, or the engine failed.
+ * The result must be released with `tf_free_string`.
+ *
+ * # Safety
+ *
+ * `command` and `stderr_text` must be null or point to NUL-terminated strings.
+ */
+char *tf_correct(const char *command, const char *stderr_text);
+
+/**
+ * Releases a string returned by the library. Does nothing if `text` is NULL.
+ *
+ * # Safety
+ *
+ * `text` must be null or a string returned by the library that wasn't released yet.
+ */
+void tf_free_string(char *text);
+
+/**
+ * Gets the version of the library, e.g. `0.1.0`. The string is static and must not be released.
+ */
+const char *tf_version(void);
+
+#endif  /* THEFUCK_RS_H */
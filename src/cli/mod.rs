@@ -1,4 +1,5 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "thefuck-rs")]
@@ -31,10 +32,148 @@ pub struct Cli {
     #[arg(short, long)]
     pub repeat: bool,
 
+    /// Pick the correction in a full-screen picker with a preview
+    #[arg(long)]
+    pub tui: bool,
+
+    /// Copy the correction to the clipboard instead of running it
+    #[arg(long)]
+    pub copy: bool,
+
+    /// Explain on stderr why each correction was suggested
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Print on stderr how long each rule took to match and to build its corrections
+    #[arg(long)]
+    pub profile: bool,
+
+    /// Read the failed command and its output as JSON from stdin instead of running it again
+    #[arg(long)]
+    pub stdin_json: bool,
+
+    /// Standard error of the failed command, instead of running it again
+    #[arg(long, conflicts_with_all = ["stderr_file", "stdin_json"])]
+    #[allow(clippy::type_complexity)]
+    pub stderr: Option<String>,
+
+    /// File holding the standard error of the failed command, instead of running it again
+    #[arg(long, value_name = "FILE", conflicts_with = "stdin_json")]
+    #[allow(clippy::type_complexity)]
+    pub stderr_file: Option<String>,
+
+    /// Exit code of the failed command, instead of running it again
+    #[arg(long, conflicts_with = "stdin_json", allow_negative_numbers = true)]
+    #[allow(clippy::type_complexity)]
+    pub exit_code: Option<i32>,
+
+    /// Command to fix instead of the one from the arguments or history
+    #[arg(long, value_name = "COMMAND")]
+    #[allow(clippy::type_complexity)]
+    pub force_command: Option<String>,
+
     /// Command that should be fixed
     #[arg(trailing_var_arg = true)]
     #[allow(clippy::type_complexity)]
     pub command: Vec<String>,
+
+    #[command(subcommand)]
+    #[allow(clippy::type_complexity)]
+    pub subcommand: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum Commands {
+    /// Manage what was learned from previous corrections
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+    /// Serve corrections in the background, keeping rules and caches warm
+    Daemon,
+    /// Answer JSON-RPC requests of editor integrations (correct, explain, listRules)
+    Serve {
+        /// Read requests from stdin and write responses to stdout, one per line
+        #[arg(long, required = true)]
+        stdio: bool,
+    },
+    /// Inspect the rules used to correct commands
+    Rules {
+        #[command(subcommand)]
+        action: RulesAction,
+    },
+    /// Revert the last applied correction
+    Undo {
+        /// Run the inverse command instead of printing it
+        #[arg(long)]
+        run: bool,
+        /// List the applied corrections instead, most recent first
+        #[arg(long, conflicts_with = "run")]
+        list: bool,
+    },
+    /// Print the shell hook that captures each command's output, so it needn't run again
+    Hook {
+        /// Shell to hook into, the detected one if omitted
+        #[allow(clippy::type_complexity)]
+        shell: Option<String>,
+    },
+    /// Show how often each rule fires and is accepted, and how long corrections take
+    Stats {
+        /// Number of rules to show, most often fired first
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Generate the man pages of thefuck-rs and its subcommands
+    GenMan {
+        /// Directory to write every page to, instead of printing the main one
+        #[arg(long, value_name = "DIR")]
+        #[allow(clippy::type_complexity)]
+        out_dir: Option<PathBuf>,
+    },
+    /// Generate a markdown reference page for each built-in rule
+    GenDocs {
+        /// Directory to write the pages to
+        #[arg(long, value_name = "DIR", default_value = "docs/rules")]
+        out_dir: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum HistoryAction {
+    /// Forget which corrections were picked before
+    ClearLearning,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum RulesAction {
+    /// List every rule, whether it's enabled, its priority and the programs it targets
+    List {
+        /// Print the rules as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run one rule against a failed command and print what it would suggest
+    Test {
+        /// Name of the rule, enabled or not
+        rule: String,
+        /// The failed command
+        #[arg(long)]
+        command: String,
+        /// Standard error of the failed command
+        #[arg(long, conflicts_with = "stderr_file")]
+        #[allow(clippy::type_complexity)]
+        stderr: Option<String>,
+        /// File holding the standard error of the failed command
+        #[arg(long, value_name = "FILE")]
+        #[allow(clippy::type_complexity)]
+        stderr_file: Option<String>,
+        /// Standard output of the failed command
+        #[arg(long, default_value = "")]
+        stdout: String,
+        /// Exit code of the failed command
+        #[arg(long, default_value_t = 1, allow_negative_numbers = true)]
+        exit_code: i32,
+    },
 }
 
 impl Cli {
@@ -47,6 +186,14 @@ impl Cli {
     }
 
     pub fn is_command_fix_request(&self) -> bool {
-        !self.command.is_empty() || std::env::var("TF_HISTORY").is_ok()
+        self.stdin_json
+            || self.force_command.is_some()
+            || !self.command.is_empty()
+            || std::env::var("TF_HISTORY").is_ok()
+    }
+
+    /// Whether the output of the failed command was given, so it needn't run again
+    pub fn has_command_output(&self) -> bool {
+        self.stderr.is_some() || self.stderr_file.is_some() || self.exit_code.is_some()
     }
 }
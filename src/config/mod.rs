@@ -1 +1,144 @@
+use crate::guard::GuardSettings;
+use crate::{TheFuckError, TheFuckResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+/// Name of the settings file inside the configuration directory
+pub const SETTINGS_FILE: &str = "settings.toml";
+
+/// Name of the directory of rule plugins inside the configuration directory
+pub const PLUGINS_DIR: &str = "plugins";
+
+/// Name of the directory of user rule scripts inside the configuration directory
+pub const RULES_DIR: &str = "rules";
+
+/// User settings, loaded from `settings.toml` in the configuration directory
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Cargo subcommands mapped to the crate that provides them, in addition to the built-in table
+    #[allow(clippy::type_complexity)]
+    pub cargo_subcommands: HashMap<String, String>,
+    /// Names of the rules that are never used, built-in or not
+    #[allow(clippy::type_complexity)]
+    pub exclude_rules: Vec<String>,
+    /// Copy the correction to the clipboard instead of running it
+    pub clipboard: bool,
+    /// Show corrections without colors
+    pub no_colors: bool,
+    /// Pick corrections in a full-screen picker instead of on the prompt line
+    pub full_screen_picker: bool,
+    /// Log debugging details to a file in the cache directory
+    pub debug: bool,
+    /// What happens to dangerous corrections
+    pub guard: GuardSettings,
+}
+
+impl Settings {
+    /// Gets the configuration directory, e.g. `~/.config/thefuck-rs`
+    #[allow(clippy::type_complexity)]
+    pub fn config_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("thefuck-rs"))
+    }
+
+    /// Gets the data directory, e.g. `~/.local/share/thefuck-rs`
+    #[allow(clippy::type_complexity)]
+    pub fn data_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("thefuck-rs"))
+    }
+
+    /// Gets the cache directory, e.g. `~/.cache/thefuck-rs`
+    #[allow(clippy::type_complexity)]
+    pub fn cache_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("thefuck-rs"))
+    }
+
+    /// Gets the directory of rule plugins, e.g. `~/.config/thefuck-rs/plugins`
+    #[allow(clippy::type_complexity)]
+    pub fn plugins_dir() -> Option<PathBuf> {
+        Self::config_dir().map(|dir| dir.join(PLUGINS_DIR))
+    }
+
+    /// Gets the directory of user rule scripts, e.g. `~/.config/thefuck-rs/rules`
+    #[allow(clippy::type_complexity)]
+    pub fn rules_dir() -> Option<PathBuf> {
+        Self::config_dir().map(|dir| dir.join(RULES_DIR))
+    }
+
+    /// Gets the custom rules directory of the original Python thefuck, e.g. `~/.config/thefuck/rules`
+    #[allow(clippy::type_complexity)]
+    pub fn legacy_rules_dir() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+            .map(|dir| dir.join("thefuck").join(RULES_DIR))
+    }
+
+    /// Loads the settings from the configuration directory, using defaults if there is no file
+    #[allow(clippy::type_complexity)]
+    pub fn load() -> TheFuckResult<Self> {
+        match Self::config_dir().map(|dir| dir.join(SETTINGS_FILE)) {
+            Some(path) if path.exists() => Self::from_file(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Loads the settings from a TOML file
+    #[allow(clippy::type_complexity)]
+    pub fn from_file(path: &Path) -> TheFuckResult<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| TheFuckError::ConfigLoadError(format!("{}: {e}", path.display())))?;
+        Self::from_toml(&content)
+    }
+
+    /// Parses the settings from TOML
+    #[allow(clippy::type_complexity)]
+    pub fn from_toml(content: &str) -> TheFuckResult<Self> {
+        Ok(toml::from_str(content)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_from_toml() {
+        let settings =
+            Settings::from_toml("[cargo_subcommands]\nnextest = \"cargo-nextest\"\n").unwrap();
+        assert_eq!(
+            settings.cargo_subcommands.get("nextest"),
+            Some(&"cargo-nextest".to_string())
+        );
+
+        assert_eq!(Settings::from_toml("").unwrap(), Settings::default());
+        assert_eq!(
+            Settings::from_toml("exclude_rules = [\"sed_unterminated_s\"]\n")
+                .unwrap()
+                .exclude_rules,
+            vec!["sed_unterminated_s"]
+        );
+        assert!(Settings::from_toml("clipboard = true\n").unwrap().clipboard);
+        let guard = Settings::from_toml("[guard]\naction = \"block\"\n")
+            .unwrap()
+            .guard;
+        assert_eq!(guard.action, crate::guard::GuardAction::Block);
+        assert!(guard.builtin_patterns);
+        assert!(Settings::from_toml("cargo_subcommands = 1").is_err());
+    }
+
+    #[test]
+    fn test_settings_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        assert!(matches!(
+            Settings::from_file(&path),
+            Err(TheFuckError::ConfigLoadError(_))
+        ));
+
+        std::fs::write(&path, "[cargo_subcommands]\nsweep = \"cargo-sweep\"\n").unwrap();
+        let settings = Settings::from_file(&path).unwrap();
+        assert_eq!(settings.cargo_subcommands.len(), 1);
+    }
+}
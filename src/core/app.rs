@@ -0,0 +1,627 @@
+use super::{CorrectionRequest, get_corrections, load_registry, register_user_rules};
+use crate::cli::{Cli, Commands, HistoryAction, RulesAction};
+use crate::config::Settings;
+use crate::daemon::{self, DaemonState};
+use crate::engine::OutputCapture;
+use crate::guard::{Guard, Verdict};
+use crate::learning::LearningStore;
+use crate::rules::{Ranking, RuleInfo, RuleRegistry, builtin_rules};
+use crate::server::Server;
+use crate::stats::StatsStore;
+use crate::types::CorrectedCommand;
+use crate::ui::Picker;
+use crate::undo::{Journal, JournalEntry};
+use crate::utils::profile;
+use crate::{TheFuckError, TheFuckResult, docs, i18n, instant, shells, ui, utils};
+use std::io::{IsTerminal, Read};
+use std::time::{Duration, Instant};
+
+pub async fn run(cli: Cli) -> TheFuckResult<()> {
+    if let Some(subcommand) = cli.subcommand {
+        return run_subcommand(subcommand).await;
+    }
+
+    // Handle alias request
+    if cli.is_alias_request() {
+        println!("alias fuck='eval $(thefuck-rs $(fc -ln -1 | tail -n1); fc -R)'");
+        return Ok(());
+    }
+
+    // Handle shell logger request
+    if cli.is_shell_logger_request() {
+        if let Some(log_file) = cli.shell_logger {
+            return instant::run_session(std::path::Path::new(&log_file));
+        }
+        return Err(TheFuckError::config_error(
+            "Shell logger file not specified",
+        ));
+    }
+
+    // Handle command fix request
+    if cli.is_command_fix_request() {
+        let settings = Settings::load()?;
+        let request = failed_request(&cli)?;
+        tracing::debug!(
+            "correcting `{}` (exit code {}, cwd {:?})\n--- stdout\n{}\n--- stderr\n{}",
+            request.command,
+            request.exit_code,
+            request.cwd,
+            request.stdout,
+            request.stderr
+        );
+        let started = Instant::now();
+        let corrections = if cli.profile {
+            // Rules are timed in-process, so the daemon is bypassed
+            let (corrections, timings) = profile::track(|| correct_locally(&request, &settings));
+            eprint!("{}", profile::report(&timings));
+            corrections
+        } else {
+            correct(&request, &settings).await?
+        };
+        record_run(&corrections, started.elapsed());
+        let guard = Guard::new(&settings.guard)?;
+        let corrections = guard.filter(corrections);
+        let colors = !settings.no_colors;
+        if cli.explain {
+            for correction in &corrections {
+                eprint!("{}", explain(correction, colors));
+            }
+        }
+        if corrections.is_empty() {
+            return Err(TheFuckError::no_rules_found(request.command));
+        }
+        let correction = if cli.yes {
+            if !cli.explain && std::io::stderr().is_terminal() {
+                eprintln!(
+                    "{}",
+                    ui::highlight_correction(&request.command, &corrections[0].text, colors)
+                );
+            }
+            &corrections[0]
+        } else {
+            let picker = if cli.tui || settings.full_screen_picker {
+                Picker::FullScreen
+            } else {
+                Picker::Inline
+            };
+            match ui::select_correction(&request.command, &corrections, picker, colors)? {
+                Some(correction) => {
+                    record_pick(correction);
+                    correction
+                }
+                None => {
+                    eprintln!("{}", i18n::tr("aborted"));
+                    return Ok(());
+                }
+            }
+        };
+        let copy = cli.copy || settings.clipboard;
+        if !copy && !confirm_dangerous(&guard, correction)? {
+            eprintln!("{}", i18n::tr("aborted"));
+            return Ok(());
+        }
+        record_accepted(correction);
+        if copy {
+            ui::copy_to_clipboard(&correction.text)?;
+            eprintln!(
+                "{}",
+                i18n::tr_with("copied", &[("command", correction.text.as_str().into())])
+            );
+            return Ok(());
+        }
+        #[cfg(feature = "python-rules")]
+        if let Err(e) = crate::legacy::run_side_effect(correction, &request.to_result()) {
+            tracing::warn!("{e}");
+        }
+        record_applied(correction);
+        println!("{}", correction.text);
+        return Ok(());
+    }
+
+    // Default: show help
+    println!("{}", i18n::tr("usage-hint"));
+    Ok(())
+}
+
+/// Gets the failed command and its output, running it again only if the output wasn't given
+#[allow(clippy::type_complexity)]
+fn failed_request(cli: &Cli) -> TheFuckResult<CorrectionRequest> {
+    if cli.stdin_json {
+        let mut json = String::new();
+        std::io::stdin().read_to_string(&mut json)?;
+        let mut request = CorrectionRequest::from_json(&json)?;
+        if let Some(command) = &cli.force_command {
+            request.command = command.clone();
+        }
+        // The daemon answering it runs elsewhere, with its own directory and environment
+        if request.cwd.is_none() {
+            request.cwd = std::env::current_dir()
+                .ok()
+                .map(|dir| dir.to_string_lossy().to_string());
+        }
+        if request.env.is_empty() {
+            request.env = std::env::vars().collect();
+        }
+        return Ok(request);
+    }
+
+    let command = failed_command(cli)
+        .ok_or_else(|| TheFuckError::parse_error("No command provided to fix"))?;
+    if !cli.has_command_output() {
+        // What the shell hook or instant mode captured is safer than running the command again
+        return OutputCapture::ShellHookOrRerun.capture(&command);
+    }
+    Ok(CorrectionRequest {
+        command,
+        stderr: read_stderr(cli.stderr.as_deref(), cli.stderr_file.as_deref())?,
+        exit_code: cli.exit_code.unwrap_or(1),
+        cwd: std::env::current_dir()
+            .ok()
+            .map(|dir| dir.to_string_lossy().to_string()),
+        env: std::env::vars().collect(),
+        ..CorrectionRequest::default()
+    })
+}
+
+/// Gets the stderr given inline, or read from a file
+#[allow(clippy::type_complexity)]
+fn read_stderr(stderr: Option<&str>, file: Option<&str>) -> TheFuckResult<String> {
+    match (stderr, file) {
+        (Some(stderr), _) => Ok(stderr.to_string()),
+        (None, Some(file)) => std::fs::read_to_string(file)
+            .map_err(|e| TheFuckError::file_system_error(format!("Failed to read {file}: {e}"))),
+        (None, None) => Ok(String::new()),
+    }
+}
+
+/// Gets the command to fix from `--force-command`, the arguments, or the last line of `TF_HISTORY`
+#[allow(clippy::type_complexity)]
+fn failed_command(cli: &Cli) -> Option<String> {
+    if let Some(command) = &cli.force_command {
+        return Some(command.clone());
+    }
+    if !cli.command.is_empty() {
+        return Some(cli.command.join(" "));
+    }
+    std::env::var("TF_HISTORY").ok().and_then(|history| {
+        history
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// Asks the user to confirm a dangerous correction, refusing it when there is no terminal to ask on
+#[allow(clippy::type_complexity)]
+fn confirm_dangerous(guard: &Guard, correction: &CorrectedCommand) -> TheFuckResult<bool> {
+    let Verdict::Confirm(danger) = guard.verdict(&correction.text) else {
+        return Ok(true);
+    };
+    if !std::io::stdin().is_terminal() {
+        eprintln!(
+            "{}",
+            i18n::tr_with(
+                "guard-dangerous",
+                &[
+                    ("command", danger.command.as_str().into()),
+                    ("reason", danger.reason.as_str().into())
+                ]
+            )
+        );
+        return Ok(false);
+    }
+    guard.confirm(
+        &danger,
+        &mut std::io::stdin().lock(),
+        &mut std::io::stderr(),
+    )
+}
+
+/// Describes why a correction was suggested
+fn explain(correction: &CorrectedCommand, colors: bool) -> String {
+    let mut explanation = format!(
+        "{}\n",
+        ui::highlight_correction(&correction.original.text, &correction.text, colors)
+    );
+    let rule = match &correction.explanation {
+        Some(explanation) => explanation.rule.as_str(),
+        None => correction.rule.as_str(),
+    };
+    explanation.push_str(&format!("  rule: {rule}\n"));
+    explanation.push_str(&format!("  priority: {}\n", correction.priority));
+    if let Some(details) = &correction.explanation {
+        if let Some(pattern) = &details.pattern {
+            explanation.push_str(&format!("  matched: {pattern}\n"));
+        }
+        for lookup in &details.lookups {
+            explanation.push_str(&format!("  lookup: {lookup}\n"));
+        }
+    }
+    explanation
+}
+
+/// Records that the user picked a correction, so it ranks higher next time
+fn record_pick(correction: &CorrectedCommand) {
+    let recorded = LearningStore::open_default().and_then(|store| store.record(correction));
+    if let Err(e) = recorded {
+        tracing::warn!("failed to learn from the pick: {e}");
+    }
+}
+
+/// Records how long finding the corrections took and which rules suggested them
+#[allow(clippy::type_complexity)]
+fn record_run(corrections: &[CorrectedCommand], latency: Duration) {
+    let recorded =
+        StatsStore::open_default().and_then(|stats| stats.record_run(corrections, latency));
+    if let Err(e) = recorded {
+        tracing::warn!("failed to record the statistics: {e}");
+    }
+}
+
+/// Records that the user went with a correction
+fn record_accepted(correction: &CorrectedCommand) {
+    let recorded = StatsStore::open_default().and_then(|stats| stats.record_accepted(correction));
+    if let Err(e) = recorded {
+        tracing::warn!("failed to record the statistics: {e}");
+    }
+}
+
+/// Records a correction in the undo journal before it's handed to the shell
+fn record_applied(correction: &CorrectedCommand) {
+    let recorded =
+        Journal::open_default().and_then(|journal| journal.record(&JournalEntry::new(correction)));
+    if let Err(e) = recorded {
+        tracing::warn!("failed to record the correction: {e}");
+    }
+}
+
+/// Gets the corrections from the daemon if it's running, or in-process otherwise
+#[allow(clippy::type_complexity)]
+async fn correct(
+    request: &CorrectionRequest,
+    settings: &Settings,
+) -> TheFuckResult<Vec<CorrectedCommand>> {
+    if let Some(corrections) = daemon::request(request).await {
+        return Ok(corrections);
+    }
+    Ok(correct_locally(request, settings))
+}
+
+/// Gets the corrections in-process
+#[allow(clippy::type_complexity)]
+fn correct_locally(request: &CorrectionRequest, settings: &Settings) -> Vec<CorrectedCommand> {
+    let registry = load_registry(settings);
+    let store = LearningStore::open_default().ok();
+    get_corrections(
+        &registry,
+        request,
+        store.as_ref().map(|store| store as &dyn Ranking),
+    )
+}
+
+#[allow(clippy::type_complexity)]
+async fn run_subcommand(subcommand: Commands) -> TheFuckResult<()> {
+    match subcommand {
+        Commands::History {
+            action: HistoryAction::ClearLearning,
+        } => {
+            let cleared = LearningStore::open_default()?.clear()?;
+            println!(
+                "{}",
+                i18n::tr_with("learning-cleared", &[("count", cleared.into())])
+            );
+            Ok(())
+        }
+        Commands::Daemon => {
+            let state = tokio::task::spawn_blocking(DaemonState::load)
+                .await
+                .map_err(|e| TheFuckError::process_error(e.to_string()))??;
+            daemon::serve(state).await
+        }
+        Commands::Serve { stdio: _ } => tokio::task::spawn_blocking(|| {
+            Server::load()?.serve(std::io::stdin().lock(), std::io::stdout().lock())
+        })
+        .await
+        .map_err(|e| TheFuckError::process_error(e.to_string()))?,
+        Commands::Rules {
+            action: RulesAction::List { json },
+        } => list_rules(json),
+        Commands::Rules {
+            action:
+                RulesAction::Test {
+                    rule,
+                    command,
+                    stderr,
+                    stderr_file,
+                    stdout,
+                    exit_code,
+                },
+        } => {
+            let request = CorrectionRequest {
+                command,
+                stdout,
+                stderr: read_stderr(stderr.as_deref(), stderr_file.as_deref())?,
+                exit_code,
+                cwd: std::env::current_dir()
+                    .ok()
+                    .map(|dir| dir.to_string_lossy().to_string()),
+                env: std::env::vars().collect(),
+                ..CorrectionRequest::default()
+            };
+            let settings = Settings::load()?;
+            print!("{}", test_rule(&all_rules(&settings), &rule, &request)?);
+            Ok(())
+        }
+        Commands::Undo { run, list } => undo(run, list),
+        Commands::Hook { shell } => {
+            let shell = match shell {
+                Some(shell) => shells::shell_from_path(&shell),
+                None => shells::detect_shell(),
+            };
+            let script = shells::hook_script(&shell)
+                .ok_or_else(|| TheFuckError::unsupported_shell(shell.as_string()))?;
+            print!("{script}");
+            Ok(())
+        }
+        Commands::Stats { top } => {
+            print!("{}", StatsStore::open_default()?.stats()?.report(top));
+            Ok(())
+        }
+        Commands::GenMan { out_dir } => match out_dir {
+            Some(dir) => docs::write_man_pages(&dir),
+            None => docs::render_man_page(&mut std::io::stdout()),
+        },
+        Commands::GenDocs { out_dir } => {
+            let settings = Settings::default();
+            #[allow(clippy::type_complexity)]
+            let mut rules: Vec<RuleInfo> = builtin_rules(&settings)
+                .iter()
+                .map(|rule| RuleInfo::new(rule.as_ref(), &settings))
+                .collect();
+            rules.sort_by(|a, b| a.name.cmp(&b.name));
+            let written = docs::write_rule_pages(&out_dir, &rules)?;
+            println!(
+                "{}",
+                i18n::tr_with(
+                    "docs-written",
+                    &[
+                        ("count", written.into()),
+                        ("dir", out_dir.display().to_string().into())
+                    ]
+                )
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Creates a registry with every built-in and user rule, enabled or not
+fn all_rules(settings: &Settings) -> RuleRegistry {
+    let mut registry = RuleRegistry::new();
+    for rule in builtin_rules(settings) {
+        registry.register(rule);
+    }
+    register_user_rules(&mut registry);
+    registry
+}
+
+/// Prints every built-in and user rule, enabled or not
+#[allow(clippy::type_complexity)]
+fn list_rules(json: bool) -> TheFuckResult<()> {
+    let settings = Settings::load()?;
+    #[allow(clippy::type_complexity)]
+    let mut rules: Vec<RuleInfo> = all_rules(&settings)
+        .rules()
+        .iter()
+        .map(|rule| RuleInfo::new(rule.as_ref(), &settings))
+        .collect();
+    rules.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rules)?);
+        return Ok(());
+    }
+    let width = rules.iter().map(|rule| rule.name.len()).max().unwrap_or(0);
+    println!("{:width$}  ENABLED  PRIORITY  PROGRAMS", "NAME");
+    for rule in &rules {
+        let programs = if rule.programs.is_empty() {
+            "*".to_string()
+        } else {
+            rule.programs.join(", ")
+        };
+        println!(
+            "{:width$}  {:7}  {:8}  {programs}",
+            rule.name,
+            if rule.enabled { "yes" } else { "no" },
+            rule.priority,
+        );
+    }
+    Ok(())
+}
+
+/// Runs one rule against a failed command, describing whether it matched and its corrections
+#[allow(clippy::type_complexity)]
+fn test_rule(
+    registry: &RuleRegistry,
+    name: &str,
+    request: &CorrectionRequest,
+) -> TheFuckResult<String> {
+    let Some(rule) = registry.get(name) else {
+        #[allow(clippy::type_complexity)]
+        let names: Vec<String> = registry
+            .rules()
+            .iter()
+            .map(|rule| rule.name().to_string())
+            .collect();
+        let hint = utils::get_closest(name, &names)
+            .map(|closest| format!(", did you mean `{closest}`?"))
+            .unwrap_or_default();
+        return Err(TheFuckError::validation_error(format!(
+            "Unknown rule `{name}`{hint}"
+        )));
+    };
+
+    let command = request.to_command();
+    let result = request.to_result();
+    let mut report = format!("Rule: {}\n", rule.name());
+    if !rule.is_match(&command, &result) {
+        report.push_str("Matched: no\n");
+        return Ok(report);
+    }
+    report.push_str("Matched: yes\n");
+    let corrections = rule.get_corrections(&command, &result);
+    if corrections.is_empty() {
+        report.push_str("No corrections\n");
+    }
+    for correction in corrections {
+        report.push_str(&format!(
+            "  {} (priority {})\n",
+            correction.text, correction.priority
+        ));
+    }
+    Ok(report)
+}
+
+/// Prints or runs the inverse of the last applied correction, or lists the applied ones
+#[allow(clippy::type_complexity)]
+fn undo(run: bool, list: bool) -> TheFuckResult<()> {
+    let journal = Journal::open_default()?;
+    if list {
+        for entry in journal.entries()?.iter().rev() {
+            let time = chrono::DateTime::from_timestamp(entry.timestamp, 0)
+                .map(|time| {
+                    time.with_timezone(&chrono::Local)
+                        .format("%F %T")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let undo = entry.undo.as_deref().unwrap_or("-");
+            println!(
+                "{time}  {} -> {}  (undo: {undo})",
+                entry.original, entry.corrected
+            );
+        }
+        return Ok(());
+    }
+
+    let entry = journal
+        .last()?
+        .ok_or_else(|| TheFuckError::validation_error("No correction to undo"))?;
+    for side_effect in &entry.side_effects {
+        eprintln!(
+            "{}",
+            i18n::tr_with(
+                "undo-by-hand",
+                &[("side-effect", side_effect.as_str().into())]
+            )
+        );
+    }
+    entry.check_undo()?;
+    let Some(inverse) = entry.undo else {
+        return Err(TheFuckError::validation_error(format!(
+            "Don't know how to undo `{}`",
+            entry.corrected
+        )));
+    };
+    if !run {
+        println!("{inverse}");
+        return Ok(());
+    }
+
+    let (program, flag) = shells::run_args(&shells::detect_shell());
+    let mut command = std::process::Command::new(program);
+    command.arg(flag).arg(&inverse);
+    if !entry.cwd.is_empty() {
+        command.current_dir(&entry.cwd);
+    }
+    let status = command.status()?;
+    if !status.success() {
+        return Err(TheFuckError::process_error(format!(
+            "`{inverse}` failed with {status}"
+        )));
+    }
+    journal.pop()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_failed_request_with_output() {
+        let cli = Cli::parse_from([
+            "thefuck-rs",
+            "--stderr",
+            "not a git command",
+            "--exit-code",
+            "-1",
+            "git",
+            "psuh",
+        ]);
+        let request = failed_request(&cli).unwrap();
+        assert_eq!(request.command, "git psuh");
+        assert_eq!(request.stderr, "not a git command");
+        assert_eq!(request.exit_code, -1);
+    }
+
+    #[test]
+    fn test_failed_request_with_stderr_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("stderr");
+        std::fs::write(&file, "not a git command").unwrap();
+        let cli = Cli::parse_from([
+            "thefuck-rs",
+            "--stderr-file",
+            &file.to_string_lossy(),
+            "--force-command",
+            "git psuh",
+            "git",
+            "push",
+        ]);
+        let request = failed_request(&cli).unwrap();
+        assert_eq!(request.command, "git psuh");
+        assert_eq!(request.stderr, "not a git command");
+        assert_eq!(request.exit_code, 1);
+
+        let cli = Cli::parse_from([
+            "thefuck-rs",
+            "--stderr-file",
+            &dir.path().join("missing").to_string_lossy(),
+            "ls",
+        ]);
+        assert!(matches!(
+            failed_request(&cli),
+            Err(TheFuckError::FileSystemError(_))
+        ));
+    }
+
+    #[test]
+    fn test_test_rule() {
+        let registry = all_rules(&Settings::default());
+        let request = CorrectionRequest {
+            command: "java Main.class".to_string(),
+            exit_code: 1,
+            ..CorrectionRequest::default()
+        };
+        assert_eq!(
+            test_rule(&registry, "java", &request).unwrap(),
+            "Rule: java\nMatched: yes\n  java Main (priority 1000)\n"
+        );
+        assert_eq!(
+            test_rule(&registry, "javac", &request).unwrap(),
+            "Rule: javac\nMatched: no\n"
+        );
+
+        let error = test_rule(&registry, "jvaa", &request).unwrap_err();
+        assert!(error.to_string().contains("did you mean `java`?"));
+    }
+
+    #[test]
+    fn test_stderr_conflicts_with_stdin_json() {
+        assert!(Cli::try_parse_from(["thefuck-rs", "--stdin-json", "--stderr", "oops"]).is_err());
+    }
+}
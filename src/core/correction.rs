@@ -0,0 +1,350 @@
+use crate::config::Settings;
+use crate::rules::{GIT_PROGRAMS, Ranking, RuleRegistry, is_app};
+use crate::types::{Command, CommandResult, CorrectedCommand};
+use crate::{TheFuckError, TheFuckResult, shells, utils};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command as ProcessCommand;
+use std::time::{Duration, Instant};
+
+/// Time a failed command gets to run again before it's killed
+pub const RERUN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Name of the file holding the last command line in the shell hook's capture directory
+pub const CAPTURE_COMMAND: &str = "command";
+
+/// Name of the file holding the last command's exit code in the capture directory
+pub const CAPTURE_EXIT_CODE: &str = "exit_code";
+
+/// Name of the file holding the last command's output in the capture directory
+pub const CAPTURE_OUTPUT: &str = "output";
+
+/// A failed command and its output, to be corrected
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CorrectionRequest {
+    /// The failed command line
+    pub command: String,
+    /// Standard output of the command
+    pub stdout: String,
+    /// Standard error of the command
+    pub stderr: String,
+    /// Exit code of the command
+    pub exit_code: i32,
+    /// Working directory of the command, the current one if unset
+    #[allow(clippy::type_complexity)]
+    pub cwd: Option<String>,
+    /// Environment variables of the command
+    #[allow(clippy::type_complexity)]
+    pub env: HashMap<String, String>,
+    /// Shell that ran the command, the detected one if unset
+    #[allow(clippy::type_complexity)]
+    pub shell: Option<String>,
+}
+
+impl CorrectionRequest {
+    /// Parses a request from JSON, e.g. piped in by a shell integration
+    #[allow(clippy::type_complexity)]
+    pub fn from_json(json: &str) -> TheFuckResult<Self> {
+        let request: Self = serde_json::from_str(json)?;
+        if request.command.trim().is_empty() {
+            return Err(TheFuckError::parse_error("No command provided to fix"));
+        }
+        Ok(request)
+    }
+
+    /// Runs a failed command again to capture its output
+    #[allow(clippy::type_complexity)]
+    pub fn rerun(command: &str) -> TheFuckResult<Self> {
+        let shell = shells::detect_shell();
+        let (program, flag) = shells::run_args(&shell);
+        let mut process = ProcessCommand::new(program);
+        process.arg(flag).arg(command);
+        // Git then reports the aliases it expands, for the rules wrapped in `git_support`
+        if is_app(
+            &Command::new(command.to_string(), shell.clone()),
+            GIT_PROGRAMS,
+        ) {
+            process.env("GIT_TRACE", "1");
+        }
+        let started = Instant::now();
+        let output = utils::run_with_timeout(&mut process, "", RERUN_TIMEOUT)?;
+        tracing::debug!(
+            "reran `{command}` in {:?}{}",
+            started.elapsed(),
+            if output.timed_out { " (timed out)" } else { "" }
+        );
+
+        Ok(Self {
+            command: command.to_string(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+            exit_code: output.status.code().unwrap_or(1),
+            cwd: std::env::current_dir()
+                .ok()
+                .map(|dir| dir.to_string_lossy().to_string()),
+            env: std::env::vars().collect(),
+            shell: Some(shell.as_string()),
+        })
+    }
+
+    /// Gets a failed command from what the shell hook captured, if it captured this command
+    ///
+    /// The hook merges stdout and stderr, so the output is all on stderr. Captures without
+    /// output, like the fish hook's, are ignored since rules need the output.
+    #[allow(clippy::type_complexity)]
+    pub fn from_capture(dir: &Path, command: &str) -> Option<Self> {
+        if !is_private(dir) {
+            tracing::warn!(
+                "ignoring the capture directory {}: others can write to it",
+                dir.display()
+            );
+            return None;
+        }
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+        let captured = read(CAPTURE_COMMAND)?;
+        if captured.trim() != command.trim() {
+            tracing::debug!("captured `{}` instead of `{command}`", captured.trim());
+            return None;
+        }
+        let exit_code = read(CAPTURE_EXIT_CODE)?.trim().parse().ok()?;
+        let output = read(CAPTURE_OUTPUT)?;
+        Some(Self {
+            command: command.to_string(),
+            stderr: output,
+            exit_code,
+            cwd: std::env::current_dir()
+                .ok()
+                .map(|dir| dir.to_string_lossy().to_string()),
+            env: std::env::vars().collect(),
+            ..Self::default()
+        })
+    }
+
+    /// Gets the failed command
+    pub fn to_command(&self) -> Command {
+        let shell = match &self.shell {
+            Some(shell) => shells::shell_from_path(shell),
+            None => shells::detect_shell(),
+        };
+        let command = Command::new(self.command.clone(), shell).with_env(self.env.clone());
+        match &self.cwd {
+            Some(cwd) => command.with_cwd(cwd.clone()),
+            None => command,
+        }
+    }
+
+    /// Gets the result of the failed command
+    pub fn to_result(&self) -> CommandResult {
+        CommandResult::new(
+            self.exit_code == 0,
+            self.exit_code,
+            self.stdout.clone(),
+            self.stderr.clone(),
+            0,
+        )
+    }
+}
+
+/// Checks that only the user can write to a capture directory, so no one else can plant output
+#[cfg(unix)]
+fn is_private(dir: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(dir).is_ok_and(|metadata| {
+        metadata.is_dir()
+            // SAFETY: getuid has no preconditions and can't fail
+            && metadata.uid() == unsafe { libc::getuid() }
+            && metadata.mode() & 0o077 == 0
+    })
+}
+
+/// Checks that only the user can write to a capture directory, which the ACLs of the user's
+/// temporary directory already ensure on Windows
+#[cfg(not(unix))]
+fn is_private(dir: &Path) -> bool {
+    dir.is_dir()
+}
+
+/// Creates a registry with the enabled built-in rules and the user's rules
+pub fn load_registry(settings: &Settings) -> RuleRegistry {
+    let mut registry = RuleRegistry::from_settings(settings);
+    register_user_rules(&mut registry);
+    registry.exclude(&settings.exclude_rules);
+    registry
+}
+
+/// Registers the user's rules from the rules, plugins and legacy rules directories
+pub fn register_user_rules(registry: &mut RuleRegistry) {
+    if let Some(dir) = Settings::rules_dir() {
+        registry.register_declarative_rules(&dir);
+        #[cfg(feature = "rhai-rules")]
+        registry.register_scripts(&dir);
+    }
+    #[cfg(feature = "wasm-plugins")]
+    if let Some(dir) = Settings::plugins_dir() {
+        if let Err(e) = registry.register_plugins(&dir) {
+            tracing::warn!("failed to load plugins: {e}");
+        }
+    }
+    #[cfg(feature = "python-rules")]
+    if let Some(dir) = Settings::legacy_rules_dir() {
+        registry.register_python_rules(&dir);
+    }
+}
+
+/// Gets the corrections of a failed command, boosted by the user's previous picks if known
+#[allow(clippy::type_complexity)]
+pub fn get_corrections(
+    registry: &RuleRegistry,
+    request: &CorrectionRequest,
+    ranking: Option<&dyn Ranking>,
+) -> Vec<CorrectedCommand> {
+    let command = request.to_command();
+    let result = request.to_result();
+    match ranking {
+        Some(ranking) => registry.get_ranked_corrections(&command, &result, ranking),
+        None => registry.get_corrections(&command, &result),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_from_json() {
+        let request: CorrectionRequest = serde_json::from_str(
+            r#"{"command": "git psuh", "stderr": "not a git command", "exit_code": 1}"#,
+        )
+        .unwrap();
+        assert_eq!(request.command, "git psuh");
+        assert_eq!(request.stdout, "");
+        assert_eq!(request.cwd, None);
+
+        let result = request.to_result();
+        assert!(!result.success);
+        assert_eq!(result.output(), "not a git command");
+    }
+
+    #[test]
+    fn test_from_json() {
+        let request = CorrectionRequest::from_json(
+            r#"{"command": "git psuh", "stdout": "", "stderr": "not a git command",
+                "exit_code": 1, "cwd": "/tmp", "env": {"LANG": "C"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.cwd.as_deref(), Some("/tmp"));
+        assert_eq!(request.env.get("LANG").map(String::as_str), Some("C"));
+
+        assert!(matches!(
+            CorrectionRequest::from_json(r#"{"command": " ", "exit_code": 1}"#),
+            Err(TheFuckError::ParseError(_))
+        ));
+        assert!(CorrectionRequest::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_to_command() {
+        let request = CorrectionRequest {
+            command: "git psuh".to_string(),
+            cwd: Some("/tmp".to_string()),
+            shell: Some("/usr/bin/fish".to_string()),
+            ..CorrectionRequest::default()
+        };
+        let command = request.to_command();
+        assert_eq!(command.cwd, "/tmp");
+        assert_eq!(command.shell, crate::types::Shell::Fish);
+    }
+
+    /// Creates a directory only the user can write to, like the shell hooks' capture directory
+    fn private_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        #[cfg(unix)]
+        std::fs::set_permissions(
+            dir.path(),
+            std::os::unix::fs::PermissionsExt::from_mode(0o700),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_from_capture() {
+        let dir = private_dir();
+        assert_eq!(
+            CorrectionRequest::from_capture(dir.path(), "git psuh"),
+            None
+        );
+
+        std::fs::write(dir.path().join(CAPTURE_COMMAND), "git psuh\n").unwrap();
+        std::fs::write(dir.path().join(CAPTURE_EXIT_CODE), "1").unwrap();
+        assert_eq!(
+            CorrectionRequest::from_capture(dir.path(), "git psuh"),
+            None
+        );
+
+        std::fs::write(dir.path().join(CAPTURE_OUTPUT), "not a git command").unwrap();
+        let request = CorrectionRequest::from_capture(dir.path(), "git psuh").unwrap();
+        assert_eq!(request.stderr, "not a git command");
+        assert_eq!(request.exit_code, 1);
+        assert_eq!(
+            CorrectionRequest::from_capture(dir.path(), "git stauts"),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_capture_ignores_shared_directory() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = private_dir();
+        std::fs::write(dir.path().join(CAPTURE_COMMAND), "git psuh").unwrap();
+        std::fs::write(dir.path().join(CAPTURE_EXIT_CODE), "1").unwrap();
+        std::fs::write(dir.path().join(CAPTURE_OUTPUT), "not a git command").unwrap();
+        assert!(CorrectionRequest::from_capture(dir.path(), "git psuh").is_some());
+
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o777)).unwrap();
+        assert_eq!(
+            CorrectionRequest::from_capture(dir.path(), "git psuh"),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rerun() {
+        let request = CorrectionRequest::rerun("echo out; echo err >&2; exit 3").unwrap();
+        assert_eq!(request.stdout, "out\n");
+        assert_eq!(request.stderr, "err\n");
+        assert_eq!(request.exit_code, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rerun_traces_git() {
+        let Ok(request) = CorrectionRequest::rerun("git --version") else {
+            return;
+        };
+        if request.exit_code != 0 {
+            return;
+        }
+        assert!(request.stderr.contains("trace: "), "{}", request.stderr);
+        let request = CorrectionRequest::rerun("echo $GIT_TRACE").unwrap();
+        assert_eq!(request.stdout, "\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rerun_kills_what_the_command_started() {
+        let started = Instant::now();
+        let request = CorrectionRequest::rerun("sleep 5; true").unwrap();
+        let elapsed = started.elapsed();
+        assert!(elapsed >= RERUN_TIMEOUT, "{elapsed:?}");
+        assert!(
+            elapsed < RERUN_TIMEOUT + Duration::from_secs(1),
+            "{elapsed:?}"
+        );
+        assert_ne!(request.exit_code, 0);
+    }
+}
@@ -1,36 +1,14 @@
-use crate::{TheFuckError, TheFuckResult, cli::Cli};
-
-pub async fn run(cli: Cli) -> TheFuckResult<()> {
-    // Handle alias request
-    if cli.is_alias_request() {
-        println!("alias fuck='eval $(thefuck-rs $(fc -ln -1 | tail -n1); fc -R)'");
-        return Ok(());
-    }
-
-    // Handle shell logger request
-    if cli.is_shell_logger_request() {
-        if let Some(log_file) = cli.shell_logger {
-            println!("Shell logging to: {log_file}");
-            // TODO: Implement shell logging functionality
-            return Ok(());
-        }
-        return Err(TheFuckError::config_error(
-            "Shell logger file not specified",
-        ));
-    }
-
-    // Handle command fix request
-    if cli.is_command_fix_request() {
-        if !cli.command.is_empty() {
-            println!("Fixing command: {:?}", cli.command);
-            // TODO: Implement command fixing logic
-            return Ok(());
-        } else {
-            return Err(TheFuckError::parse_error("No command provided to fix"));
-        }
-    }
-
-    // Default: show help
-    println!("Use --help for usage information");
-    Ok(())
-}
+//! Correcting failed commands, and the command-line application doing it.
+//!
+//! The application needs a terminal, a process runtime and SQLite, so it's left out of wasm32
+//! builds, where only the correction of requests is available.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod app;
+mod correction;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use app::run;
+pub use correction::{
+    CorrectionRequest, RERUN_TIMEOUT, get_corrections, load_registry, register_user_rules,
+};
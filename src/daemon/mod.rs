@@ -0,0 +1,338 @@
+//! Background server that keeps the rules and caches warm between corrections.
+//!
+//! The daemon listens on a Unix socket (a named pipe on Windows) for correction requests, one
+//! JSON object per line, and answers each with a JSON line of corrections. The CLI uses it
+//! whenever it's running and falls back to correcting in-process otherwise.
+
+use crate::config::Settings;
+use crate::core::{self, CorrectionRequest};
+use crate::learning::LearningStore;
+use crate::rules::{Ranking, RuleRegistry};
+use crate::types::CorrectedCommand;
+use crate::{TheFuckError, TheFuckResult, utils};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// Time the CLI waits to connect to the daemon before correcting in-process
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Time the CLI waits for the daemon's corrections
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Answer of the daemon to a correction request
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonResponse {
+    /// Corrections of the failed command, most likely first
+    #[allow(clippy::type_complexity)]
+    pub corrections: Vec<CorrectedCommand>,
+    /// Why the request couldn't be handled
+    #[allow(clippy::type_complexity)]
+    pub error: Option<String>,
+}
+
+/// Rules and stores shared by every connection to the daemon
+pub struct DaemonState {
+    registry: RuleRegistry,
+    #[allow(clippy::type_complexity)]
+    store: Mutex<Option<LearningStore>>,
+}
+
+impl DaemonState {
+    /// Creates the state from a registry and an optional learning store
+    #[allow(clippy::type_complexity)]
+    pub fn new(registry: RuleRegistry, store: Option<LearningStore>) -> Self {
+        Self {
+            registry,
+            store: Mutex::new(store),
+        }
+    }
+
+    /// Loads the settings, the user's rules and the learning store, and warms up the caches
+    #[allow(clippy::type_complexity)]
+    pub fn load() -> TheFuckResult<Self> {
+        let settings = Settings::load()?;
+        let registry = core::load_registry(&settings);
+        let store = LearningStore::open_default().ok();
+        utils::all_executables();
+        Ok(Self::new(registry, store))
+    }
+
+    /// Gets the corrections of a failed command
+    #[allow(clippy::type_complexity)]
+    pub fn correct(&self, request: &CorrectionRequest) -> Vec<CorrectedCommand> {
+        let store = self.store.lock().unwrap_or_else(|p| p.into_inner());
+        core::get_corrections(
+            &self.registry,
+            request,
+            store.as_ref().map(|store| store as &dyn Ranking),
+        )
+    }
+}
+
+/// Answers the correction requests of one connection until it's closed
+#[allow(clippy::type_complexity)]
+async fn handle<S>(stream: S, state: Arc<DaemonState>) -> TheFuckResult<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        let response = match serde_json::from_str::<CorrectionRequest>(&line) {
+            Ok(request) => {
+                let state = Arc::clone(&state);
+                match tokio::task::spawn_blocking(move || state.correct(&request)).await {
+                    Ok(corrections) => DaemonResponse {
+                        corrections,
+                        error: None,
+                    },
+                    Err(e) => DaemonResponse {
+                        corrections: Vec::new(),
+                        error: Some(e.to_string()),
+                    },
+                }
+            }
+            Err(e) => DaemonResponse {
+                corrections: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        };
+        let mut json = serde_json::to_string(&response)?;
+        json.push('\n');
+        writer.write_all(json.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+/// Sends a correction request over a connection and reads the corrections
+#[allow(clippy::type_complexity)]
+async fn exchange<S>(stream: S, request: &CorrectionRequest) -> TheFuckResult<Vec<CorrectedCommand>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut json = serde_json::to_string(request)?;
+    json.push('\n');
+    writer.write_all(json.as_bytes()).await?;
+
+    let line = BufReader::new(reader)
+        .lines()
+        .next_line()
+        .await?
+        .ok_or_else(|| TheFuckError::process_error("daemon closed the connection"))?;
+    let response: DaemonResponse = serde_json::from_str(&line)?;
+    match response.error {
+        Some(error) => Err(TheFuckError::process_error(error)),
+        None => Ok(response.corrections),
+    }
+}
+
+/// Gets corrections from the daemon, or `None` if it isn't running or didn't answer
+#[allow(clippy::type_complexity)]
+pub async fn request(request: &CorrectionRequest) -> Option<Vec<CorrectedCommand>> {
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, platform::connect())
+        .await
+        .ok()??;
+    match tokio::time::timeout(RESPONSE_TIMEOUT, exchange(stream, request)).await {
+        Ok(Ok(corrections)) => Some(corrections),
+        Ok(Err(e)) => {
+            tracing::warn!("daemon failed to correct: {e}");
+            None
+        }
+        Err(_) => {
+            tracing::warn!("daemon didn't answer in time");
+            None
+        }
+    }
+}
+
+/// Serves correction requests until interrupted
+#[allow(clippy::type_complexity)]
+pub async fn serve(state: DaemonState) -> TheFuckResult<()> {
+    platform::serve(Arc::new(state)).await
+}
+
+#[cfg(unix)]
+mod platform {
+    use super::{DaemonState, handle};
+    use crate::config::Settings;
+    use crate::{TheFuckError, TheFuckResult};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use tokio::net::{UnixListener, UnixStream};
+
+    /// Name of the daemon's socket
+    const SOCKET_NAME: &str = "thefuck-rs.sock";
+
+    /// Gets the path of the daemon's socket, in the runtime directory if there is one
+    #[allow(clippy::type_complexity)]
+    pub(super) fn socket_path() -> Option<PathBuf> {
+        dirs::runtime_dir()
+            .map(|dir| dir.join(SOCKET_NAME))
+            .or_else(|| Settings::cache_dir().map(|dir| dir.join(SOCKET_NAME)))
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(super) async fn connect() -> Option<UnixStream> {
+        UnixStream::connect(socket_path()?).await.ok()
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(super) async fn serve(state: Arc<DaemonState>) -> TheFuckResult<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = socket_path()
+            .ok_or_else(|| TheFuckError::config_error("No directory for the daemon's socket"))?;
+        if UnixStream::connect(&path).await.is_ok() {
+            return Err(TheFuckError::process_error(format!(
+                "daemon is already running on {}",
+                path.display()
+            )));
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // A socket left over by a daemon that didn't shut down cleanly
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+        eprintln!(
+            "{}",
+            crate::i18n::tr_with(
+                "daemon-listening",
+                &[("address", path.display().to_string().into())]
+            )
+        );
+
+        let result = loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        let state = Arc::clone(&state);
+                        tokio::spawn(async move {
+                            if let Err(e) = handle(stream, state).await {
+                                tracing::warn!("daemon connection failed: {e}");
+                            }
+                        });
+                    }
+                    Err(e) => break Err(e.into()),
+                },
+                _ = tokio::signal::ctrl_c() => break Ok(()),
+            }
+        };
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::{DaemonState, handle};
+    use crate::TheFuckResult;
+    use std::sync::Arc;
+    use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeClient, ServerOptions};
+
+    /// Gets the name of the daemon's pipe, which is per user
+    fn pipe_name() -> String {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        format!(r"\\.\pipe\thefuck-rs-{user}")
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(super) async fn connect() -> Option<NamedPipeClient> {
+        ClientOptions::new().open(pipe_name()).ok()
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(super) async fn serve(state: Arc<DaemonState>) -> TheFuckResult<()> {
+        let name = pipe_name();
+        let mut server = ServerOptions::new()
+            .first_pipe_instance(true)
+            .create(&name)?;
+        eprintln!(
+            "{}",
+            crate::i18n::tr_with("daemon-listening", &[("address", name.as_str().into())])
+        );
+        loop {
+            tokio::select! {
+                connected = server.connect() => {
+                    connected?;
+                    let client = std::mem::replace(&mut server, ServerOptions::new().create(&name)?);
+                    let state = Arc::clone(&state);
+                    tokio::spawn(async move {
+                        if let Err(e) = handle(client, state).await {
+                            tracing::warn!("daemon connection failed: {e}");
+                        }
+                    });
+                }
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rule;
+    use crate::types::{Command, CommandResult};
+
+    struct PushRule;
+
+    impl Rule for PushRule {
+        fn name(&self) -> &'static str {
+            "push"
+        }
+
+        fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+            command.text == "git psuh" && !result.success
+        }
+
+        fn get_new_commands(&self, _command: &Command, _result: &CommandResult) -> Vec<String> {
+            vec!["git push".to_string()]
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn state() -> Arc<DaemonState> {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(PushRule));
+        Arc::new(DaemonState::new(registry, None))
+    }
+
+    #[tokio::test]
+    async fn test_exchange() {
+        let (client, server) = tokio::io::duplex(4096);
+        let server = tokio::spawn(handle(server, state()));
+
+        let request = CorrectionRequest {
+            command: "git psuh".to_string(),
+            exit_code: 1,
+            ..CorrectionRequest::default()
+        };
+        let corrections = exchange(client, &request).await.unwrap();
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].text, "git push");
+        assert_eq!(corrections[0].rule, "push");
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request() {
+        let (mut client, server) = tokio::io::duplex(4096);
+        let server = tokio::spawn(handle(server, state()));
+
+        client.write_all(b"not json\n").await.unwrap();
+        let mut lines = BufReader::new(&mut client).lines();
+        let response: DaemonResponse =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert!(response.error.is_some());
+        assert!(response.corrections.is_empty());
+        drop(lines);
+        drop(client);
+        server.await.unwrap().unwrap();
+    }
+}
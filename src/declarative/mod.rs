@@ -0,0 +1,301 @@
+//! User rules defined in TOML.
+//!
+//! Each `*.toml` file in the rules directory defines one rule, e.g.
+//!
+//! ```toml
+//! program = "git"
+//! pattern = 'git push --set-upstream (\S+) (\S+)'
+//! replacement = "git push --set-upstream $1 $2"
+//! ```
+//!
+//! The rule matches when the command runs `program` (any program if omitted) and `pattern`
+//! matches the command's stderr (`stream = "stdout"` or `"output"` to look elsewhere). The
+//! replacement expands capture groups (`$1`, `${name}`; `$$` for a literal `$`) and
+//! `{command}` to the failed command. The rule is named after the file stem unless `name`
+//! is set, and `priority` overrides the default priority.
+
+use crate::rules::{DEFAULT_PRIORITY, Rule};
+use crate::types::{Command, CommandResult};
+use crate::{TheFuckError, TheFuckResult};
+use regex::Regex;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Extension of declarative rule files in the rules directory
+pub const RULE_EXTENSION: &str = "toml";
+
+/// Placeholder for the failed command in replacements
+const COMMAND_PLACEHOLDER: &str = "{command}";
+
+/// Output of the failed command that a pattern is matched against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stream {
+    /// Standard error
+    #[default]
+    Stderr,
+    /// Standard output
+    Stdout,
+    /// Both stdout and stderr
+    Output,
+}
+
+/// A declarative rule as written in its file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Definition {
+    #[allow(clippy::type_complexity)]
+    name: Option<String>,
+    #[allow(clippy::type_complexity)]
+    program: Option<String>,
+    pattern: String,
+    replacement: String,
+    #[serde(default)]
+    stream: Stream,
+    #[allow(clippy::type_complexity)]
+    priority: Option<u32>,
+}
+
+/// A rule that rewrites commands whose output matches a regex
+#[derive(Debug)]
+pub struct DeclarativeRule {
+    name: &'static str,
+    #[allow(clippy::type_complexity)]
+    program: Option<&'static str>,
+    pattern: Regex,
+    replacement: String,
+    stream: Stream,
+    priority: u32,
+}
+
+impl DeclarativeRule {
+    /// Parses a rule from TOML, naming it `name` unless the definition sets one
+    #[allow(clippy::type_complexity)]
+    pub fn from_toml(name: &str, content: &str) -> TheFuckResult<Self> {
+        let definition: Definition = toml::from_str(content)?;
+        let pattern = Regex::new(&definition.pattern)
+            .map_err(|e| TheFuckError::validation_error(format!("{name}: {e}")))?;
+        let name = definition.name.unwrap_or_else(|| name.to_string());
+        Ok(Self {
+            // Rule names are static, and rules are loaded once per process
+            name: Box::leak(name.into_boxed_str()),
+            program: definition
+                .program
+                .map(|program| &*Box::leak(program.into_boxed_str())),
+            pattern,
+            replacement: definition.replacement,
+            stream: definition.stream,
+            priority: definition.priority.unwrap_or(DEFAULT_PRIORITY),
+        })
+    }
+
+    /// Loads a rule from a file, naming it after the file stem unless the definition sets one
+    #[allow(clippy::type_complexity)]
+    pub fn from_file(path: &Path) -> TheFuckResult<Self> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .ok_or_else(|| TheFuckError::path_error(path.display().to_string()))?;
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| TheFuckError::ConfigLoadError(format!("{}: {e}", path.display())))?;
+        Self::from_toml(&name, &content)
+    }
+
+    /// Loads every rule in a directory, sorted by file name and skipping invalid ones
+    #[allow(clippy::type_complexity)]
+    pub fn load_dir(dir: &Path) -> Vec<Self> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        #[allow(clippy::type_complexity)]
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension == RULE_EXTENSION)
+            })
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .filter_map(|path| match Self::from_file(path) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    tracing::warn!("skipping rule {}: {e}", path.display());
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Gets the output the pattern is matched against
+    fn text(&self, result: &CommandResult) -> String {
+        match self.stream {
+            Stream::Stderr => result.stderr.clone(),
+            Stream::Stdout => result.stdout.clone(),
+            Stream::Output => result.output(),
+        }
+    }
+}
+
+impl Rule for DeclarativeRule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        match &self.program {
+            Some(program) => std::slice::from_ref(program),
+            None => &[],
+        }
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let program_matches = match self.program {
+            Some(program) => command.program() == Some(program),
+            None => true,
+        };
+        program_matches && self.pattern.is_match(&self.text(result))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn matched_pattern(&self, _command: &Command, result: &CommandResult) -> Option<String> {
+        let text = self.text(result);
+        let matched = self.pattern.find(&text)?;
+        Some(format!("/{}/ matched `{}`", self.pattern, matched.as_str()))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let text = self.text(result);
+        let Some(captures) = self.pattern.captures(&text) else {
+            return Vec::new();
+        };
+        #[allow(clippy::type_complexity)]
+        let parts: Vec<String> = self
+            .replacement
+            .split(COMMAND_PLACEHOLDER)
+            .map(|part| {
+                let mut expanded = String::new();
+                captures.expand(part, &mut expanded);
+                expanded
+            })
+            .collect();
+        let new_command = parts.join(&command.text);
+        if new_command.trim().is_empty() {
+            Vec::new()
+        } else {
+            vec![new_command.trim().to_string()]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const GIT_PUSH: &str = r#"
+        program = "git"
+        pattern = 'git push --set-upstream (\S+) (?P<branch>\S+)'
+        replacement = "git push --set-upstream $1 ${branch}"
+        priority = 1100
+    "#;
+
+    const PUSH_OUTPUT: &str = "fatal: The current branch feature has no upstream branch.\n\
+        To push the current branch and set the remote as upstream, use\n\n    \
+        git push --set-upstream origin feature\n";
+
+    #[test]
+    fn test_from_toml() {
+        let rule = DeclarativeRule::from_toml("git_push", GIT_PUSH).unwrap();
+        assert_eq!(rule.name(), "git_push");
+        assert_eq!(rule.priority(), 1100);
+        assert_eq!(rule.stream, Stream::Stderr);
+
+        let named =
+            "name = \"upstream\"\npattern = \"x\"\nreplacement = \"y\"\nstream = \"output\"";
+        let rule = DeclarativeRule::from_toml("file", named).unwrap();
+        assert_eq!(rule.name(), "upstream");
+        assert_eq!(rule.priority(), DEFAULT_PRIORITY);
+        assert_eq!(rule.stream, Stream::Output);
+
+        assert!(matches!(
+            DeclarativeRule::from_toml("bad", "pattern = \"(\"\nreplacement = \"\""),
+            Err(TheFuckError::ValidationError(_))
+        ));
+        assert!(DeclarativeRule::from_toml("bad", "pattern = \"x\"").is_err());
+        assert!(
+            DeclarativeRule::from_toml("bad", "pattern = \"x\"\nreplacement = \"y\"\nfoo = 1")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_match() {
+        let rule = DeclarativeRule::from_toml("git_push", GIT_PUSH).unwrap();
+        let result = CommandResult::failure(128, PUSH_OUTPUT.to_string());
+        assert!(rule.is_match(&Command::new("git push".to_string(), Shell::Bash), &result));
+        assert!(!rule.is_match(&Command::new("hg push".to_string(), Shell::Bash), &result));
+        assert!(!rule.is_match(
+            &Command::new("git push".to_string(), Shell::Bash),
+            &CommandResult::success(PUSH_OUTPUT.to_string())
+        ));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let rule = DeclarativeRule::from_toml("git_push", GIT_PUSH).unwrap();
+        let command = Command::new("git push".to_string(), Shell::Bash);
+        let result = CommandResult::failure(128, PUSH_OUTPUT.to_string());
+        assert_eq!(
+            rule.get_new_commands(&command, &result),
+            vec!["git push --set-upstream origin feature"]
+        );
+
+        let sudo = "pattern = 'Permission denied'\nreplacement = 'sudo {command}'";
+        let rule = DeclarativeRule::from_toml("sudo", sudo).unwrap();
+        let command = Command::new("cat $HOME/secret".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "cat: secret: Permission denied".to_string());
+        assert_eq!(
+            rule.get_new_commands(&command, &result),
+            vec!["sudo cat $HOME/secret"]
+        );
+    }
+
+    #[test]
+    fn test_matched_pattern() {
+        let sudo = "pattern = 'Permission \\w+'\nreplacement = 'sudo {command}'";
+        let rule = DeclarativeRule::from_toml("sudo", sudo).unwrap();
+        let command = Command::new("cat secret".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "cat: secret: Permission denied".to_string());
+        assert_eq!(
+            rule.matched_pattern(&command, &result).as_deref(),
+            Some(r"/Permission \w+/ matched `Permission denied`")
+        );
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("git_push.toml"), GIT_PUSH).unwrap();
+        std::fs::write(dir.path().join("broken.toml"), "pattern = ").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), GIT_PUSH).unwrap();
+
+        #[allow(clippy::type_complexity)]
+        let names: Vec<&str> = DeclarativeRule::load_dir(dir.path())
+            .iter()
+            .map(|rule| rule.name())
+            .collect();
+        assert_eq!(names, vec!["git_push"]);
+        assert!(DeclarativeRule::load_dir(&dir.path().join("missing")).is_empty());
+    }
+}
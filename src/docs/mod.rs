@@ -0,0 +1,138 @@
+//! Documentation generated for packagers: man pages from the command line definition, and a
+//! markdown reference page per rule from the rules' metadata.
+
+use crate::TheFuckResult;
+use crate::cli::Cli;
+use crate::rules::RuleInfo;
+use clap::CommandFactory;
+use std::io::Write;
+use std::path::Path;
+
+/// Name of the rules index among the rule pages
+pub const RULES_INDEX: &str = "index.md";
+
+/// Renders the man page of the main command
+#[allow(clippy::type_complexity)]
+pub fn render_man_page(output: &mut dyn Write) -> TheFuckResult<()> {
+    clap_mangen::Man::new(Cli::command()).render(output)?;
+    Ok(())
+}
+
+/// Writes the man pages of the main command and every subcommand to a directory
+#[allow(clippy::type_complexity)]
+pub fn write_man_pages(dir: &Path) -> TheFuckResult<()> {
+    std::fs::create_dir_all(dir)?;
+    clap_mangen::generate_to(Cli::command(), dir)?;
+    Ok(())
+}
+
+/// Escapes text for a markdown table cell
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Lists the programs a rule targets as code spans
+fn programs(rule: &RuleInfo) -> String {
+    if rule.programs.is_empty() {
+        return "any".to_string();
+    }
+    rule.programs
+        .iter()
+        .map(|program| format!("`{program}`"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Gets the reference page of a rule
+pub fn rule_page(rule: &RuleInfo) -> String {
+    let mut page = format!("# {}\n\n", rule.name);
+    if !rule.description.is_empty() {
+        page.push_str(&format!("{}\n\n", rule.description));
+    }
+    page.push_str("| | |\n|---|---|\n");
+    page.push_str(&format!("| Programs | {} |\n", programs(rule)));
+    page.push_str(&format!("| Priority | {} |\n", rule.priority));
+    page.push_str(&format!(
+        "| Enabled by default | {} |\n",
+        if rule.enabled { "yes" } else { "no" }
+    ));
+    page
+}
+
+/// Gets the index of the rule pages
+#[allow(clippy::type_complexity)]
+pub fn rules_index(rules: &[RuleInfo]) -> String {
+    let mut index = "# Rules\n\n| Rule | Programs | Description |\n|---|---|---|\n".to_string();
+    for rule in rules {
+        let disabled = if rule.enabled {
+            ""
+        } else {
+            " (disabled by default)"
+        };
+        index.push_str(&format!(
+            "| [{name}]({name}.md) | {} | {}{disabled} |\n",
+            programs(rule),
+            table_cell(&rule.description),
+            name = rule.name,
+        ));
+    }
+    index
+}
+
+/// Writes a page per rule and their index to a directory, getting the number of rule pages
+#[allow(clippy::type_complexity)]
+pub fn write_rule_pages(dir: &Path, rules: &[RuleInfo]) -> TheFuckResult<usize> {
+    std::fs::create_dir_all(dir)?;
+    for rule in rules {
+        std::fs::write(dir.join(format!("{}.md", rule.name)), rule_page(rule))?;
+    }
+    std::fs::write(dir.join(RULES_INDEX), rules_index(rules))?;
+    Ok(rules.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::type_complexity)]
+    fn rule(name: &str, programs: &[&str], enabled: bool) -> RuleInfo {
+        RuleInfo {
+            name: name.to_string(),
+            description: "Runs the class, e.g. `java Foo.class` -> `java Foo`".to_string(),
+            enabled,
+            priority: 1000,
+            programs: programs.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_rule_page() {
+        assert_eq!(
+            rule_page(&rule("java", &["java"], true)),
+            "# java\n\nRuns the class, e.g. `java Foo.class` -> `java Foo`\n\n| | |\n|---|---|\n\
+             | Programs | `java` |\n| Priority | 1000 |\n| Enabled by default | yes |\n"
+        );
+    }
+
+    #[test]
+    fn test_write_rule_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let rules = vec![rule("java", &["java"], true), rule("anything", &[], false)];
+        assert_eq!(write_rule_pages(dir.path(), &rules).unwrap(), 2);
+        assert!(dir.path().join("java.md").exists());
+
+        let index = std::fs::read_to_string(dir.path().join(RULES_INDEX)).unwrap();
+        assert!(index.contains("| [java](java.md) | `java` |"));
+        assert!(index.contains("| [anything](anything.md) | any |"));
+        assert!(index.contains("(disabled by default) |\n"));
+    }
+
+    #[test]
+    fn test_render_man_page() {
+        let mut page = Vec::new();
+        render_man_page(&mut page).unwrap();
+        let page = String::from_utf8(page).unwrap();
+        assert!(page.starts_with(".ie"));
+        assert!(page.contains("thefuck\\-rs"));
+    }
+}
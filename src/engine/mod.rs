@@ -0,0 +1,370 @@
+//! Embeddable correction engine.
+//!
+//! [`CorrectionEngine`] bundles the rules, settings and shell the CLI corrects commands with,
+//! so other tools can correct a failed command without going through the CLI:
+//!
+//! ```no_run
+//! use thefuck_rs::{CorrectionEngine, Shell};
+//!
+//! let engine = CorrectionEngine::builder()
+//!     .shell(Shell::Zsh)
+//!     .exclude_rules(["pacman"])
+//!     .build()
+//!     .unwrap();
+//! for correction in engine.correct("cargo biuld", "error: no such command: `biuld`") {
+//!     println!("{}", correction.text);
+//! }
+//! ```
+
+use crate::config::Settings;
+use crate::core::{CorrectionRequest, get_corrections, register_user_rules};
+use crate::rules::{Ranking, Rule, RuleRegistry, builtin_rules, is_enabled};
+use crate::types::{CorrectedCommand, Shell};
+use crate::{TheFuckError, TheFuckResult, instant, shells};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// How the engine gets the output of a command it's asked to correct without one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputCapture {
+    /// Only correct commands whose output is given
+    #[default]
+    Provided,
+    /// Use what the shell hook or instant mode captured
+    ShellHook,
+    /// Run the command again
+    Rerun,
+    /// Use what the shell hook or instant mode captured, running the command again otherwise
+    ShellHookOrRerun,
+}
+
+impl OutputCapture {
+    /// Captures the output of a failed command this way
+    #[allow(clippy::type_complexity)]
+    pub fn capture(self, command: &str) -> TheFuckResult<CorrectionRequest> {
+        let captured = || {
+            std::env::var_os(shells::CAPTURE_DIR_ENV)
+                .and_then(|dir| CorrectionRequest::from_capture(Path::new(&dir), command))
+                .or_else(|| {
+                    std::env::var_os(instant::OUTPUT_LOG_ENV)
+                        .and_then(|log| instant::captured_request(Path::new(&log), command))
+                })
+        };
+        let missing =
+            || TheFuckError::validation_error(format!("No output was captured for `{command}`"));
+        match self {
+            Self::Provided => Err(missing()),
+            Self::ShellHook => captured().ok_or_else(missing),
+            Self::Rerun => CorrectionRequest::rerun(command),
+            Self::ShellHookOrRerun => match captured() {
+                Some(request) => Ok(request),
+                None => CorrectionRequest::rerun(command),
+            },
+        }
+    }
+}
+
+/// Which rules an engine corrects commands with
+#[derive(Debug, Clone, Default, PartialEq)]
+enum RuleSelection {
+    /// The built-in rules enabled by the settings
+    #[default]
+    Enabled,
+    /// Only the named built-in rules, enabled or not
+    #[allow(clippy::type_complexity)]
+    Only(Vec<String>),
+    /// No built-in rules
+    None,
+}
+
+/// Builds a [`CorrectionEngine`]
+#[derive(Default)]
+pub struct CorrectionEngineBuilder {
+    settings: Settings,
+    selection: RuleSelection,
+    #[allow(clippy::type_complexity)]
+    exclude: Vec<String>,
+    #[allow(clippy::type_complexity)]
+    custom: Vec<Box<dyn Rule>>,
+    user_rules: bool,
+    learning: bool,
+    #[allow(clippy::type_complexity)]
+    shell: Option<Shell>,
+    #[allow(clippy::type_complexity)]
+    cwd: Option<String>,
+    capture: OutputCapture,
+}
+
+impl CorrectionEngineBuilder {
+    /// Configures the rules with settings, e.g. loaded with [`Settings::load`]
+    pub fn settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Only uses the named built-in rules, even those disabled by default
+    #[allow(clippy::type_complexity)]
+    pub fn rules<I: IntoIterator<Item = S>, S: Into<String>>(mut self, names: I) -> Self {
+        self.selection = RuleSelection::Only(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Uses no built-in rules, only the ones added with [`rule`](Self::rule)
+    pub fn without_builtin_rules(mut self) -> Self {
+        self.selection = RuleSelection::None;
+        self
+    }
+
+    /// Never uses the named rules, in addition to the ones the settings exclude
+    #[allow(clippy::type_complexity)]
+    pub fn exclude_rules<I: IntoIterator<Item = S>, S: Into<String>>(mut self, names: I) -> Self {
+        self.exclude.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a rule of the embedding tool
+    pub fn rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.custom.push(Box::new(rule));
+        self
+    }
+
+    /// Also uses the user's rules, scripts and plugins from the configuration directory
+    pub fn user_rules(mut self, enabled: bool) -> Self {
+        self.user_rules = enabled;
+        self
+    }
+
+    /// Ranks corrections by the user's previous picks, as the CLI does
+    pub fn learning(mut self, enabled: bool) -> Self {
+        self.learning = enabled;
+        self
+    }
+
+    /// Sets the shell commands are run in, the detected one by default
+    pub fn shell(mut self, shell: Shell) -> Self {
+        self.shell = Some(shell);
+        self
+    }
+
+    /// Sets the directory commands are run in, the current one by default
+    pub fn cwd(mut self, cwd: impl Into<String>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Sets how the output of commands corrected without one is captured
+    pub fn output_capture(mut self, capture: OutputCapture) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Builds the engine, failing if a selected rule doesn't exist
+    #[allow(clippy::type_complexity)]
+    pub fn build(self) -> TheFuckResult<CorrectionEngine> {
+        let mut registry = RuleRegistry::new();
+        let builtin = builtin_rules(&self.settings);
+        match &self.selection {
+            RuleSelection::Enabled => builtin
+                .into_iter()
+                .filter(|rule| is_enabled(rule.as_ref(), &self.settings))
+                .for_each(|rule| registry.register(rule)),
+            RuleSelection::Only(names) => {
+                if let Some(unknown) = names
+                    .iter()
+                    .find(|name| !builtin.iter().any(|rule| rule.name() == name.as_str()))
+                {
+                    return Err(TheFuckError::validation_error(format!(
+                        "Unknown rule: {unknown}"
+                    )));
+                }
+                builtin
+                    .into_iter()
+                    .filter(|rule| names.iter().any(|name| name == rule.name()))
+                    .for_each(|rule| registry.register(rule));
+            }
+            RuleSelection::None => {}
+        }
+        if self.user_rules {
+            register_user_rules(&mut registry);
+        }
+        for rule in self.custom {
+            registry.register(rule);
+        }
+        registry.exclude(&self.settings.exclude_rules);
+        registry.exclude(&self.exclude);
+
+        let ranking = if self.learning {
+            Some(Mutex::new(learning_store()?))
+        } else {
+            None
+        };
+        Ok(CorrectionEngine {
+            registry,
+            ranking,
+            shell: self.shell.unwrap_or_else(shells::detect_shell),
+            cwd: self.cwd,
+            capture: self.capture,
+        })
+    }
+}
+
+/// Opens the store of the user's previous picks
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::type_complexity)]
+fn learning_store() -> TheFuckResult<Box<dyn Ranking>> {
+    Ok(Box::new(crate::learning::LearningStore::open_default()?))
+}
+
+/// Opens the store of the user's previous picks, which needs SQLite and isn't in wasm32 builds
+#[cfg(target_arch = "wasm32")]
+#[allow(clippy::type_complexity)]
+fn learning_store() -> TheFuckResult<Box<dyn Ranking>> {
+    Err(TheFuckError::validation_error(
+        "Learning isn't available in wasm32 builds",
+    ))
+}
+
+/// Corrects failed commands with a set of rules
+pub struct CorrectionEngine {
+    registry: RuleRegistry,
+    /// Locked so that one engine can serve every thread, the learning store isn't `Sync`
+    #[allow(clippy::type_complexity)]
+    ranking: Option<Mutex<Box<dyn Ranking>>>,
+    shell: Shell,
+    #[allow(clippy::type_complexity)]
+    cwd: Option<String>,
+    capture: OutputCapture,
+}
+
+impl CorrectionEngine {
+    /// Starts building an engine with the enabled built-in rules and default settings
+    pub fn builder() -> CorrectionEngineBuilder {
+        CorrectionEngineBuilder::default()
+    }
+
+    /// Gets the rules the engine corrects commands with
+    pub fn registry(&self) -> &RuleRegistry {
+        &self.registry
+    }
+
+    /// Gets the shell commands are run in
+    pub fn shell(&self) -> &Shell {
+        &self.shell
+    }
+
+    /// Gets the corrections of a failed command from its output, most likely first
+    #[allow(clippy::type_complexity)]
+    pub fn correct(&self, command: &str, output: &str) -> Vec<CorrectedCommand> {
+        self.correct_request(&CorrectionRequest {
+            command: command.to_string(),
+            stderr: output.to_string(),
+            exit_code: 1,
+            ..CorrectionRequest::default()
+        })
+    }
+
+    /// Gets the corrections of a failed command described in full, most likely first
+    ///
+    /// The engine's shell and directory are used when the request doesn't name its own.
+    #[allow(clippy::type_complexity)]
+    pub fn correct_request(&self, request: &CorrectionRequest) -> Vec<CorrectedCommand> {
+        let mut request = request.clone();
+        request.shell.get_or_insert_with(|| self.shell.as_string());
+        if request.cwd.is_none() {
+            request.cwd = self.cwd.clone();
+        }
+        let ranking = self
+            .ranking
+            .as_ref()
+            .map(|ranking| ranking.lock().unwrap_or_else(|p| p.into_inner()));
+        get_corrections(&self.registry, &request, ranking.as_deref().map(|r| &**r))
+    }
+
+    /// Gets the corrections of a failed command, capturing its output as configured
+    #[allow(clippy::type_complexity)]
+    pub fn correct_command(&self, command: &str) -> TheFuckResult<Vec<CorrectedCommand>> {
+        let request = self.capture(command)?;
+        Ok(self.correct_request(&request))
+    }
+
+    /// Captures the output of a failed command as configured
+    #[allow(clippy::type_complexity)]
+    pub fn capture(&self, command: &str) -> TheFuckResult<CorrectionRequest> {
+        self.capture.capture(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Command, CommandResult};
+
+    /// Corrects `sl` to `ls`
+    struct Sl;
+
+    impl Rule for Sl {
+        fn name(&self) -> &'static str {
+            "sl"
+        }
+
+        fn is_match(&self, command: &Command, _result: &CommandResult) -> bool {
+            command.starts_with("sl")
+        }
+
+        #[allow(clippy::type_complexity)]
+        fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+            vec![command.replace_command("ls")]
+        }
+    }
+
+    const TERRAFORM_OUTPUT: &str =
+        "Terraform has no command named \"pla\". Did you mean \"plan\"?\n";
+
+    #[test]
+    fn test_correct() {
+        let engine = CorrectionEngine::builder()
+            .rules(["terraform_no_command"])
+            .shell(Shell::Zsh)
+            .cwd("/tmp")
+            .build()
+            .unwrap();
+        assert_eq!(engine.registry().len(), 1);
+        let corrections = engine.correct("terraform pla", TERRAFORM_OUTPUT);
+        assert_eq!(corrections[0].text, "terraform plan");
+        assert_eq!(corrections[0].original.shell, Shell::Zsh);
+        assert_eq!(corrections[0].original.cwd, "/tmp");
+        assert!(engine.correct("terraform plan", "").is_empty());
+    }
+
+    #[test]
+    fn test_rule_selection() {
+        let engine = CorrectionEngine::builder()
+            .without_builtin_rules()
+            .rule(Sl)
+            .build()
+            .unwrap();
+        assert_eq!(engine.registry().len(), 1);
+        assert_eq!(engine.correct("sl -la", "")[0].text, "ls -la");
+
+        let engine = CorrectionEngine::builder()
+            .rules(["terraform_no_command"])
+            .exclude_rules(["terraform_no_command"])
+            .build()
+            .unwrap();
+        assert!(engine.registry().is_empty());
+
+        assert!(matches!(
+            CorrectionEngine::builder().rules(["no_such_rule"]).build(),
+            Err(TheFuckError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_capture() {
+        let engine = CorrectionEngine::builder()
+            .without_builtin_rules()
+            .build()
+            .unwrap();
+        assert!(engine.correct_command("sl").is_err());
+    }
+}
@@ -21,6 +21,9 @@ pub enum TheFuckError {
     #[error("Rule execution failed: {0}")]
     RuleExecutionError(String),
 
+    #[error("Plugin error: {0}")]
+    PluginError(String),
+
     /// Configuration errors
     #[error("Configuration error: {0}")]
     ConfigError(String),
@@ -73,6 +76,11 @@ pub enum TheFuckError {
     #[error(transparent)]
     TomlError(#[from] toml::de::Error),
 
+    /// Learning database errors
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error(transparent)]
+    DatabaseError(#[from] rusqlite::Error),
+
     /// UTF-8 encoding errors
     #[error(transparent)]
     Utf8Error(#[from] std::string::FromUtf8Error),
@@ -103,6 +111,9 @@ impl<T> ErrorContext<T> for TheFuckResult<T> {
             TheFuckError::RuleExecutionError(msg) => {
                 TheFuckError::RuleExecutionError(format!("{context}: {msg}"))
             }
+            TheFuckError::PluginError(msg) => {
+                TheFuckError::PluginError(format!("{context}: {msg}"))
+            }
             TheFuckError::ConfigError(msg) => {
                 TheFuckError::ConfigError(format!("{context}: {msg}"))
             }
@@ -147,6 +158,11 @@ impl TheFuckError {
         TheFuckError::NoRulesFound(command.into())
     }
 
+    /// Create a plugin error
+    pub fn plugin_error<S: Into<String>>(message: S) -> Self {
+        TheFuckError::PluginError(message.into())
+    }
+
     /// Create a configuration error
     pub fn config_error<S: Into<String>>(message: S) -> Self {
         TheFuckError::ConfigError(message.into())
@@ -0,0 +1,176 @@
+//! C ABI of the correction engine, for terminal emulators and editors that embed it.
+//!
+//! Its header is `include/thefuck_rs.h`, and callers link against the shared library built with
+//! `cargo rustc --lib --release --crate-type cdylib --features ffi`. Building with the `ffi` feature generates it in
+//! `OUT_DIR`, copy it over when the functions change, the tests fail until then.
+//! Strings returned by the library are owned by it and must be released with
+//! [`tf_free_string`].
+
+use crate::config::Settings;
+use crate::engine::CorrectionEngine;
+use crate::types::CorrectedCommand;
+use serde::Serialize;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::OnceLock;
+
+/// A correction as returned to C callers
+#[derive(Debug, Serialize)]
+struct Correction<'a> {
+    text: &'a str,
+    rule: &'a str,
+    priority: u32,
+    #[allow(clippy::type_complexity)]
+    side_effects: &'a [String],
+}
+
+impl<'a> From<&'a CorrectedCommand> for Correction<'a> {
+    fn from(correction: &'a CorrectedCommand) -> Self {
+        Self {
+            text: &correction.text,
+            rule: &correction.rule,
+            priority: correction.priority,
+            side_effects: &correction.side_effects,
+        }
+    }
+}
+
+/// The engine every call uses, created by the first one, or none if that failed
+#[allow(clippy::type_complexity)]
+static ENGINE: OnceLock<Option<CorrectionEngine>> = OnceLock::new();
+
+/// Gets the engine, creating it with the user's settings and rules on first use
+#[allow(clippy::type_complexity)]
+fn engine() -> Option<&'static CorrectionEngine> {
+    ENGINE
+        .get_or_init(|| {
+            CorrectionEngine::builder()
+                .settings(Settings::load().unwrap_or_default())
+                .user_rules(true)
+                .build()
+                .map_err(|e| tracing::warn!("failed to create the correction engine: {e}"))
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Reads a C string, if it's set and valid UTF-8
+///
+/// # Safety
+///
+/// `text` must be null or point to a NUL-terminated string.
+#[allow(clippy::type_complexity)]
+unsafe fn read<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        return None;
+    }
+    // SAFETY: the caller guarantees `text` is a NUL-terminated string
+    unsafe { CStr::from_ptr(text) }.to_str().ok()
+}
+
+/// Gets the corrections of a failed command as a JSON array
+#[allow(clippy::type_complexity)]
+fn correct(command: &str, stderr: &str) -> Option<String> {
+    let corrections = engine()?.correct(command, stderr);
+    let corrections: Vec<Correction> = corrections.iter().map(Correction::from).collect();
+    serde_json::to_string(&corrections).ok()
+}
+
+/// Corrects a failed command from its standard error.
+///
+/// Returns a JSON array of corrections, most likely first, each with a `text`, the `rule` that
+/// suggested it, its `priority` and its `side_effects`, e.g.
+/// `[{"text":"cargo build","rule":"cargo_no_command","priority":900,"side_effects":[]}]`.
+/// Returns NULL if an argument is NULL or not UTF-8, or the engine failed.
+/// The result must be released with `tf_free_string`.
+///
+/// # Safety
+///
+/// `command` and `stderr_text` must be null or point to NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tf_correct(
+    command: *const c_char,
+    stderr_text: *const c_char,
+) -> *mut c_char {
+    // SAFETY: the caller guarantees both are null or NUL-terminated strings
+    let (Some(command), Some(stderr)) = (unsafe { read(command) }, unsafe { read(stderr_text) })
+    else {
+        return std::ptr::null_mut();
+    };
+    // Unwinding into C is undefined behavior, so a panicking rule yields NULL instead
+    catch_unwind(AssertUnwindSafe(|| correct(command, stderr)))
+        .ok()
+        .flatten()
+        .and_then(|json| CString::new(json).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by the library. Does nothing if `text` is NULL.
+///
+/// # Safety
+///
+/// `text` must be null or a string returned by the library that wasn't released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tf_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: the caller guarantees `text` came from `CString::into_raw` in this library
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+/// Gets the version of the library, e.g. `0.1.0`. The string is static and must not be released.
+#[unsafe(no_mangle)]
+pub extern "C" fn tf_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correct() {
+        let command = CString::new("terraform pla").unwrap();
+        let stderr =
+            CString::new("Terraform has no command named \"pla\". Did you mean \"plan\"?").unwrap();
+        let json = unsafe { tf_correct(command.as_ptr(), stderr.as_ptr()) };
+        assert!(!json.is_null());
+
+        let corrections: serde_json::Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(json) }.to_str().unwrap()).unwrap();
+        assert_eq!(corrections[0]["text"], "terraform plan");
+        assert_eq!(corrections[0]["rule"], "terraform_no_command");
+        unsafe { tf_free_string(json) };
+    }
+
+    #[test]
+    fn test_engine_is_shared_by_threads() {
+        let address = || engine().map(|engine| engine as *const CorrectionEngine as usize);
+        let other = std::thread::spawn(address).join().unwrap();
+        assert!(other.is_some());
+        assert_eq!(address(), other);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let command = CString::new("ls").unwrap();
+        assert!(unsafe { tf_correct(command.as_ptr(), std::ptr::null()) }.is_null());
+        assert!(unsafe { tf_correct(std::ptr::null(), command.as_ptr()) }.is_null());
+        unsafe { tf_free_string(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn test_header_is_up_to_date() {
+        assert_eq!(
+            include_str!("../../include/thefuck_rs.h"),
+            include_str!(concat!(env!("OUT_DIR"), "/thefuck_rs.h")),
+            "copy the generated header over include/thefuck_rs.h"
+        );
+    }
+
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(tf_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
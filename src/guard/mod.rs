@@ -0,0 +1,441 @@
+//! Safety net for dangerous corrections, whichever rule suggested them.
+//!
+//! Every correction is checked against a denylist of patterns before it is shown: those that
+//! wipe a disk, remove the root or home directory, fork-bomb the machine or force-push to a
+//! protected branch. Depending on the settings, a dangerous correction is either dropped or
+//! only run once the user types a confirmation word.
+
+use crate::types::CorrectedCommand;
+use crate::{TheFuckError, TheFuckResult, i18n, utils};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use std::sync::OnceLock;
+
+/// Built-in dangerous command patterns and why they are dangerous
+#[allow(clippy::type_complexity)]
+const BUILTIN_PATTERNS: &[(&str, &str)] = &[
+    (
+        r"\brm\s+(?:-\S+\s+)*(?:/|/\*|~/?\*?|\$HOME/?\*?)(?:\s|;|&|\||$)",
+        "removes the root or home directory",
+    ),
+    (r"\brm\s.*--no-preserve-root", "removes the root directory"),
+    (r"\bmkfs(?:\.\w+)?\b", "formats a file system"),
+    (
+        r"\bdd\s.*\bof=/dev/(?:sd|hd|vd|xvd|nvme|mmcblk|disk)",
+        "overwrites a disk",
+    ),
+    (
+        r">\s*/dev/(?:sd|hd|vd|xvd|nvme|mmcblk|disk)",
+        "overwrites a disk",
+    ),
+    (r":\(\)\s*\{\s*:\s*\|\s*:\s*&\s*\}\s*;\s*:", "fork bomb"),
+    (
+        r"\b(?:chmod|chown)\s+(?:-\S+\s+)*-\S*R\S*\s+\S+\s+/(?:\s|$)",
+        "changes the whole file system",
+    ),
+];
+
+/// Shell operators separating the commands of a script
+#[allow(clippy::type_complexity)]
+const SEPARATORS: &[&str] = &["&&", "||", ";", "|", "&"];
+
+/// What happens to a dangerous correction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GuardAction {
+    /// Run it only once the confirmation word is typed
+    #[default]
+    Confirm,
+    /// Never suggest it
+    Block,
+}
+
+/// Settings of the guard, the `[guard]` table of `settings.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuardSettings {
+    /// Check corrections at all
+    pub enabled: bool,
+    /// What happens to dangerous corrections
+    pub action: GuardAction,
+    /// Word to type to run a dangerous correction
+    pub confirmation_word: String,
+    /// Check the built-in patterns, besides `patterns`
+    pub builtin_patterns: bool,
+    /// Extra regular expressions of dangerous commands
+    #[allow(clippy::type_complexity)]
+    pub patterns: Vec<String>,
+    /// Branches that must never be force-pushed to
+    #[allow(clippy::type_complexity)]
+    pub protected_branches: Vec<String>,
+}
+
+impl Default for GuardSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: GuardAction::Confirm,
+            confirmation_word: "yes".to_string(),
+            builtin_patterns: true,
+            patterns: Vec::new(),
+            protected_branches: vec!["main".to_string(), "master".to_string()],
+        }
+    }
+}
+
+/// Why a command is dangerous
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Danger {
+    /// The dangerous command
+    pub command: String,
+    /// What the command would do
+    pub reason: String,
+}
+
+/// What the guard lets happen to a correction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// The correction is harmless
+    Allow,
+    /// The correction runs only once the user confirms it
+    Confirm(Danger),
+    /// The correction is never suggested
+    Block(Danger),
+}
+
+/// Checks corrections against the dangerous command patterns
+pub struct Guard {
+    settings: GuardSettings,
+    #[allow(clippy::type_complexity)]
+    patterns: Vec<(Regex, String)>,
+    /// Branch a bare `git push --force` pushes, looked up the first time it's needed
+    #[allow(clippy::type_complexity)]
+    current_branch: OnceLock<Option<String>>,
+}
+
+impl Guard {
+    /// Creates a guard, failing if a configured pattern isn't a valid regular expression
+    #[allow(clippy::type_complexity)]
+    pub fn new(settings: &GuardSettings) -> TheFuckResult<Self> {
+        let mut patterns = Vec::new();
+        if settings.builtin_patterns {
+            for (pattern, reason) in BUILTIN_PATTERNS {
+                let regex = Regex::new(pattern).expect("valid built-in guard pattern");
+                patterns.push((regex, reason.to_string()));
+            }
+        }
+        for pattern in &settings.patterns {
+            let regex = Regex::new(pattern).map_err(|e| {
+                TheFuckError::config_error(format!("Invalid guard pattern `{pattern}`: {e}"))
+            })?;
+            patterns.push((regex, format!("matches `{pattern}`")));
+        }
+        Ok(Self {
+            settings: settings.clone(),
+            patterns,
+            current_branch: OnceLock::new(),
+        })
+    }
+
+    /// Sets the branch a bare `git push --force` pushes, instead of asking git
+    #[allow(clippy::type_complexity)]
+    pub fn with_current_branch(self, branch: Option<&str>) -> Self {
+        let _ = self.current_branch.set(branch.map(str::to_string));
+        self
+    }
+
+    /// Gets why a command is dangerous, or `None` if it isn't
+    #[allow(clippy::type_complexity)]
+    pub fn danger(&self, command: &str) -> Option<Danger> {
+        if !self.settings.enabled {
+            return None;
+        }
+        let reason = self
+            .patterns
+            .iter()
+            .find(|(regex, _)| regex.is_match(command))
+            .map(|(_, reason)| reason.clone())
+            .or_else(|| {
+                self.forced_push_branch(command)
+                    .map(|branch| format!("force-pushes to the protected branch {branch}"))
+            })?;
+        Some(Danger {
+            command: command.to_string(),
+            reason,
+        })
+    }
+
+    /// Gets what happens to a command
+    pub fn verdict(&self, command: &str) -> Verdict {
+        match self.danger(command) {
+            None => Verdict::Allow,
+            Some(danger) => match self.settings.action {
+                GuardAction::Confirm => Verdict::Confirm(danger),
+                GuardAction::Block => Verdict::Block(danger),
+            },
+        }
+    }
+
+    /// Drops the blocked corrections, telling the user about them
+    #[allow(clippy::type_complexity)]
+    pub fn filter(&self, corrections: Vec<CorrectedCommand>) -> Vec<CorrectedCommand> {
+        corrections
+            .into_iter()
+            .filter(|correction| match self.verdict(&correction.text) {
+                Verdict::Block(danger) => {
+                    eprintln!(
+                        "{}",
+                        i18n::tr_with(
+                            "guard-blocked",
+                            &[
+                                ("command", danger.command.into()),
+                                ("reason", danger.reason.into())
+                            ]
+                        )
+                    );
+                    false
+                }
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Asks the user to type the confirmation word to run a dangerous command
+    #[allow(clippy::type_complexity)]
+    pub fn confirm(
+        &self,
+        danger: &Danger,
+        input: &mut impl BufRead,
+        output: &mut impl Write,
+    ) -> TheFuckResult<bool> {
+        writeln!(
+            output,
+            "{}",
+            i18n::tr_with(
+                "guard-dangerous",
+                &[
+                    ("command", danger.command.as_str().into()),
+                    ("reason", danger.reason.as_str().into())
+                ]
+            )
+        )?;
+        write!(
+            output,
+            "{} ",
+            i18n::tr_with(
+                "guard-confirm",
+                &[("word", self.settings.confirmation_word.as_str().into())]
+            )
+        )?;
+        output.flush()?;
+        let mut line = String::new();
+        input.read_line(&mut line)?;
+        Ok(line.trim() == self.settings.confirmation_word)
+    }
+
+    /// Gets the protected branch a script force-pushes to, if any
+    #[allow(clippy::type_complexity)]
+    fn forced_push_branch(&self, command: &str) -> Option<String> {
+        let words = utils::split_script(command)
+            .unwrap_or_else(|| command.split_whitespace().map(str::to_string).collect());
+        words
+            .split(|word| SEPARATORS.contains(&word.as_str()))
+            .find_map(|words| self.forced_push_branch_of(words))
+    }
+
+    /// Gets the protected branch a single command force-pushes to, if any
+    #[allow(clippy::type_complexity)]
+    fn forced_push_branch_of(&self, words: &[String]) -> Option<String> {
+        let git = words.iter().position(|word| word == "git")?;
+        let push = git + 1 + words[git + 1..].iter().position(|word| word == "push")?;
+        let arguments = &words[push + 1..];
+        let force_flag = arguments.iter().any(|argument| {
+            argument == "--force"
+                || argument.starts_with("--force-with-lease")
+                || (argument.starts_with('-')
+                    && !argument.starts_with("--")
+                    && argument.contains('f'))
+        });
+        #[allow(clippy::type_complexity)]
+        let refspecs: Vec<&str> = arguments
+            .iter()
+            .filter(|argument| !argument.starts_with('-'))
+            .skip(1)
+            .map(String::as_str)
+            .collect();
+
+        let protected = |branch: &str| {
+            self.settings
+                .protected_branches
+                .iter()
+                .find(|protected| protected.as_str() == branch)
+                .cloned()
+        };
+        if refspecs.is_empty() {
+            if !force_flag {
+                return None;
+            }
+            let branch = self.current_branch.get_or_init(|| {
+                utils::get_output("git", &["symbolic-ref", "--short", "HEAD"])
+                    .map(|branch| branch.trim().to_string())
+            });
+            return branch.as_deref().and_then(protected);
+        }
+        refspecs.iter().find_map(|refspec| {
+            let forced = force_flag || refspec.starts_with('+');
+            let destination = refspec
+                .trim_start_matches('+')
+                .rsplit(':')
+                .next()
+                .unwrap_or_default();
+            let branch = destination
+                .strip_prefix("refs/heads/")
+                .unwrap_or(destination);
+            if forced { protected(branch) } else { None }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Command, Shell};
+
+    fn guard_on_main(settings: GuardSettings) -> Guard {
+        Guard::new(&settings)
+            .unwrap()
+            .with_current_branch(Some("main"))
+    }
+
+    #[test]
+    fn test_builtin_patterns() {
+        let guard = guard_on_main(GuardSettings::default());
+        for command in [
+            "rm -rf /",
+            "sudo rm -rf / ",
+            "rm -fr ~/",
+            "rm -r --force /*",
+            "rm -rf $HOME",
+            "rm -rf --no-preserve-root /",
+            "mkfs.ext4 /dev/sda1",
+            "dd if=/dev/zero of=/dev/sda bs=1M",
+            "cat image.iso > /dev/sdb",
+            ":(){ :|:& };:",
+            ":(){:|:&};:",
+            "chmod -R 777 /",
+        ] {
+            assert!(guard.danger(command).is_some(), "{command}");
+        }
+        for command in [
+            "rm -rf ./build",
+            "rm -rf /tmp/build",
+            "farm -rf /",
+            "dd if=disk.img of=backup.img",
+            "chmod -R 755 ./public",
+            "git push",
+        ] {
+            assert_eq!(guard.danger(command), None, "{command}");
+        }
+    }
+
+    #[test]
+    fn test_forced_push() {
+        let guard = guard_on_main(GuardSettings::default());
+        let reason = |command: &str| guard.danger(command).map(|danger| danger.reason);
+        assert_eq!(
+            reason("git push --force origin main").as_deref(),
+            Some("force-pushes to the protected branch main")
+        );
+        assert!(reason("git push -fu origin master").is_some());
+        assert!(reason("git push origin +main").is_some());
+        assert!(reason("git push origin +feature:refs/heads/master").is_some());
+        assert!(reason("git push --force-with-lease").is_some());
+        assert!(reason("git fetch && git push -f").is_some());
+        assert_eq!(reason("git push --force origin feature"), None);
+        assert_eq!(reason("git push origin main"), None);
+        assert_eq!(reason("git push -u origin +feature"), None);
+
+        let guard = Guard::new(&GuardSettings::default())
+            .unwrap()
+            .with_current_branch(Some("feature"));
+        assert_eq!(guard.danger("git push --force"), None);
+    }
+
+    #[test]
+    fn test_settings() {
+        let settings = GuardSettings {
+            action: GuardAction::Block,
+            builtin_patterns: false,
+            patterns: vec![r"^sudo\s+reboot\b".to_string()],
+            ..GuardSettings::default()
+        };
+        let guard = guard_on_main(settings.clone());
+        assert_eq!(guard.verdict("rm -rf /"), Verdict::Allow);
+        assert!(matches!(
+            guard.verdict("sudo reboot now"),
+            Verdict::Block(_)
+        ));
+        assert!(matches!(
+            guard.verdict("git push -f origin main"),
+            Verdict::Block(_)
+        ));
+
+        let disabled = guard_on_main(GuardSettings {
+            enabled: false,
+            ..settings
+        });
+        assert_eq!(disabled.verdict("sudo reboot"), Verdict::Allow);
+
+        assert!(matches!(
+            Guard::new(&GuardSettings {
+                patterns: vec!["(".to_string()],
+                ..GuardSettings::default()
+            }),
+            Err(TheFuckError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_filter() {
+        let original = Command::new("rm -rf /tmp/x".to_string(), Shell::Bash);
+        let corrections = vec![
+            CorrectedCommand::new("rm -rf /".to_string(), original.clone(), 1000),
+            CorrectedCommand::new("rm -rf /tmp/x/".to_string(), original, 900),
+        ];
+        let confirm = guard_on_main(GuardSettings::default());
+        assert_eq!(confirm.filter(corrections.clone()).len(), 2);
+
+        let block = guard_on_main(GuardSettings {
+            action: GuardAction::Block,
+            ..GuardSettings::default()
+        });
+        let kept = block.filter(corrections);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].text, "rm -rf /tmp/x/");
+    }
+
+    #[test]
+    fn test_confirm() {
+        let guard = guard_on_main(GuardSettings::default());
+        let danger = guard.danger("mkfs /dev/sda").unwrap();
+        let mut output = Vec::new();
+        assert!(
+            guard
+                .confirm(&danger, &mut "yes\n".as_bytes(), &mut output)
+                .unwrap()
+        );
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("formats a file system"));
+        assert!(output.contains("Type yes"));
+
+        let mut output = Vec::new();
+        for answer in ["y\n", "\n", ""] {
+            assert!(
+                !guard
+                    .confirm(&danger, &mut answer.as_bytes(), &mut output)
+                    .unwrap()
+            );
+        }
+    }
+}
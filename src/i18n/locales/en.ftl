@@ -0,0 +1,62 @@
+# Messages shown to the user, in English. Every other locale falls back to these.
+
+aborted = Aborted
+copied = Copied to the clipboard: { $command }
+usage-hint = Use --help for usage information
+learning-cleared =
+    { $count ->
+        [one] Cleared { $count } learned correction
+       *[other] Cleared { $count } learned corrections
+    }
+undo-by-hand = Also revert by hand: { $side-effect }
+daemon-listening = Listening on { $address }
+log-open-failed = Failed to open the log file { $path }: { $error }
+logging-to = Logging debug details to { $path }
+docs-written =
+    { $count ->
+        [one] Wrote { $count } rule page to { $dir }
+       *[other] Wrote { $count } rule pages to { $dir }
+    }
+
+## Guard
+
+guard-blocked = Blocked a dangerous correction ({ $reason }): { $command }
+guard-dangerous = Dangerous correction ({ $reason }): { $command }
+guard-confirm = Type { $word } to run it anyway:
+
+## Selectors
+
+selector-keys = enter/↑/↓/ctrl+c
+selector-numbered-prompt = Pick a correction [1-{ $count }, q to quit]:
+selector-not-a-correction = Not a correction: { $answer }
+picker-corrections = Corrections (enter/↑/↓/esc)
+picker-preview = Preview
+preview-runs = Runs: { $command }
+preview-in = In: { $cwd }
+preview-current-directory = the current directory
+preview-rule = Rule: { $rule } (priority { $priority })
+preview-matched = Matched: { $pattern }
+preview-lookup = Lookup: { $lookup }
+preview-side-effect = Side effect: { $side-effect }
+preview-undo = Undo: { $command }
+preview-no-undo = Undo: no known inverse
+
+## Errors
+
+error-parse = Failed to parse command: { $details }
+error-unsupported-shell = Shell not supported: { $details }
+error-shell-execution = Shell execution failed: { $details }
+error-no-rules-found = No matching rules found for command: { $details }
+error-rule-execution = Rule execution failed: { $details }
+error-plugin = Plugin error: { $details }
+error-config = Configuration error: { $details }
+error-config-load = Failed to load configuration file: { $details }
+error-file-system = File system error: { $details }
+error-file-read = Failed to read file: { $details }
+error-file-write = Failed to write file: { $details }
+error-process = Process execution failed: { $details }
+error-command-execution = Command execution failed: { $details }
+error-history = Failed to access command history: { $details }
+error-validation = Validation error: { $details }
+error-network = Network error: { $details }
+error-path = Path conversion error: { $details }
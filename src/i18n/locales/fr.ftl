@@ -0,0 +1,62 @@
+# Messages shown to the user, in French.
+
+aborted = Abandonné
+copied = Copié dans le presse-papiers : { $command }
+usage-hint = Utilisez --help pour afficher l'aide
+learning-cleared =
+    { $count ->
+        [one] { $count } correction apprise effacée
+       *[other] { $count } corrections apprises effacées
+    }
+undo-by-hand = À annuler aussi à la main : { $side-effect }
+daemon-listening = En écoute sur { $address }
+log-open-failed = Impossible d'ouvrir le journal { $path } : { $error }
+logging-to = Détails de débogage journalisés dans { $path }
+docs-written =
+    { $count ->
+        [one] { $count } page de règle écrite dans { $dir }
+       *[other] { $count } pages de règles écrites dans { $dir }
+    }
+
+## Guard
+
+guard-blocked = Correction dangereuse bloquée ({ $reason }) : { $command }
+guard-dangerous = Correction dangereuse ({ $reason }) : { $command }
+guard-confirm = Tapez { $word } pour l'exécuter quand même :
+
+## Selectors
+
+selector-keys = entrée/↑/↓/ctrl+c
+selector-numbered-prompt = Choisissez une correction [1-{ $count }, q pour quitter] :
+selector-not-a-correction = Pas une correction : { $answer }
+picker-corrections = Corrections (entrée/↑/↓/échap)
+picker-preview = Aperçu
+preview-runs = Exécute : { $command }
+preview-in = Dans : { $cwd }
+preview-current-directory = le répertoire courant
+preview-rule = Règle : { $rule } (priorité { $priority })
+preview-matched = Correspondance : { $pattern }
+preview-lookup = Recherche : { $lookup }
+preview-side-effect = Effet de bord : { $side-effect }
+preview-undo = Annulation : { $command }
+preview-no-undo = Annulation : aucun inverse connu
+
+## Errors
+
+error-parse = Impossible d'analyser la commande : { $details }
+error-unsupported-shell = Shell non pris en charge : { $details }
+error-shell-execution = Échec de l'exécution du shell : { $details }
+error-no-rules-found = Aucune règle ne correspond à la commande : { $details }
+error-rule-execution = Échec de l'exécution de la règle : { $details }
+error-plugin = Erreur de plugin : { $details }
+error-config = Erreur de configuration : { $details }
+error-config-load = Impossible de charger le fichier de configuration : { $details }
+error-file-system = Erreur du système de fichiers : { $details }
+error-file-read = Impossible de lire le fichier : { $details }
+error-file-write = Impossible d'écrire le fichier : { $details }
+error-process = Échec de l'exécution du processus : { $details }
+error-command-execution = Échec de l'exécution de la commande : { $details }
+error-history = Impossible d'accéder à l'historique des commandes : { $details }
+error-validation = Erreur de validation : { $details }
+error-network = Erreur réseau : { $details }
+error-path = Erreur de conversion de chemin : { $details }
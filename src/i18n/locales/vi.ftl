@@ -0,0 +1,54 @@
+# Messages shown to the user, in Vietnamese.
+
+aborted = Đã hủy
+copied = Đã sao chép vào bộ nhớ tạm: { $command }
+usage-hint = Dùng --help để xem hướng dẫn sử dụng
+learning-cleared = Đã xóa { $count } lệnh sửa đã học
+undo-by-hand = Cần hoàn tác thủ công: { $side-effect }
+daemon-listening = Đang lắng nghe tại { $address }
+log-open-failed = Không thể mở tệp nhật ký { $path }: { $error }
+logging-to = Đang ghi chi tiết gỡ lỗi vào { $path }
+docs-written = Đã ghi { $count } trang quy tắc vào { $dir }
+
+## Guard
+
+guard-blocked = Đã chặn một sửa lỗi nguy hiểm ({ $reason }): { $command }
+guard-dangerous = Sửa lỗi nguy hiểm ({ $reason }): { $command }
+guard-confirm = Gõ { $word } để vẫn chạy nó:
+
+## Selectors
+
+selector-keys = enter/↑/↓/ctrl+c
+selector-numbered-prompt = Chọn một lệnh sửa [1-{ $count }, q để thoát]:
+selector-not-a-correction = Không phải lệnh sửa: { $answer }
+picker-corrections = Các lệnh sửa (enter/↑/↓/esc)
+picker-preview = Xem trước
+preview-runs = Chạy: { $command }
+preview-in = Tại: { $cwd }
+preview-current-directory = thư mục hiện tại
+preview-rule = Quy tắc: { $rule } (độ ưu tiên { $priority })
+preview-matched = Khớp: { $pattern }
+preview-lookup = Tra cứu: { $lookup }
+preview-side-effect = Tác dụng phụ: { $side-effect }
+preview-undo = Hoàn tác: { $command }
+preview-no-undo = Hoàn tác: không có lệnh đảo ngược
+
+## Errors
+
+error-parse = Không thể phân tích lệnh: { $details }
+error-unsupported-shell = Không hỗ trợ shell: { $details }
+error-shell-execution = Chạy shell thất bại: { $details }
+error-no-rules-found = Không có quy tắc nào khớp với lệnh: { $details }
+error-rule-execution = Chạy quy tắc thất bại: { $details }
+error-plugin = Lỗi plugin: { $details }
+error-config = Lỗi cấu hình: { $details }
+error-config-load = Không thể tải tệp cấu hình: { $details }
+error-file-system = Lỗi hệ thống tệp: { $details }
+error-file-read = Không thể đọc tệp: { $details }
+error-file-write = Không thể ghi tệp: { $details }
+error-process = Chạy tiến trình thất bại: { $details }
+error-command-execution = Chạy lệnh thất bại: { $details }
+error-history = Không thể truy cập lịch sử lệnh: { $details }
+error-validation = Lỗi xác thực: { $details }
+error-network = Lỗi mạng: { $details }
+error-path = Lỗi chuyển đổi đường dẫn: { $details }
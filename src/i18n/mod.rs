@@ -0,0 +1,232 @@
+//! Translations of the messages shown to the user.
+//!
+//! Messages are Fluent files embedded in the binary, one per locale in `locales/`. The locale is
+//! detected once from `LC_ALL`, `LC_MESSAGES` or `LANG`, and a message missing from its file
+//! falls back to the English one.
+
+use crate::TheFuckError;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+/// Locale used when none of the user's is translated
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Translated locales and their messages
+#[allow(clippy::type_complexity)]
+const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("locales/en.ftl")),
+    ("fr", include_str!("locales/fr.ftl")),
+    ("vi", include_str!("locales/vi.ftl")),
+];
+
+/// Environment variables naming the user's locale, by precedence
+#[allow(clippy::type_complexity)]
+const LOCALE_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// Gets the translated locale matching the user's, e.g. `vi` for `LANG=vi_VN.UTF-8`
+#[allow(clippy::type_complexity)]
+pub fn detect_locale(var: impl Fn(&str) -> Option<String>) -> &'static str {
+    let Some(value) = LOCALE_VARS
+        .iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.is_empty())
+    else {
+        return DEFAULT_LOCALE;
+    };
+    let language = value
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    LOCALES
+        .iter()
+        .map(|(locale, _)| *locale)
+        .find(|locale| *locale == language)
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Messages of one locale, with the English ones for what it doesn't translate
+pub struct Messages {
+    #[allow(clippy::type_complexity)]
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl Messages {
+    /// Loads the messages of a locale, English if it isn't translated
+    pub fn new(locale: &str) -> Self {
+        let language: LanguageIdentifier = locale
+            .parse()
+            .unwrap_or_else(|_| DEFAULT_LOCALE.parse().expect("valid default locale"));
+        let mut bundle = FluentBundle::new_concurrent(vec![language]);
+        // Placeables aren't wrapped in Unicode isolation marks, which terminals print as is
+        bundle.set_use_isolating(false);
+        for (name, source) in LOCALES {
+            if *name != DEFAULT_LOCALE && *name != locale {
+                continue;
+            }
+            let resource =
+                FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+                    tracing::warn!("invalid messages for {name}: {errors:?}");
+                    resource
+                });
+            bundle.add_resource_overriding(resource);
+        }
+        Self { bundle }
+    }
+
+    /// Formats a message, or gets its id back if there is no such message
+    #[allow(clippy::type_complexity)]
+    pub fn format(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let Some(pattern) = self
+            .bundle
+            .get_message(id)
+            .and_then(|message| message.value())
+        else {
+            tracing::warn!("no message `{id}`");
+            return id.to_string();
+        };
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        let mut errors = Vec::new();
+        let message = self
+            .bundle
+            .format_pattern(pattern, Some(&fluent_args), &mut errors);
+        if !errors.is_empty() {
+            tracing::warn!("failed to format `{id}`: {errors:?}");
+        }
+        message.into_owned()
+    }
+}
+
+/// Gets the messages of the user's locale
+fn messages() -> &'static Messages {
+    #[allow(clippy::type_complexity)]
+    static MESSAGES: OnceLock<Messages> = OnceLock::new();
+    MESSAGES.get_or_init(|| {
+        // Tests compare against the English messages whatever the developer's locale is
+        let locale = if cfg!(test) {
+            DEFAULT_LOCALE
+        } else {
+            detect_locale(|name| std::env::var(name).ok())
+        };
+        Messages::new(locale)
+    })
+}
+
+/// Gets a message in the user's locale
+pub fn tr(id: &str) -> String {
+    messages().format(id, &[])
+}
+
+/// Gets a message in the user's locale, filling in its arguments
+#[allow(clippy::type_complexity)]
+pub fn tr_with(id: &str, args: &[(&str, FluentValue)]) -> String {
+    messages().format(id, args)
+}
+
+/// Describes an error in the user's locale
+pub fn describe_error(error: &TheFuckError) -> String {
+    let (id, details) = match error {
+        TheFuckError::ParseError(details) => ("error-parse", details),
+        TheFuckError::UnsupportedShell(details) => ("error-unsupported-shell", details),
+        TheFuckError::ShellExecutionError(details) => ("error-shell-execution", details),
+        TheFuckError::NoRulesFound(details) => ("error-no-rules-found", details),
+        TheFuckError::RuleExecutionError(details) => ("error-rule-execution", details),
+        TheFuckError::PluginError(details) => ("error-plugin", details),
+        TheFuckError::ConfigError(details) => ("error-config", details),
+        TheFuckError::ConfigLoadError(details) => ("error-config-load", details),
+        TheFuckError::FileSystemError(details) => ("error-file-system", details),
+        TheFuckError::FileReadError(details) => ("error-file-read", details),
+        TheFuckError::FileWriteError(details) => ("error-file-write", details),
+        TheFuckError::ProcessError(details) => ("error-process", details),
+        TheFuckError::CommandExecutionError(details) => ("error-command-execution", details),
+        TheFuckError::HistoryError(details) => ("error-history", details),
+        TheFuckError::ValidationError(details) => ("error-validation", details),
+        TheFuckError::NetworkError(details) => ("error-network", details),
+        TheFuckError::PathError(details) => ("error-path", details),
+        _ => return error.to_string(),
+    };
+    tr_with(id, &[("details", details.as_str().into())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[allow(clippy::type_complexity)]
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        #[allow(clippy::type_complexity)]
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_detect_locale() {
+        assert_eq!(detect_locale(env(&[("LANG", "vi_VN.UTF-8")])), "vi");
+        assert_eq!(
+            detect_locale(env(&[("LANG", "vi_VN.UTF-8"), ("LC_ALL", "fr_FR")])),
+            "fr"
+        );
+        assert_eq!(
+            detect_locale(env(&[("LANG", "fr_FR"), ("LC_ALL", "")])),
+            "fr"
+        );
+        assert_eq!(detect_locale(env(&[("LANG", "C.UTF-8")])), "en");
+        assert_eq!(detect_locale(env(&[("LANG", "de_DE")])), "en");
+        assert_eq!(detect_locale(env(&[])), "en");
+    }
+
+    #[test]
+    fn test_messages() {
+        let english = Messages::new("en");
+        assert_eq!(
+            english.format("learning-cleared", &[("count", 1.into())]),
+            "Cleared 1 learned correction"
+        );
+        assert_eq!(
+            english.format("copied", &[("command", "git push".into())]),
+            "Copied to the clipboard: git push"
+        );
+
+        let french = Messages::new("fr");
+        assert_eq!(
+            french.format("learning-cleared", &[("count", 2.into())]),
+            "2 corrections apprises effacées"
+        );
+        assert_eq!(french.format("no-such-message", &[]), "no-such-message");
+    }
+
+    #[test]
+    #[allow(clippy::type_complexity)]
+    fn test_locales_translate_every_message() {
+        let ids = |source: &str| -> Vec<String> {
+            source
+                .lines()
+                .filter_map(|line| line.split_once(" ="))
+                .map(|(id, _)| id.to_string())
+                .filter(|id| !id.starts_with([' ', '#']))
+                .collect()
+        };
+        let english = ids(LOCALES[0].1);
+        for (locale, source) in &LOCALES[1..] {
+            assert_eq!(ids(source), english, "messages of {locale}");
+            assert!(FluentResource::try_new(source.to_string()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_describe_error() {
+        assert_eq!(
+            describe_error(&TheFuckError::no_rules_found("git psh")),
+            "No matching rules found for command: git psh"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Logged shell sessions on Windows, through a ConPTY pseudo console.
+
+use super::{OUTPUT_LOG_ENV, SessionLog};
+use crate::types::Shell;
+use crate::{TheFuckError, TheFuckResult, shells};
+use conpty::Process;
+use conpty::console::Console;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Describes a ConPTY failure
+fn conpty_error(error: conpty::error::Error) -> TheFuckError {
+    TheFuckError::process_error(format!("pseudo console failed: {error}"))
+}
+
+/// Runs a shell in a pseudo console, forwarding the keyboard to it and its output to both the
+/// console and the session log, until it exits
+#[allow(clippy::type_complexity)]
+pub(super) fn run_session(
+    shell: &Shell,
+    log_path: &Path,
+    mut log: SessionLog,
+) -> TheFuckResult<()> {
+    let (program, _) = shells::run_args(shell);
+    let mut command = std::process::Command::new(program);
+    // The pseudo console only gets the environment it's given
+    command.envs(std::env::vars()).env(OUTPUT_LOG_ENV, log_path);
+    let mut process = Process::spawn(command).map_err(conpty_error)?;
+    let mut input = process.input().map_err(conpty_error)?;
+    let mut output = process.output().map_err(conpty_error)?;
+
+    let console = Console::current().map_err(conpty_error)?;
+    console.set_raw().map_err(conpty_error)?;
+
+    // Stuck reading the keyboard until the next key press, so it's never joined
+    std::thread::spawn(move || {
+        let _ = std::io::copy(&mut std::io::stdin(), &mut input);
+    });
+    let forward = std::thread::spawn(move || -> std::io::Result<()> {
+        let mut stdout = std::io::stdout();
+        let mut buffer = [0; 4096];
+        loop {
+            let read = match output.read(&mut buffer) {
+                Ok(0) | Err(_) => return Ok(()),
+                Ok(read) => read,
+            };
+            stdout.write_all(&buffer[..read])?;
+            stdout.flush()?;
+            log.write_all(&buffer[..read])?;
+        }
+    });
+
+    let exited = process.wait(None).map_err(conpty_error);
+    // Closing the pseudo console ends its output, and with it the forwarding
+    drop(process);
+    let forwarded = forward
+        .join()
+        .map_err(|_| TheFuckError::process_error("forwarding the output panicked"));
+    console.reset().map_err(conpty_error)?;
+    exited?;
+    forwarded??;
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! Experimental instant mode: a logged shell session, so failed commands can be corrected
+//! from their logged output instead of being run again.
+//!
+//! `--shell-logger FILE` starts the user's shell inside a pseudo console and tees everything it
+//! prints to `FILE`, whose path the session gets in `TF_OUTPUT_LOG`. When correcting a command,
+//! its output is the logged text between the prompt line it was typed on and the next prompt.
+//! Only Windows is supported for now, through ConPTY; Unix shells capture their output with
+//! `thefuck-rs hook` instead.
+
+#[cfg(windows)]
+mod conpty;
+
+use crate::TheFuckResult;
+use crate::core::CorrectionRequest;
+use regex::Regex;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Environment variable naming the log of the current instant mode session
+pub const OUTPUT_LOG_ENV: &str = "TF_OUTPUT_LOG";
+
+/// Size past which the session log starts over, in bytes
+pub const MAX_SESSION_LOG_SIZE: u64 = 256 * 1024;
+
+/// Log of everything a shell session printed, starting over when it grows too large
+pub struct SessionLog {
+    file: File,
+    size: u64,
+    max_size: u64,
+}
+
+impl SessionLog {
+    /// Creates an empty session log, creating its directory if needed
+    #[allow(clippy::type_complexity)]
+    pub fn create(path: &Path, max_size: u64) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(Self {
+            file,
+            size: 0,
+            max_size,
+        })
+    }
+}
+
+impl Write for SessionLog {
+    #[allow(clippy::type_complexity)]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // Only the last commands matter, so older output is dropped wholesale
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_size {
+            self.file.set_len(0)?;
+            self.file.seek(SeekFrom::Start(0))?;
+            self.size = 0;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Removes the terminal escape sequences and carriage returns a pseudo console emits
+pub fn strip_escapes(text: &str) -> String {
+    #[allow(clippy::type_complexity)]
+    static ESCAPES: OnceLock<Regex> = OnceLock::new();
+    let escapes = ESCAPES.get_or_init(|| {
+        Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]")
+            .expect("valid escape sequence pattern")
+    });
+    escapes.replace_all(text, "").replace('\r', "")
+}
+
+/// Gets the logged output of the last time a command was typed at the prompt
+#[allow(clippy::type_complexity)]
+pub fn last_output(log: &str, command: &str) -> Option<String> {
+    let command = command.trim();
+    if command.is_empty() {
+        return None;
+    }
+    let text = strip_escapes(log);
+    #[allow(clippy::type_complexity)]
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines
+        .iter()
+        .rposition(|line| line.trim_end().ends_with(command))?;
+    let prompt = lines[start]
+        .trim_end()
+        .strip_suffix(command)
+        .unwrap_or_default()
+        .trim();
+    let output = &lines[start + 1..];
+    let end = if prompt.is_empty() {
+        output.len()
+    } else {
+        output
+            .iter()
+            .position(|line| line.trim_start().starts_with(prompt))
+            .unwrap_or(output.len())
+    };
+    Some(output[..end].join("\n"))
+}
+
+/// Gets a failed command from the instant mode session log, if it logged this command
+///
+/// The log doesn't hold exit codes, so the command is assumed to have failed.
+#[allow(clippy::type_complexity)]
+pub fn captured_request(log_path: &Path, command: &str) -> Option<CorrectionRequest> {
+    let log = std::fs::read(log_path).ok()?;
+    let output = last_output(&String::from_utf8_lossy(&log), command)?;
+    Some(CorrectionRequest {
+        command: command.to_string(),
+        stderr: output,
+        exit_code: 1,
+        cwd: std::env::current_dir()
+            .ok()
+            .map(|dir| dir.to_string_lossy().to_string()),
+        env: std::env::vars().collect(),
+        ..CorrectionRequest::default()
+    })
+}
+
+/// Runs the user's shell in a logged session until it exits
+#[allow(clippy::type_complexity)]
+pub fn run_session(log_path: &Path) -> TheFuckResult<()> {
+    let log = SessionLog::create(log_path, MAX_SESSION_LOG_SIZE)?;
+    #[cfg(windows)]
+    {
+        conpty::run_session(&crate::shells::detect_shell(), log_path, log)
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = log;
+        Err(crate::TheFuckError::unsupported_shell(
+            "instant mode needs Windows' pseudo console, use `thefuck-rs hook` to capture output",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_escapes() {
+        assert_eq!(
+            strip_escapes("\x1b[2J\x1b[m\x1b]0;Windows PowerShell\x07PS C:\\> ls\r\n"),
+            "PS C:\\> ls\n"
+        );
+    }
+
+    #[test]
+    fn test_last_output() {
+        let log = "PS C:\\> git psuh\r\n\x1b[31mgit: 'psuh' is not a git command.\x1b[m\r\n\
+                   \r\nThe most similar command is\r\n\tpush\r\nPS C:\\> git stauts\r\n\
+                   nope\r\nPS C:\\> git psuh\r\ngit: 'psuh' is not a git command.\r\n\
+                   PS C:\\> fuck\r\n";
+        assert_eq!(
+            last_output(log, "git psuh").as_deref(),
+            Some("git: 'psuh' is not a git command.")
+        );
+        assert_eq!(last_output(log, "git stauts").as_deref(), Some("nope"));
+        assert_eq!(last_output(log, "git pul"), None);
+        assert_eq!(last_output("> ls\nfile\n", "ls").as_deref(), Some("file"));
+    }
+
+    #[test]
+    fn test_session_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.log");
+        let mut log = SessionLog::create(&path, 20).unwrap();
+        log.write_all(b"PS> ls\r\nfile\r\n").unwrap();
+        log.write_all(b"PS> git psuh\r\n").unwrap();
+        log.write_all(b"nope\r\n").unwrap();
+        log.flush().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "PS> git psuh\r\nnope\r\n"
+        );
+
+        assert_eq!(captured_request(&path, "ls"), None);
+        let request = captured_request(&path, "git psuh").unwrap();
+        assert_eq!(request.stderr, "nope");
+        assert_eq!(request.exit_code, 1);
+    }
+}
@@ -0,0 +1,199 @@
+//! Learns which corrections the user picks, so they rank higher the next time.
+//!
+//! Each pick is recorded as the failed command's pattern (its program and subcommand), the
+//! chosen correction and the rule that suggested it. A correction picked before for the same
+//! pattern gets a large boost, and every correction of a rule picked before gets a small one.
+
+use crate::config::Settings;
+use crate::rules::Ranking;
+use crate::types::{Command, CorrectedCommand};
+use crate::{TheFuckError, TheFuckResult};
+use rusqlite::{Connection, params};
+use std::path::Path;
+
+/// Name of the learning database inside the data directory
+pub const LEARNING_DB: &str = "learning.db";
+
+/// Priority boost per time the same correction was picked for the same pattern
+const CORRECTION_BOOST: u32 = 100;
+
+/// Priority boost per time a correction of the same rule was picked
+const RULE_BOOST: u32 = 10;
+
+/// Maximum number of picks that count towards a boost
+const MAX_PICKS: u32 = 10;
+
+/// Gets the pattern of a failed command, e.g. `git psuh` for `git psuh origin main`
+pub fn command_pattern(command: &Command) -> String {
+    let mut words = command.trimmed().split_whitespace();
+    let program = words.next().unwrap_or_default();
+    match words.next() {
+        Some(subcommand) if !subcommand.starts_with('-') => format!("{program} {subcommand}"),
+        _ => program.to_string(),
+    }
+}
+
+/// Local store of the corrections the user picked
+pub struct LearningStore {
+    connection: Connection,
+}
+
+impl LearningStore {
+    /// Opens the store in a database file, creating it if needed
+    #[allow(clippy::type_complexity)]
+    pub fn open(path: &Path) -> TheFuckResult<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Opens the store in the data directory
+    #[allow(clippy::type_complexity)]
+    pub fn open_default() -> TheFuckResult<Self> {
+        let dir = Settings::data_dir()
+            .ok_or_else(|| TheFuckError::config_error("No data directory found"))?;
+        Self::open(&dir.join(LEARNING_DB))
+    }
+
+    /// Opens a store that only lives in memory
+    #[allow(clippy::type_complexity)]
+    pub fn in_memory() -> TheFuckResult<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn init(connection: Connection) -> TheFuckResult<Self> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS picks (
+                pattern TEXT NOT NULL,
+                correction TEXT NOT NULL,
+                rule TEXT NOT NULL,
+                count INTEGER NOT NULL,
+                last_used INTEGER NOT NULL,
+                PRIMARY KEY (pattern, correction, rule)
+            );
+            CREATE INDEX IF NOT EXISTS picks_rule ON picks (rule);",
+        )?;
+        Ok(Self { connection })
+    }
+
+    /// Records that the user picked a correction
+    #[allow(clippy::type_complexity)]
+    pub fn record(&self, correction: &CorrectedCommand) -> TheFuckResult<()> {
+        self.connection.execute(
+            "INSERT INTO picks (pattern, correction, rule, count, last_used)
+             VALUES (?1, ?2, ?3, 1, ?4)
+             ON CONFLICT (pattern, correction, rule)
+             DO UPDATE SET count = count + 1, last_used = excluded.last_used",
+            params![
+                command_pattern(&correction.original),
+                correction.text,
+                correction.rule,
+                chrono::Utc::now().timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Gets the priority boost of a correction from the user's previous picks
+    pub fn boost(&self, correction: &CorrectedCommand) -> u32 {
+        let correction_picks = self.count(
+            "SELECT COALESCE(SUM(count), 0) FROM picks WHERE pattern = ?1 AND correction = ?2",
+            params![command_pattern(&correction.original), correction.text],
+        );
+        let rule_picks = if correction.rule.is_empty() {
+            0
+        } else {
+            self.count(
+                "SELECT COALESCE(SUM(count), 0) FROM picks WHERE rule = ?1",
+                params![correction.rule],
+            )
+        };
+        correction_picks.min(MAX_PICKS) * CORRECTION_BOOST + rule_picks.min(MAX_PICKS) * RULE_BOOST
+    }
+
+    fn count(&self, query: &str, params: impl rusqlite::Params) -> u32 {
+        self.connection
+            .query_row(query, params, |row| row.get::<_, i64>(0))
+            .map_or(0, |count| count.clamp(0, u32::MAX as i64) as u32)
+    }
+
+    /// Forgets every recorded pick, returning how many were removed
+    #[allow(clippy::type_complexity)]
+    pub fn clear(&self) -> TheFuckResult<usize> {
+        Ok(self.connection.execute("DELETE FROM picks", [])?)
+    }
+}
+
+impl Ranking for LearningStore {
+    fn boost(&self, correction: &CorrectedCommand) -> u32 {
+        LearningStore::boost(self, correction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[allow(clippy::type_complexity)]
+    fn correction(script: &str, text: &str, rule: &str) -> CorrectedCommand {
+        let command = Command::new(script.to_string(), Shell::Bash);
+        CorrectedCommand::new(text.to_string(), command, 1000).with_rule(rule)
+    }
+
+    #[test]
+    fn test_command_pattern() {
+        let pattern =
+            |script: &str| command_pattern(&Command::new(script.to_string(), Shell::Bash));
+        assert_eq!(pattern("git psuh origin main"), "git psuh");
+        assert_eq!(pattern("ls -la"), "ls");
+        assert_eq!(pattern("  make  "), "make");
+    }
+
+    #[test]
+    fn test_boost() {
+        let store = LearningStore::in_memory().unwrap();
+        let push = correction("git psuh origin", "git push origin", "git_not_command");
+        assert_eq!(store.boost(&push), 0);
+
+        store.record(&push).unwrap();
+        store.record(&push).unwrap();
+        assert_eq!(store.boost(&push), 2 * CORRECTION_BOOST + 2 * RULE_BOOST);
+
+        let other = correction("git stauts", "git status", "git_not_command");
+        assert_eq!(store.boost(&other), 2 * RULE_BOOST);
+        let unknown = correction("git psuh origin", "git push origin", "");
+        assert_eq!(store.boost(&unknown), 2 * CORRECTION_BOOST);
+
+        for _ in 0..20 {
+            store.record(&push).unwrap();
+        }
+        assert_eq!(
+            store.boost(&push),
+            MAX_PICKS * CORRECTION_BOOST + MAX_PICKS * RULE_BOOST
+        );
+    }
+
+    #[test]
+    fn test_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(LEARNING_DB);
+        let store = LearningStore::open(&path).unwrap();
+        store
+            .record(&correction("git psuh", "git push", "git_not_command"))
+            .unwrap();
+        store
+            .record(&correction("git stauts", "git status", "git_not_command"))
+            .unwrap();
+        drop(store);
+
+        let store = LearningStore::open(&path).unwrap();
+        assert_eq!(store.clear().unwrap(), 2);
+        assert_eq!(
+            store.boost(&correction("git psuh", "git push", "git_not_command")),
+            0
+        );
+    }
+}
@@ -0,0 +1,240 @@
+"""Runs a legacy thefuck rule on behalf of thefuck-rs.
+
+Reads a request from stdin and writes the response to stdout, both as JSON:
+
+    {"rule": "/path/to/rule.py", "script": "git psuh", "output": "...", "cwd": "...", "env": {}}
+    {"match": true, "new_commands": ["git push"], "priority": 1000, "error": null}
+
+Rules are described once when loaded, and a picked correction's side effect is run with
+`new_command` added to the first kind of request:
+
+    {"describe": ["/path/to/rule.py"]}
+    [{"enabled_by_default": true, "priority": 1000, "apps": ["git"], "side_effect": false}]
+
+If the `thefuck` package isn't installed, a minimal stand-in provides the helpers rules
+commonly import from it.
+"""
+
+import difflib
+import importlib.util
+import json
+import os
+import re
+import shlex
+import shutil
+import sys
+import traceback
+import types
+from functools import wraps
+
+
+class Command(object):
+    def __init__(self, script, output):
+        self.script = script
+        self.output = output
+
+    @property
+    def stdout(self):
+        return self.output
+
+    @property
+    def stderr(self):
+        return self.output
+
+    @property
+    def script_parts(self):
+        try:
+            return shlex.split(self.script)
+        except ValueError:
+            return self.script.split()
+
+    def update(self, **kwargs):
+        kwargs.setdefault('script', self.script)
+        kwargs.setdefault('output', self.output)
+        return Command(**kwargs)
+
+
+def _decorator(decorate):
+    def decorator(fn):
+        return wraps(fn)(decorate(fn))
+    return decorator
+
+
+def for_app(*app_names, **kwargs):
+    at_least = kwargs.get('at_least', 0)
+
+    @_decorator
+    def decorate(fn):
+        def wrapper(command, *args, **kwargs):
+            parts = command.script_parts
+            if len(parts) > at_least and parts[0] in app_names:
+                return fn(command, *args, **kwargs)
+            return False
+        # Read when describing the rule, so it's only run for these programs
+        wrapper.thefuck_rs_apps = list(app_names)
+        return wrapper
+    return decorate
+
+
+def sudo_support(fn):
+    @wraps(fn)
+    def wrapper(command):
+        if not command.script.startswith('sudo '):
+            return fn(command)
+        result = fn(command.update(script=command.script[5:]))
+        if isinstance(result, str):
+            return u'sudo {}'.format(result)
+        if isinstance(result, list):
+            return [u'sudo {}'.format(x) for x in result]
+        return result
+    return wrapper
+
+
+def replace_argument(script, from_, to):
+    replaced_in_the_end = re.sub(u' {}$'.format(re.escape(from_)), u' {}'.format(to),
+                                 script, count=1)
+    if replaced_in_the_end != script:
+        return replaced_in_the_end
+    return script.replace(u' {} '.format(from_), u' {} '.format(to), 1)
+
+
+def get_close_matches(word, possibilities, n=3, cutoff=0.6):
+    return difflib.get_close_matches(word, possibilities, n, cutoff)
+
+
+def get_closest(word, possibilities, cutoff=0.6, fallback_to_first=True):
+    possibilities = list(possibilities)
+    try:
+        return difflib.get_close_matches(word, possibilities, 1, cutoff)[0]
+    except IndexError:
+        if fallback_to_first:
+            return possibilities[0]
+
+
+def replace_command(command, broken, matched):
+    new_cmds = get_close_matches(broken, matched, cutoff=0.1)
+    return [replace_argument(command.script, broken, new_cmd.strip()) for new_cmd in new_cmds]
+
+
+def get_all_matched_commands(stderr, separator='Did you mean'):
+    if not isinstance(separator, list):
+        separator = [separator]
+    should_yield = False
+    for line in stderr.split('\n'):
+        for sep in separator:
+            if sep in line:
+                should_yield = True
+                break
+        else:
+            if should_yield and line:
+                yield line.strip()
+
+
+def eager(fn):
+    @wraps(fn)
+    def wrapper(*args, **kwargs):
+        return list(fn(*args, **kwargs))
+    return wrapper
+
+
+def memoize(fn):
+    return fn
+
+
+def cache(*depends_on):
+    return memoize
+
+
+class _Shell(object):
+    def and_(self, *commands):
+        return u' && '.join(commands)
+
+    def or_(self, *commands):
+        return u' || '.join(commands)
+
+    def quote(self, s):
+        return shlex.quote(s)
+
+
+def _install_stand_in():
+    try:
+        import thefuck.utils  # noqa: F401
+        return
+    except Exception:
+        pass
+
+    modules = {name: types.ModuleType(name) for name in (
+        'thefuck', 'thefuck.utils', 'thefuck.types', 'thefuck.shells',
+        'thefuck.specific', 'thefuck.specific.sudo')}
+    utils = modules['thefuck.utils']
+    for fn in (for_app, replace_argument, get_close_matches, get_closest, replace_command,
+               get_all_matched_commands, eager, memoize, cache):
+        setattr(utils, fn.__name__, fn)
+    utils.which = shutil.which
+    modules['thefuck.types'].Command = Command
+    modules['thefuck.shells'].shell = _Shell()
+    modules['thefuck.specific.sudo'].sudo_support = sudo_support
+    for name, module in modules.items():
+        parent, _, child = name.rpartition('.')
+        if parent:
+            setattr(modules[parent], child, module)
+    sys.modules.update(modules)
+
+
+def _load_rule(path):
+    spec = importlib.util.spec_from_file_location('thefuck_rs_legacy_rule', path)
+    rule = importlib.util.module_from_spec(spec)
+    spec.loader.exec_module(rule)
+    return rule
+
+
+def describe(path):
+    try:
+        rule = _load_rule(path)
+    except Exception:
+        return {'error': traceback.format_exc()}
+    return {
+        'enabled_by_default': bool(getattr(rule, 'enabled_by_default', True)),
+        'priority': getattr(rule, 'priority', None),
+        'apps': getattr(getattr(rule, 'match', None), 'thefuck_rs_apps', None),
+        'side_effect': callable(getattr(rule, 'side_effect', None)),
+    }
+
+
+def evaluate(request):
+    _install_stand_in()
+    if 'describe' in request:
+        return [describe(path) for path in request['describe']]
+    os.environ.update(request.get('env') or {})
+    if request.get('cwd') and os.path.isdir(request['cwd']):
+        os.chdir(request['cwd'])
+
+    rule = _load_rule(request['rule'])
+    command = Command(request['script'], request['output'])
+    if 'new_command' in request:
+        rule.side_effect(command, request['new_command'])
+        return {'match': True, 'new_commands': [request['new_command']]}
+
+    response = {'match': False, 'new_commands': [], 'priority': getattr(rule, 'priority', None)}
+    if getattr(rule, 'requires_output', True) and not command.output:
+        return response
+    if rule.match(command):
+        new_commands = rule.get_new_command(command)
+        if isinstance(new_commands, str):
+            new_commands = [new_commands]
+        response['match'] = True
+        response['new_commands'] = [str(new_command) for new_command in new_commands]
+    return response
+
+
+def main():
+    try:
+        response = evaluate(json.load(sys.stdin))
+    except Exception:
+        response = {'match': False, 'new_commands': [], 'error': traceback.format_exc()}
+    # Rules may print while they run, so the response goes out as the last line
+    sys.stdout.write('\n' + json.dumps(response) + '\n')
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,529 @@
+//! Bridge to custom rules written for the original Python thefuck.
+//!
+//! Each `*.py` file in the legacy rules directory (`~/.config/thefuck/rules`) is loaded as one
+//! rule named after the file stem. The rule runs in a Python subprocess that gets the command
+//! and its output as JSON on stdin and answers with the match and corrections on stdout, so
+//! rules keep working unchanged. If the `thefuck` package isn't installed, the bridge provides
+//! the helpers rules commonly import from it (`for_app`, `sudo_support`, `replace_argument`,
+//! `get_closest`, ...).
+//!
+//! Rules are described once when they're loaded, so a rule's `enabled_by_default`, `priority`
+//! and `for_app` programs are honoured without running Python for unrelated commands. A picked
+//! correction's `side_effect` is run by [`run_side_effect`] before the command.
+
+use crate::config::Settings;
+use crate::rules::{DEFAULT_PRIORITY, Rule, is_app};
+use crate::types::{Command, CommandResult, CorrectedCommand};
+use crate::utils;
+use crate::{TheFuckError, TheFuckResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command as ProcessCommand;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Python program that evaluates a legacy rule
+const BRIDGE: &str = include_str!("bridge.py");
+
+/// Extension of legacy rule files
+pub const RULE_EXTENSION: &str = "py";
+
+/// Time a legacy rule gets to answer before it's killed
+const BRIDGE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Command sent to the bridge
+#[derive(Serialize)]
+struct Request<'a> {
+    rule: &'a Path,
+    script: &'a str,
+    output: &'a str,
+    cwd: &'a str,
+    #[allow(clippy::type_complexity)]
+    env: &'a HashMap<String, String>,
+    /// The picked correction, to run the rule's side effect for it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::type_complexity)]
+    new_command: Option<&'a str>,
+}
+
+/// Rules sent to the bridge to be described
+#[derive(Serialize)]
+struct DescribeRequest<'a> {
+    #[allow(clippy::type_complexity)]
+    describe: &'a [PathBuf],
+}
+
+/// What a legacy rule declares, read once when it's loaded
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+struct Description {
+    enabled_by_default: bool,
+    #[allow(clippy::type_complexity)]
+    priority: Option<u32>,
+    #[allow(clippy::type_complexity)]
+    apps: Option<Vec<String>>,
+    side_effect: bool,
+    #[allow(clippy::type_complexity)]
+    error: Option<String>,
+}
+
+impl Default for Description {
+    fn default() -> Self {
+        Self {
+            enabled_by_default: true,
+            priority: None,
+            apps: None,
+            side_effect: false,
+            error: None,
+        }
+    }
+}
+
+/// Result of evaluating a legacy rule
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+struct Evaluation {
+    #[serde(rename = "match")]
+    matched: bool,
+    #[serde(default)]
+    #[allow(clippy::type_complexity)]
+    new_commands: Vec<String>,
+    #[allow(clippy::type_complexity)]
+    priority: Option<u32>,
+    #[allow(clippy::type_complexity)]
+    error: Option<String>,
+}
+
+/// Finds the Python interpreter used to run legacy rules
+#[allow(clippy::type_complexity)]
+pub fn find_python() -> Option<PathBuf> {
+    utils::which("python3").or_else(|| utils::which("python"))
+}
+
+/// Runs the bridge with a request, killing it if it doesn't finish in time
+#[allow(clippy::type_complexity)]
+fn run_bridge(python: &Path, request: &str) -> TheFuckResult<String> {
+    let output = utils::run_with_timeout(
+        ProcessCommand::new(python).arg("-c").arg(BRIDGE),
+        request,
+        BRIDGE_TIMEOUT,
+    )?;
+    if output.timed_out {
+        return Err(TheFuckError::process_error("legacy rule timed out"));
+    }
+    Ok(output.stdout)
+}
+
+/// Parses the last line the bridge printed, since rules may print their own
+#[allow(clippy::type_complexity)]
+fn parse_response<T: serde::de::DeserializeOwned>(response: &str) -> TheFuckResult<T> {
+    let line = response
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .ok_or_else(|| TheFuckError::process_error("legacy rule didn't answer"))?;
+    Ok(serde_json::from_str(line)?)
+}
+
+/// Describes legacy rules in one bridge run
+#[allow(clippy::type_complexity)]
+fn describe(paths: &[PathBuf], python: &Path) -> TheFuckResult<Vec<Description>> {
+    let request = serde_json::to_string(&DescribeRequest { describe: paths })?;
+    let descriptions: Vec<Description> = parse_response(&run_bridge(python, &request)?)?;
+    if descriptions.len() != paths.len() {
+        return Err(TheFuckError::process_error(
+            "legacy rules weren't all described",
+        ));
+    }
+    Ok(descriptions)
+}
+
+/// Runs the side effect of the legacy rule that suggested a correction, if it has one
+#[allow(clippy::type_complexity)]
+pub fn run_side_effect(correction: &CorrectedCommand, result: &CommandResult) -> TheFuckResult<()> {
+    if correction.side_effects.is_empty() || correction.rule.is_empty() {
+        return Ok(());
+    }
+    let Some(dir) = Settings::legacy_rules_dir() else {
+        return Ok(());
+    };
+    let path = dir.join(format!("{}.{RULE_EXTENSION}", correction.rule));
+    if !path.is_file() {
+        return Ok(());
+    }
+    let python =
+        find_python().ok_or_else(|| TheFuckError::process_error("Python isn't installed"))?;
+    PythonRule::new(&path, &python)?.run_side_effect(correction, result)
+}
+
+/// A rule written for the original Python thefuck
+pub struct PythonRule {
+    name: &'static str,
+    path: PathBuf,
+    python: PathBuf,
+    enabled_by_default: bool,
+    #[allow(clippy::type_complexity)]
+    priority: Option<u32>,
+    /// Programs from the rule's `for_app`, empty if it can match any command
+    #[allow(clippy::type_complexity)]
+    programs: &'static [&'static str],
+    side_effect: bool,
+    /// The last evaluated command and output, since matching and correcting run the rule once
+    #[allow(clippy::type_complexity)]
+    last: Mutex<Option<(String, String, Evaluation)>>,
+}
+
+impl PythonRule {
+    /// Creates a rule from a legacy rule file, naming it after the file stem
+    #[allow(clippy::type_complexity)]
+    pub fn new(path: &Path, python: &Path) -> TheFuckResult<Self> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .ok_or_else(|| TheFuckError::path_error(path.display().to_string()))?;
+        Ok(Self {
+            // Rule names are static, and rules are loaded once per process
+            name: Box::leak(name.into_boxed_str()),
+            path: path.to_path_buf(),
+            python: python.to_path_buf(),
+            enabled_by_default: true,
+            priority: None,
+            programs: &[],
+            side_effect: false,
+            last: Mutex::new(None),
+        })
+    }
+
+    /// Applies what the rule declares
+    fn with_description(mut self, description: Description) -> Self {
+        self.enabled_by_default = description.enabled_by_default;
+        self.priority = description.priority;
+        if let Some(apps) = description.apps {
+            // Like the rule names, the programs live as long as the process
+            #[allow(clippy::type_complexity)]
+            let programs: Vec<&'static str> = apps
+                .into_iter()
+                .map(|app| &*Box::leak(app.into_boxed_str()))
+                .collect();
+            self.programs = Box::leak(programs.into_boxed_slice());
+        }
+        self.side_effect = description.side_effect;
+        self
+    }
+
+    /// Loads every legacy rule in a directory, sorted by file name
+    #[allow(clippy::type_complexity)]
+    pub fn load_dir(dir: &Path, python: &Path) -> Vec<Self> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        #[allow(clippy::type_complexity)]
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension == RULE_EXTENSION)
+                    && !path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with('_'))
+            })
+            .collect();
+        paths.sort();
+        let descriptions = describe(&paths, python).unwrap_or_else(|e| {
+            tracing::warn!("failed to describe the legacy rules: {e}");
+            vec![Description::default(); paths.len()]
+        });
+        paths
+            .iter()
+            .zip(descriptions)
+            .filter_map(|(path, description)| {
+                if let Some(error) = &description.error {
+                    tracing::warn!("failed to load legacy rule {}: {error}", path.display());
+                    return None;
+                }
+                Some(Self::new(path, python).ok()?.with_description(description))
+            })
+            .collect()
+    }
+
+    /// Checks if the command runs one of the rule's programs, with or without `sudo`
+    fn targets(&self, command: &Command) -> bool {
+        self.programs.is_empty()
+            || is_app(command, self.programs)
+            || (command.program() == Some("sudo")
+                && command
+                    .argument(0)
+                    .is_some_and(|program| self.programs.contains(&program)))
+    }
+
+    /// Evaluates the rule, reusing the previous evaluation of the same command
+    fn evaluate(&self, command: &Command, result: &CommandResult) -> Evaluation {
+        let output = result.output();
+        let mut last = self.last.lock().unwrap_or_else(|p| p.into_inner());
+        if let Some((script, previous_output, evaluation)) = last.as_ref() {
+            if *script == command.text && *previous_output == output {
+                return evaluation.clone();
+            }
+        }
+
+        let evaluation = self
+            .run(command, &output, None)
+            .unwrap_or_else(|e| Evaluation {
+                error: Some(e.to_string()),
+                ..Evaluation::default()
+            });
+        if let Some(error) = &evaluation.error {
+            tracing::warn!("legacy rule {} failed: {error}", self.name);
+        }
+        *last = Some((command.text.clone(), output, evaluation.clone()));
+        evaluation
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn run(
+        &self,
+        command: &Command,
+        output: &str,
+        new_command: Option<&str>,
+    ) -> TheFuckResult<Evaluation> {
+        let request = serde_json::to_string(&Request {
+            rule: &self.path,
+            script: &command.text,
+            output,
+            cwd: &command.cwd,
+            env: &command.env,
+            new_command,
+        })?;
+        parse_response(&run_bridge(&self.python, &request)?)
+    }
+
+    /// Runs the rule's `side_effect` for a picked correction
+    #[allow(clippy::type_complexity)]
+    pub fn run_side_effect(
+        &self,
+        correction: &CorrectedCommand,
+        result: &CommandResult,
+    ) -> TheFuckResult<()> {
+        let output = result.output();
+        let evaluation = self.run(&correction.original, &output, Some(&correction.text))?;
+        match evaluation.error {
+            Some(error) => Err(TheFuckError::process_error(format!(
+                "side effect of legacy rule {} failed: {error}",
+                self.name
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Rule for PythonRule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+            .or_else(|| {
+                self.last
+                    .lock()
+                    .unwrap_or_else(|p| p.into_inner())
+                    .as_ref()
+                    .and_then(|(_, _, evaluation)| evaluation.priority)
+            })
+            .unwrap_or(DEFAULT_PRIORITY)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        self.programs
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        self.enabled_by_default
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        self.targets(command) && self.evaluate(command, result).matched
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let evaluation = self.evaluate(command, result);
+        if evaluation.matched {
+            evaluation.new_commands
+        } else {
+            Vec::new()
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_corrections(&self, command: &Command, result: &CommandResult) -> Vec<CorrectedCommand> {
+        self.get_new_commands(command, result)
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let priority = self.priority().saturating_sub(index as u32);
+                let correction =
+                    CorrectedCommand::new(text, command.clone(), priority).with_rule(self.name);
+                if self.side_effect {
+                    correction
+                        .with_side_effect(format!("runs `side_effect` of {}", self.path.display()))
+                } else {
+                    correction
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const GIT_PUSH: &str = "\
+from thefuck.utils import for_app, replace_argument
+from thefuck.specific.sudo import sudo_support
+
+priority = 1100
+
+
+@sudo_support
+@for_app('git')
+def match(command):
+    return 'push' not in command.script and 'did you mean' in command.output
+
+
+@sudo_support
+def get_new_command(command):
+    print('noise')
+    return replace_argument(command.script, command.script_parts[1], 'push')
+";
+
+    #[allow(clippy::type_complexity)]
+    fn rule(source: &str) -> Option<(tempfile::TempDir, PythonRule)> {
+        let python = find_python()?;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("git_push.py");
+        std::fs::write(&path, source).unwrap();
+        let rule = PythonRule::new(&path, &python).unwrap();
+        Some((dir, rule))
+    }
+
+    #[test]
+    fn test_match() {
+        let Some((_dir, rule)) = rule(GIT_PUSH) else {
+            return;
+        };
+        let result = CommandResult::failure(1, "git: 'psuh'. did you mean push?".to_string());
+        let command = Command::new("git psuh".to_string(), Shell::Bash);
+        assert!(rule.is_match(&command, &result));
+        assert_eq!(rule.priority(), 1100);
+
+        let command = Command::new("hg psuh".to_string(), Shell::Bash);
+        assert!(!rule.is_match(&command, &result));
+        let command = Command::new("git psuh".to_string(), Shell::Bash);
+        assert!(!rule.is_match(&command, &CommandResult::failure(1, String::new())));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let Some((_dir, rule)) = rule(GIT_PUSH) else {
+            return;
+        };
+        let result = CommandResult::failure(1, "git: 'psuh'. did you mean push?".to_string());
+        let command = Command::new("sudo git psuh origin".to_string(), Shell::Bash);
+        assert_eq!(
+            rule.get_new_commands(&command, &result),
+            vec!["sudo git push origin"]
+        );
+    }
+
+    #[test]
+    fn test_broken_rule() {
+        let Some((_dir, rule)) = rule("def match(command):\n    raise ValueError()\n") else {
+            return;
+        };
+        let command = Command::new("git psuh".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "error".to_string());
+        assert!(!rule.is_match(&command, &result));
+        assert!(rule.get_new_commands(&command, &result).is_empty());
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("git_push.py"), GIT_PUSH).unwrap();
+        std::fs::write(dir.path().join("__init__.py"), "").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let python = Path::new("python3");
+        #[allow(clippy::type_complexity)]
+        let names: Vec<&str> = PythonRule::load_dir(dir.path(), python)
+            .iter()
+            .map(|rule| rule.name())
+            .collect();
+        assert_eq!(names, vec!["git_push"]);
+        assert!(PythonRule::load_dir(&dir.path().join("missing"), python).is_empty());
+    }
+
+    #[test]
+    fn test_load_dir_describes_rules() {
+        let Some(python) = find_python() else {
+            return;
+        };
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("git_push.py"), GIT_PUSH).unwrap();
+        std::fs::write(
+            dir.path().join("touch.py"),
+            "\
+enabled_by_default = False
+
+
+def match(command):
+    return True
+
+
+def get_new_command(command):
+    return 'ls'
+
+
+def side_effect(old_cmd, command):
+    open('touched', 'w').write(old_cmd.script + ' -> ' + command)
+",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("broken.py"), "import missing_module\n").unwrap();
+
+        let rules = PythonRule::load_dir(dir.path(), &python);
+        #[allow(clippy::type_complexity)]
+        let names: Vec<&str> = rules.iter().map(|rule| rule.name()).collect();
+        assert_eq!(names, vec!["git_push", "touch"]);
+
+        let (git_push, touch) = (&rules[0], &rules[1]);
+        assert!(git_push.enabled_by_default());
+        assert_eq!(git_push.programs(), ["git"]);
+        assert_eq!(git_push.priority(), 1100);
+        let result = CommandResult::failure(1, "did you mean push?".to_string());
+        assert!(!git_push.is_match(&Command::new("hg psuh".to_string(), Shell::Bash), &result));
+        assert!(git_push.is_match(
+            &Command::new("sudo git psuh".to_string(), Shell::Bash),
+            &result
+        ));
+
+        assert!(!touch.enabled_by_default());
+        assert!(touch.programs().is_empty());
+        let command = Command::new("lss".to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string());
+        let corrections = touch.get_corrections(&command, &result);
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].side_effects.len(), 1);
+        touch.run_side_effect(&corrections[0], &result).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("touched")).unwrap(),
+            "lss -> ls"
+        );
+    }
+}
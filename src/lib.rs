@@ -1,16 +1,45 @@
 pub mod cli;
 pub mod config;
 pub mod core;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod daemon;
+pub mod declarative;
+pub mod docs;
+pub mod engine;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod guard;
+pub mod i18n;
+pub mod instant;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod learning;
+#[cfg(feature = "python-rules")]
+pub mod legacy;
+pub mod logging;
+#[cfg(feature = "wasm-plugins")]
+pub mod plugins;
 pub mod rules;
+#[cfg(feature = "rhai-rules")]
+pub mod scripting;
+pub mod server;
 pub mod shells;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod stats;
 pub mod types;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ui;
+pub mod undo;
 pub mod utils;
 
 pub use cli::Cli;
+pub use config::Settings;
+#[cfg(not(target_arch = "wasm32"))]
 pub use core::run;
+pub use engine::{CorrectionEngine, CorrectionEngineBuilder, OutputCapture};
 pub use error::{ErrorContext, TheFuckError, TheFuckResult};
-pub use types::{Command, CommandResult, CorrectedCommand, ParsedCommand, Shell};
+pub use rules::{Ranking, Rule, RuleRegistry};
+pub use types::{Command, CommandResult, CorrectedCommand, Explanation, ParsedCommand, Shell};
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const NAME: &str = env!("CARGO_PKG_NAME");
@@ -0,0 +1,182 @@
+//! Logging: warnings go to stderr, and with debugging on everything also goes to a log file.
+//!
+//! The log file lives in the cache directory so it can be attached to bug reports. When it
+//! grows past [`MAX_LOG_SIZE`] it is rotated to `debug.log.1`, `debug.log.2`, ..., keeping at
+//! most [`MAX_LOG_FILES`] of them.
+
+use crate::config::Settings;
+use crate::i18n;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{Layer, fmt};
+
+/// Name of the log file inside the logs directory
+pub const LOG_FILE: &str = "debug.log";
+
+/// Name of the logs directory inside the cache directory
+pub const LOGS_DIR: &str = "logs";
+
+/// Size past which the log file is rotated, in bytes
+pub const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+/// Number of rotated log files kept besides the current one
+pub const MAX_LOG_FILES: usize = 3;
+
+/// Gets the path of the log file, e.g. `~/.cache/thefuck-rs/logs/debug.log`
+#[allow(clippy::type_complexity)]
+pub fn log_path() -> Option<PathBuf> {
+    Settings::cache_dir().map(|dir| dir.join(LOGS_DIR).join(LOG_FILE))
+}
+
+/// Gets the path of a rotated log file, e.g. `debug.log.1`
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+/// A log file that is rotated when it grows too large
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+}
+
+impl RotatingFile {
+    /// Opens a log file for appending, creating it and its directory if needed
+    #[allow(clippy::type_complexity)]
+    pub fn open(path: &Path, max_size: u64, max_files: usize) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            size,
+            max_size,
+            max_files,
+        })
+    }
+
+    /// Shifts the rotated files up by one, dropping the oldest, and starts a new file
+    #[allow(clippy::type_complexity)]
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            self.file.set_len(0)?;
+        } else {
+            let _ = std::fs::remove_file(rotated_path(&self.path, self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = rotated_path(&self.path, index);
+                if from.exists() {
+                    std::fs::rename(&from, rotated_path(&self.path, index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+            self.file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+        }
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    #[allow(clippy::type_complexity)]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Sets up logging, also writing debug details to the log file when `debug` is set
+///
+/// Gets the path of the log file when one is written.
+#[allow(clippy::type_complexity)]
+pub fn init(debug: bool) -> Option<PathBuf> {
+    let stderr = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(LevelFilter::INFO);
+    let log_file = if debug {
+        log_path().and_then(
+            |path| match RotatingFile::open(&path, MAX_LOG_SIZE, MAX_LOG_FILES) {
+                Ok(file) => Some((path, file)),
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        i18n::tr_with(
+                            "log-open-failed",
+                            &[
+                                ("path", path.display().to_string().into()),
+                                ("error", e.to_string().into())
+                            ]
+                        )
+                    );
+                    None
+                }
+            },
+        )
+    } else {
+        None
+    };
+    let Some((path, file)) = log_file else {
+        tracing_subscriber::registry().with(stderr).init();
+        return None;
+    };
+    let file_layer = fmt::layer()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_filter(LevelFilter::DEBUG);
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(file_layer)
+        .init();
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotating_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join(LOG_FILE);
+        let mut file = RotatingFile::open(&path, 10, 2).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        let read = |path: PathBuf| std::fs::read_to_string(path).unwrap();
+        assert_eq!(read(path.clone()), "fourth\n");
+        assert_eq!(read(rotated_path(&path, 1)), "third\n");
+        assert_eq!(read(rotated_path(&path, 2)), "second\n");
+        assert!(!rotated_path(&path, 3).exists());
+
+        drop(file);
+        let mut file = RotatingFile::open(&path, 10, 2).unwrap();
+        file.write_all(b"fifth\n").unwrap();
+        assert_eq!(read(path.clone()), "fifth\n");
+        assert_eq!(read(rotated_path(&path, 1)), "fourth\n");
+    }
+}
@@ -1,17 +1,25 @@
 use clap::Parser;
-use thefuck_rs::{TheFuckResult, cli::Cli};
+use thefuck_rs::{Settings, cli::Cli, i18n, logging};
 
 #[tokio::main]
-#[allow(clippy::type_complexity)]
-async fn main() -> TheFuckResult<()> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
-
+async fn main() {
     // Parse command line arguments
     let cli = Cli::parse();
 
-    // Run the application
-    thefuck_rs::run(cli).await?;
+    // Initialize logging, on stderr since stdout is evaluated by the shell alias, and in the
+    // log file when debugging
+    let debug = cli.debug || Settings::load().is_ok_and(|settings| settings.debug);
+    if let Some(path) = logging::init(debug) {
+        eprintln!(
+            "{}",
+            i18n::tr_with("logging-to", &[("path", path.display().to_string().into())])
+        );
+    }
 
-    Ok(())
+    // Run the application, describing errors in the user's language
+    if let Err(e) = thefuck_rs::run(cli).await {
+        tracing::debug!("failed: {e}");
+        eprintln!("{}", i18n::describe_error(&e));
+        std::process::exit(1);
+    }
 }
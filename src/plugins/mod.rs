@@ -0,0 +1,440 @@
+//! Third-party rules loaded from WebAssembly modules.
+//!
+//! Each `*.wasm` file in the plugins directory is loaded as one rule named after the file stem.
+//! A plugin module must export:
+//!
+//! - `memory`: the linear memory the host reads from and writes to
+//! - `is_match() -> i32`: non-zero if the rule applies to the failed command
+//! - `get_new_commands() -> i64`: a pointer (high 32 bits) and length (low 32 bits) of the
+//!   corrected commands, UTF-8 encoded and separated by newlines
+//! - `priority() -> i32` (optional): the priority of the corrections
+//!
+//! Plugins run without WASI, so they can't touch the file system, network or processes.
+//! They read the failed command through these imports from the `thefuck` module, which copy
+//! at most `cap` bytes to `ptr` and return the full length of the value:
+//!
+//! - `command(ptr, cap) -> i32`: the command text
+//! - `output(ptr, cap) -> i32`: the combined stdout and stderr of the command
+//! - `cwd(ptr, cap) -> i32`: the working directory of the command
+//! - `env(name_ptr, name_len, ptr, cap) -> i32`: a variable of the command's environment,
+//!   or -1 if it isn't set
+//! - `exit_code() -> i32`: the exit code of the command
+//!
+//! Every call runs in a fresh instance with bounded fuel and memory, so a misbehaving plugin
+//! can't hang or exhaust the host; a trap counts as no match.
+
+use crate::rules::{DEFAULT_PRIORITY, Rule};
+use crate::types::{Command, CommandResult};
+use crate::{TheFuckError, TheFuckResult};
+use std::collections::HashMap;
+use std::path::Path;
+use wasmtime::{
+    Caller, Config, Engine, Extern, Instance, InstancePre, Linker, Memory, Module, Store,
+    StoreLimits, StoreLimitsBuilder,
+};
+
+/// Extension of plugin modules in the plugins directory
+pub const PLUGIN_EXTENSION: &str = "wasm";
+
+/// Fuel available to a single call into a plugin
+const FUEL: u64 = 10_000_000;
+
+/// Maximum linear memory of a plugin instance, in bytes
+const MAX_MEMORY: usize = 16 << 20;
+
+/// The failed command as seen by a plugin
+#[derive(Default)]
+struct HostState {
+    command: String,
+    output: String,
+    cwd: String,
+    #[allow(clippy::type_complexity)]
+    env: HashMap<String, String>,
+    exit_code: i32,
+    limits: StoreLimits,
+}
+
+impl HostState {
+    fn new(command: &Command, result: &CommandResult) -> Self {
+        Self {
+            command: command.text.clone(),
+            output: result.output(),
+            cwd: command.cwd.clone(),
+            env: command.env.clone(),
+            exit_code: result.exit_code,
+            limits: StoreLimits::default(),
+        }
+    }
+}
+
+/// Gets the exported memory of the calling plugin
+#[allow(clippy::type_complexity)]
+fn guest_memory(caller: &mut Caller<'_, HostState>) -> wasmtime::Result<Memory> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(wasmtime::Error::msg("plugin doesn't export its memory")),
+    }
+}
+
+/// Copies as much of the value as fits in the guest buffer, returning the value's full length
+#[allow(clippy::type_complexity)]
+fn write_guest(
+    caller: &mut Caller<'_, HostState>,
+    value: &[u8],
+    ptr: i32,
+    cap: i32,
+) -> wasmtime::Result<i32> {
+    let memory = guest_memory(caller)?;
+    let len = value.len().min(cap.max(0) as usize);
+    memory.write(caller, ptr as u32 as usize, &value[..len])?;
+    Ok(value.len() as i32)
+}
+
+/// Reads a UTF-8 string from guest memory
+#[allow(clippy::type_complexity)]
+fn read_guest(
+    memory: &Memory,
+    store: impl wasmtime::AsContext,
+    ptr: u32,
+    len: u32,
+) -> wasmtime::Result<String> {
+    let data = memory.data(&store);
+    let start = ptr as usize;
+    let bytes = start
+        .checked_add(len as usize)
+        .and_then(|end| data.get(start..end))
+        .ok_or_else(|| wasmtime::Error::msg("plugin returned an out of bounds string"))?;
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Builds the host API exposed to plugins
+#[allow(clippy::type_complexity)]
+fn host_linker(engine: &Engine) -> wasmtime::Result<Linker<HostState>> {
+    let mut linker = Linker::new(engine);
+    linker.func_wrap(
+        "thefuck",
+        "command",
+        |mut caller: Caller<'_, HostState>, ptr: i32, cap: i32| {
+            let value = caller.data().command.clone();
+            write_guest(&mut caller, value.as_bytes(), ptr, cap)
+        },
+    )?;
+    linker.func_wrap(
+        "thefuck",
+        "output",
+        |mut caller: Caller<'_, HostState>, ptr: i32, cap: i32| {
+            let value = caller.data().output.clone();
+            write_guest(&mut caller, value.as_bytes(), ptr, cap)
+        },
+    )?;
+    linker.func_wrap(
+        "thefuck",
+        "cwd",
+        |mut caller: Caller<'_, HostState>, ptr: i32, cap: i32| {
+            let value = caller.data().cwd.clone();
+            write_guest(&mut caller, value.as_bytes(), ptr, cap)
+        },
+    )?;
+    linker.func_wrap(
+        "thefuck",
+        "env",
+        |mut caller: Caller<'_, HostState>, name_ptr: i32, name_len: i32, ptr: i32, cap: i32| {
+            let memory = guest_memory(&mut caller)?;
+            let name = read_guest(&memory, &caller, name_ptr as u32, name_len as u32)?;
+            match caller.data().env.get(&name).cloned() {
+                Some(value) => write_guest(&mut caller, value.as_bytes(), ptr, cap),
+                None => Ok(-1),
+            }
+        },
+    )?;
+    linker.func_wrap("thefuck", "exit_code", |caller: Caller<'_, HostState>| {
+        caller.data().exit_code
+    })?;
+    Ok(linker)
+}
+
+/// Compiles plugin modules against the sandboxed host API
+pub struct PluginLoader {
+    engine: Engine,
+    #[allow(clippy::type_complexity)]
+    linker: Linker<HostState>,
+}
+
+impl PluginLoader {
+    /// Creates a loader with fuel metering enabled
+    #[allow(clippy::type_complexity)]
+    pub fn new() -> TheFuckResult<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(plugin_error)?;
+        let linker = host_linker(&engine).map_err(plugin_error)?;
+        Ok(Self { engine, linker })
+    }
+
+    /// Loads a plugin from the bytes of a WASM module (or its text format)
+    #[allow(clippy::type_complexity)]
+    pub fn load(&self, name: &str, bytes: impl AsRef<[u8]>) -> TheFuckResult<WasmRule> {
+        let context = |e: wasmtime::Error| plugin_error(format!("{name}: {e}"));
+        let module = Module::new(&self.engine, bytes).map_err(context)?;
+        for export in ["memory", "is_match", "get_new_commands"] {
+            if module.get_export(export).is_none() {
+                return Err(TheFuckError::plugin_error(format!(
+                    "{name}: missing export `{export}`"
+                )));
+            }
+        }
+        let pre = self.linker.instantiate_pre(&module).map_err(context)?;
+
+        let mut rule = WasmRule {
+            // Rule names are static, and plugins are loaded once per process
+            name: Box::leak(name.to_string().into_boxed_str()),
+            priority: DEFAULT_PRIORITY,
+            pre,
+        };
+        if module.get_export("priority").is_some() {
+            let priority = rule
+                .call(HostState::default(), |store, instance| {
+                    instance
+                        .get_typed_func::<(), i32>(&mut *store, "priority")?
+                        .call(store, ())
+                })
+                .map_err(context)?;
+            rule.priority = priority.max(0) as u32;
+        }
+        Ok(rule)
+    }
+
+    /// Loads a plugin from a file, naming it after the file stem
+    #[allow(clippy::type_complexity)]
+    pub fn load_file(&self, path: &Path) -> TheFuckResult<WasmRule> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .ok_or_else(|| TheFuckError::path_error(path.display().to_string()))?;
+        let bytes = std::fs::read(path)
+            .map_err(|e| TheFuckError::FileReadError(format!("{}: {e}", path.display())))?;
+        self.load(&name, bytes)
+    }
+
+    /// Loads every plugin in a directory, sorted by file name and skipping invalid modules
+    #[allow(clippy::type_complexity)]
+    pub fn load_dir(&self, dir: &Path) -> Vec<WasmRule> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        #[allow(clippy::type_complexity)]
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension == PLUGIN_EXTENSION)
+            })
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .filter_map(|path| match self.load_file(path) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    tracing::warn!("skipping plugin {}: {e}", path.display());
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+fn plugin_error(error: impl std::fmt::Display) -> TheFuckError {
+    TheFuckError::plugin_error(error.to_string())
+}
+
+/// A rule implemented by a WASM plugin
+pub struct WasmRule {
+    name: &'static str,
+    priority: u32,
+    #[allow(clippy::type_complexity)]
+    pre: InstancePre<HostState>,
+}
+
+impl WasmRule {
+    /// Runs a function against a fresh, resource-limited instance of the plugin
+    #[allow(clippy::type_complexity)]
+    fn call<R>(
+        &self,
+        mut state: HostState,
+        f: impl FnOnce(&mut Store<HostState>, &Instance) -> wasmtime::Result<R>,
+    ) -> wasmtime::Result<R> {
+        state.limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY)
+            .instances(1)
+            .build();
+        let mut store = Store::new(self.pre.module().engine(), state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(FUEL)?;
+        let instance = self.pre.instantiate(&mut store)?;
+        f(&mut store, &instance)
+    }
+}
+
+impl Rule for WasmRule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let matched = self.call(HostState::new(command, result), |store, instance| {
+            instance
+                .get_typed_func::<(), i32>(&mut *store, "is_match")?
+                .call(store, ())
+        });
+        match matched {
+            Ok(matched) => matched != 0,
+            Err(e) => {
+                tracing::warn!("plugin {} failed to match: {e}", self.name);
+                false
+            }
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let commands = self.call(HostState::new(command, result), |store, instance| {
+            let packed = instance
+                .get_typed_func::<(), i64>(&mut *store, "get_new_commands")?
+                .call(&mut *store, ())?;
+            let memory = instance
+                .get_memory(&mut *store, "memory")
+                .ok_or_else(|| wasmtime::Error::msg("plugin doesn't export its memory"))?;
+            read_guest(&memory, &*store, (packed >> 32) as u32, packed as u32)
+        });
+        match commands {
+            Ok(commands) => commands
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+            Err(e) => {
+                tracing::warn!("plugin {} failed to correct: {e}", self.name);
+                Vec::new()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    /// Suggests `git push` for `git psuh`, echoing the `REMOTE` variable after it if set
+    const GIT_PUSH: &str = r#"
+        (module
+          (import "thefuck" "command" (func $command (param i32 i32) (result i32)))
+          (import "thefuck" "env" (func $env (param i32 i32 i32 i32) (result i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 0) "git push ")
+          (data (i32.const 64) "REMOTE")
+          (func (export "priority") (result i32) (i32.const 1500))
+          (func (export "is_match") (result i32)
+            (drop (call $command (i32.const 128) (i32.const 64)))
+            (i32.eq (i32.load8_u (i32.const 133)) (i32.const 115)))
+          (func (export "get_new_commands") (result i64)
+            (local $len i32)
+            (local.set $len (call $env (i32.const 64) (i32.const 6) (i32.const 9) (i32.const 32)))
+            (if (i32.lt_s (local.get $len) (i32.const 0))
+              (then (local.set $len (i32.const -1))))
+            (i64.extend_i32_u (i32.add (i32.const 9) (local.get $len)))))
+    "#;
+
+    const SPIN: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "is_match") (result i32) (loop $spin (br $spin)) (i32.const 1))
+          (func (export "get_new_commands") (result i64) (i64.const 0)))
+    "#;
+
+    #[allow(clippy::type_complexity)]
+    fn git(text: &str, env: &[(&str, &str)]) -> Command {
+        Command::new(text.to_string(), Shell::Bash).with_env(
+            env.iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_load() {
+        let loader = PluginLoader::new().unwrap();
+        let rule = loader.load("git_push", GIT_PUSH).unwrap();
+        assert_eq!(rule.name(), "git_push");
+        assert_eq!(rule.priority(), 1500);
+
+        let missing = "(module (memory (export \"memory\") 1))";
+        assert!(matches!(
+            loader.load("missing", missing),
+            Err(TheFuckError::PluginError(_))
+        ));
+        assert!(loader.load("invalid", "not wasm").is_err());
+    }
+
+    #[test]
+    fn test_match() {
+        let rule = PluginLoader::new()
+            .unwrap()
+            .load("git_push", GIT_PUSH)
+            .unwrap();
+        let result = CommandResult::failure(1, String::new());
+        assert!(rule.is_match(&git("git psuh", &[]), &result));
+        assert!(!rule.is_match(&git("git push", &[]), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let rule = PluginLoader::new()
+            .unwrap()
+            .load("git_push", GIT_PUSH)
+            .unwrap();
+        let result = CommandResult::failure(1, String::new());
+        assert_eq!(
+            rule.get_new_commands(&git("git psuh", &[]), &result),
+            vec!["git push"]
+        );
+        assert_eq!(
+            rule.get_new_commands(&git("git psuh", &[("REMOTE", "origin")]), &result),
+            vec!["git push origin"]
+        );
+    }
+
+    #[test]
+    fn test_runaway_plugin() {
+        let rule = PluginLoader::new().unwrap().load("spin", SPIN).unwrap();
+        let result = CommandResult::failure(1, String::new());
+        assert!(!rule.is_match(&git("git psuh", &[]), &result));
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("git_push.wasm"), GIT_PUSH).unwrap();
+        std::fs::write(dir.path().join("broken.wasm"), "not wasm").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), GIT_PUSH).unwrap();
+
+        let loader = PluginLoader::new().unwrap();
+        #[allow(clippy::type_complexity)]
+        let names: Vec<&str> = loader
+            .load_dir(dir.path())
+            .iter()
+            .map(|rule| rule.name())
+            .collect();
+        assert_eq!(names, vec!["git_push"]);
+        assert!(loader.load_dir(&dir.path().join("missing")).is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Commands built into adb
+#[allow(clippy::type_complexity)]
+const ADB_COMMANDS: &[&str] = &[
+    "backup",
+    "bugreport",
+    "connect",
+    "devices",
+    "disable-verity",
+    "disconnect",
+    "emu",
+    "enable-verity",
+    "forward",
+    "get-devpath",
+    "get-serialno",
+    "get-state",
+    "install",
+    "install-multiple",
+    "jdwp",
+    "keygen",
+    "kill-server",
+    "logcat",
+    "pair",
+    "ppp",
+    "pull",
+    "push",
+    "reboot",
+    "reconnect",
+    "remount",
+    "restore",
+    "reverse",
+    "root",
+    "run-as",
+    "shell",
+    "sideload",
+    "start-server",
+    "sync",
+    "tcpip",
+    "uninstall",
+    "unroot",
+    "usb",
+    "wait-for-device",
+];
+
+/// Global options of adb that take a value
+#[allow(clippy::type_complexity)]
+const OPTIONS_WITH_VALUE: &[&str] = &["-s", "-t", "-H", "-P", "-L"];
+
+/// Fixes misspelled adb commands, e.g. `adb lgcat` -> `adb logcat`
+pub struct AdbUnknownCommand;
+
+/// Gets the first argument after adb's global options, which adb treats as the command
+#[allow(clippy::type_complexity)]
+fn adb_command(command: &Command) -> Option<&str> {
+    let arguments = command.arguments();
+    let mut index = 0;
+    while let Some(arg) = arguments.get(index) {
+        if OPTIONS_WITH_VALUE.contains(arg) {
+            index += 2;
+        } else if arg.starts_with('-') {
+            index += 1;
+        } else {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+impl Rule for AdbUnknownCommand {
+    fn name(&self) -> &'static str {
+        "adb_unknown_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled adb commands, e.g. `adb lgcat` -> `adb logcat`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        (output.contains("unknown command")
+            || output.contains("did you mean")
+            || output.starts_with("Android Debug Bridge version"))
+            && adb_command(command).is_some_and(|name| {
+                !ADB_COMMANDS.contains(&name) && utils::get_closest(name, ADB_COMMANDS).is_some()
+            })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        match adb_command(command) {
+            Some(broken) => utils::replace_command(command.trimmed(), broken, ADB_COMMANDS),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("adb lgcat".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "adb: unknown command lgcat\n".to_string());
+        assert!(AdbUnknownCommand.is_match(&command, &result));
+
+        let command = Command::new("adb -s 1234 shel".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "Android Debug Bridge version 1.0.41\nVersion 34.0.5\n".to_string(),
+        );
+        assert!(AdbUnknownCommand.is_match(&command, &result));
+
+        let command = Command::new("adb logcat".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "error: no devices/emulators found\n".to_string());
+        assert!(!AdbUnknownCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("adb -s 1234 shel ls".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, String::new());
+        assert_eq!(
+            AdbUnknownCommand.get_new_commands(&command, &result)[0],
+            "adb -s 1234 shell ls"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into apk
+#[allow(clippy::type_complexity)]
+const APK_COMMANDS: &[&str] = &[
+    "add", "audit", "cache", "del", "dot", "fetch", "fix", "index", "info", "list", "manifest",
+    "policy", "search", "stats", "update", "upgrade", "verify", "version",
+];
+
+/// Command other package managers install with, typed out of habit
+const INSTALL: &str = "install";
+
+/// Fixes misspelled apk commands and installs missing programs on Alpine,
+/// e.g. `apk isntall vim` -> `apk add vim`, `htop` -> `apk add htop && htop`
+pub struct Apk;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"'([^']+)' is not an apk command|applet '([^']+)' not found")
+            .expect("valid regex")
+    });
+    let captures = regex.captures(output)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(2))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Gets the program the shell couldn't find, e.g. from `sh: htop: not found`
+#[allow(clippy::type_complexity)]
+fn missing_program(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"(?m)^(?:\S+: )?(?:line \d+: )?([^\s:]+): (?:command )?not found")
+            .expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses `apk search -x cmd:<program>` output, dropping the `-<version>-r<release>` suffix
+#[allow(clippy::type_complexity)]
+fn parse_search_output(output: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"^(.+)-[^-]+-r\d+$").expect("valid regex"));
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match regex.captures(line) {
+            Some(captures) => captures[1].to_string(),
+            None => line.to_string(),
+        })
+        .collect()
+}
+
+/// Gets the packages providing the program, from apk's index
+#[allow(clippy::type_complexity)]
+fn providing_packages(program: &str) -> Vec<String> {
+    utils::get_output("apk", &["search", "-x", &format!("cmd:{program}")])
+        .map(|output| parse_search_output(&output))
+        .unwrap_or_default()
+}
+
+impl Rule for Apk {
+    fn name(&self) -> &'static str {
+        "apk"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled apk commands and installs missing programs on Alpine, e.g. `apk isntall vim` -> `apk add vim`, `htop` -> `apk add htop && htop`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        if !utils::is_available("apk", command) {
+            return false;
+        }
+        let output = result.output();
+        if command.starts_with("apk") {
+            return unknown_command(&output).is_some();
+        }
+        missing_program(&output).is_some_and(|program| !providing_packages(&program).is_empty())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let script = command.trimmed();
+        if command.starts_with("apk") {
+            let Some(broken) = unknown_command(&output) else {
+                return Vec::new();
+            };
+            // `install`, even misspelled, is the usual habit from other package managers
+            let mut known = APK_COMMANDS.to_vec();
+            known.push(INSTALL);
+            if utils::get_closest(&broken, &known).is_some_and(|closest| closest == INSTALL) {
+                return vec![command.replace_argument(&broken, "add")];
+            }
+            return utils::replace_command(script, &broken, APK_COMMANDS);
+        }
+        missing_program(&output)
+            .map(|program| providing_packages(&program))
+            .unwrap_or_default()
+            .iter()
+            .map(|package| format!("apk add {package} && {script}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::sudo_support;
+    use crate::types::Shell;
+    use crate::utils::resolve::FUNCTIONS_ENV;
+    use std::collections::HashMap;
+
+    /// Gets a command run where the shell has an `apk`
+    #[allow(clippy::type_complexity)]
+    fn on_alpine(script: &str) -> Command {
+        Command::new(script.to_string(), Shell::Unknown("ash".to_string())).with_env(HashMap::from(
+            [(FUNCTIONS_ENV.to_string(), "apk".to_string())],
+        ))
+    }
+
+    #[test]
+    fn test_match() {
+        let command = on_alpine("apk ad vim");
+        let result = CommandResult::failure(
+            1,
+            "ERROR: 'ad' is not an apk command. See 'apk --help'.\n".to_string(),
+        );
+        assert!(Apk.is_match(&command, &result));
+
+        let empty = tempfile::tempdir().unwrap();
+        let elsewhere =
+            Command::new("apk ad vim".to_string(), Shell::Bash).with_env(HashMap::from([(
+                "PATH".to_string(),
+                empty.path().to_string_lossy().to_string(),
+            )]));
+        assert!(!Apk.is_match(&elsewhere, &result));
+
+        let command = on_alpine("apk add vim");
+        let result = CommandResult::success("OK: 30 MiB in 40 packages\n".to_string());
+        assert!(!Apk.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let shell = Shell::Unknown("ash".to_string());
+        for (script, broken, expected) in [
+            ("apk ad vim", "ad", "apk add vim"),
+            ("apk install vim", "install", "apk add vim"),
+            ("apk isntall vim", "isntall", "apk add vim"),
+            ("apk instal vim", "instal", "apk add vim"),
+            ("apk updat", "updat", "apk update"),
+        ] {
+            let command = Command::new(script.to_string(), shell.clone());
+            let result = CommandResult::failure(
+                1,
+                format!("ERROR: '{broken}' is not an apk command. See 'apk --help'.\n"),
+            );
+            assert_eq!(Apk.get_new_commands(&command, &result)[0], expected);
+        }
+
+        let result = CommandResult::failure(
+            1,
+            "ERROR: 'ad' is not an apk command. See 'apk --help'.\n".to_string(),
+        );
+        assert_eq!(
+            sudo_support(Apk).get_new_commands(&on_alpine("sudo apk ad vim"), &result),
+            vec!["sudo apk add vim"]
+        );
+    }
+
+    #[test]
+    fn test_missing_program() {
+        assert_eq!(
+            missing_program("sh: htop: not found\n"),
+            Some("htop".to_string())
+        );
+        assert_eq!(
+            missing_program("bash: htop: command not found\n"),
+            Some("htop".to_string())
+        );
+        assert_eq!(missing_program("ERROR: unable to select packages\n"), None);
+    }
+
+    #[test]
+    fn test_parse_search_output() {
+        assert_eq!(
+            parse_search_output("htop-3.2.2-r1\npy3-pip-23.1.2-r0\nvim\n"),
+            vec!["htop", "py3-pip", "vim"]
+        );
+    }
+}
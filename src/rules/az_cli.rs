@@ -0,0 +1,92 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled Azure CLI commands, e.g. `az providers list` -> `az provider list`
+pub struct AzCli;
+
+#[allow(clippy::type_complexity)]
+fn broken_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(
+            r"'([^']+)' is (?:misspelled or not recognized|not in the '[^']*' command group)",
+        )
+        .expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for AzCli {
+    fn name(&self) -> &'static str {
+        "az_cli"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled Azure CLI commands, e.g. `az providers list` -> `az provider list`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        broken_command(&output).is_some() && !utils::extract_suggestions(&output).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let Some(broken) = broken_command(&output) else {
+            return Vec::new();
+        };
+        utils::extract_suggestions(&output)
+            .iter()
+            .map(|choice| command.replace_argument(&broken, choice))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "az: 'providers' is not in the 'az' command group. See 'az --help'.\n\n\
+        The most similar choice to 'providers' is:\n    provider\n";
+
+    const MISSPELLED_OUTPUT: &str = "az: 'gropu' is misspelled or not recognized by the \
+        system.\n\nThe most similar choices to 'gropu' are:\n    group\n    groups\n\n\
+        Examples from AI knowledge base:\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("az providers list".to_string(), Shell::Bash);
+        let result = CommandResult::failure(2, OUTPUT.to_string());
+        assert!(AzCli.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            1,
+            "ERROR: (AuthorizationFailed) The client does not have authorization\n".to_string(),
+        );
+        assert!(!AzCli.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("az providers list".to_string(), Shell::Bash);
+        let result = CommandResult::failure(2, OUTPUT.to_string());
+        assert_eq!(
+            AzCli.get_new_commands(&command, &result),
+            vec!["az provider list"]
+        );
+
+        let command = Command::new("az gropu list".to_string(), Shell::Bash);
+        let result = CommandResult::failure(2, MISSPELLED_OUTPUT.to_string());
+        assert_eq!(
+            AzCli.get_new_commands(&command, &result),
+            vec!["az group list", "az groups list"]
+        );
+    }
+}
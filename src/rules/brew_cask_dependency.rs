@@ -0,0 +1,125 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Installs casks with `brew install --cask`, either because the requested formula
+/// is actually a cask or because the formula depends on one
+pub struct BrewCaskDependency;
+
+#[allow(clippy::type_complexity)]
+fn cask_install_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"(?m)^\s*brew (?:install --cask|cask install) (\S+)").expect("valid regex")
+    })
+}
+
+/// Gets the casks the error output tells to install, in order of appearance
+#[allow(clippy::type_complexity)]
+fn suggested_casks(result: &CommandResult) -> Vec<String> {
+    let mut casks: Vec<String> = Vec::new();
+    for captures in cask_install_regex().captures_iter(&result.output()) {
+        let cask = captures[1].trim_matches(['"', '\'']).to_string();
+        if !casks.contains(&cask) {
+            casks.push(cask);
+        }
+    }
+    casks
+}
+
+impl Rule for BrewCaskDependency {
+    fn name(&self) -> &'static str {
+        "brew_cask_dependency"
+    }
+
+    fn description(&self) -> &'static str {
+        "Installs casks with `brew install --cask`, either because the requested formula is actually a cask or because the formula depends on one"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        command.argument(0) == Some("install")
+            && !command.contains_argument("--cask")
+            && !suggested_casks(result).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let casks = suggested_casks(result);
+        let packages: Vec<&str> = command
+            .arguments()
+            .into_iter()
+            .skip(1)
+            .filter(|arg| !arg.starts_with('-'))
+            .collect();
+
+        if !packages.is_empty() && packages.iter().all(|p| casks.iter().any(|c| c == p)) {
+            return vec![command.insert_after_argument("install", "--cask")];
+        }
+
+        let dependencies: Vec<&str> = casks
+            .iter()
+            .map(String::as_str)
+            .filter(|cask| !packages.contains(cask))
+            .collect();
+        if dependencies.is_empty() {
+            return Vec::new();
+        }
+        vec![format!(
+            "brew install --cask {} && {}",
+            dependencies.join(" "),
+            command.trimmed()
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const CASK_OUTPUT: &str = "Warning: No available formula with the name \"firefox\".\n\
+        ==> Searching for a previously deleted formula (in the last month)...\n\
+        Error: No previously deleted formula found.\n\
+        It was migrated from homebrew/core to homebrew/cask.\n\
+        You can install it by running:\n  brew install --cask firefox\n";
+
+    const DEPENDENCY_OUTPUT: &str = "sshfs: OsxfuseRequirement unsatisfied!\n\n\
+        You can install with Homebrew-Cask:\n  brew cask install osxfuse\n\n\
+        You can download from:\n  https://osxfuse.github.io/\n\
+        Error: An unsatisfied requirement failed this build.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("brew install firefox".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, CASK_OUTPUT.to_string());
+        assert!(BrewCaskDependency.is_match(&command, &result));
+
+        let command = Command::new("brew install --cask firefox".to_string(), Shell::Bash);
+        assert!(!BrewCaskDependency.is_match(&command, &result));
+
+        let command = Command::new("brew install wget".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "Error: No such formula\n".to_string());
+        assert!(!BrewCaskDependency.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands_for_cask() {
+        let command = Command::new("brew install firefox".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, CASK_OUTPUT.to_string());
+        assert_eq!(
+            BrewCaskDependency.get_new_commands(&command, &result),
+            vec!["brew install --cask firefox"]
+        );
+    }
+
+    #[test]
+    fn test_get_new_commands_for_dependency() {
+        let command = Command::new("brew install sshfs".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, DEPENDENCY_OUTPUT.to_string());
+        assert_eq!(
+            BrewCaskDependency.get_new_commands(&command, &result),
+            vec!["brew install --cask osxfuse && brew install sshfs"]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Suggests a dry run of an overwriting link when `brew link` fails because of conflicting files
+pub struct BrewLink;
+
+impl Rule for BrewLink {
+    fn name(&self) -> &'static str {
+        "brew_link"
+    }
+
+    fn description(&self) -> &'static str {
+        "Suggests a dry run of an overwriting link when `brew link` fails because of conflicting files"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        matches!(command.argument(0), Some("ln" | "link"))
+            && result.output().contains("brew link --overwrite --dry-run")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let Some(subcommand) = command.argument(0) else {
+            return Vec::new();
+        };
+        let script = utils::replace_argument(command.trimmed(), subcommand, "link");
+        vec![utils::insert_after_argument(
+            &script,
+            "link",
+            "--overwrite --dry-run",
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Linking /usr/local/Cellar/coreutils/8.25... \n\
+        Error: Could not symlink bin/gbase32\n\
+        Target /usr/local/bin/gbase32\n\
+        already exists. You may want to remove it:\n  rm '/usr/local/bin/gbase32'\n\n\
+        To force the link and overwrite all conflicting files:\n  brew link --overwrite coreutils\n\n\
+        To list all files that would be deleted:\n  brew link --overwrite --dry-run coreutils\n";
+
+    #[test]
+    fn test_match() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        let command = Command::new("brew link coreutils".to_string(), Shell::Bash);
+        assert!(BrewLink.is_match(&command, &result));
+        let command = Command::new("brew ln coreutils".to_string(), Shell::Bash);
+        assert!(BrewLink.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, String::new());
+        let command = Command::new("brew link coreutils".to_string(), Shell::Bash);
+        assert!(!BrewLink.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        let command = Command::new("brew ln coreutils".to_string(), Shell::Bash);
+        assert_eq!(
+            BrewLink.get_new_commands(&command, &result),
+            vec!["brew link --overwrite --dry-run coreutils"]
+        );
+        let command = Command::new("brew  ln 'coreutils'".to_string(), Shell::Bash);
+        assert_eq!(
+            BrewLink.get_new_commands(&command, &result),
+            vec!["brew  link --overwrite --dry-run 'coreutils'"]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Suggests `brew uninstall --force` when other installed versions prevent uninstalling
+pub struct BrewUninstall;
+
+impl Rule for BrewUninstall {
+    fn name(&self) -> &'static str {
+        "brew_uninstall"
+    }
+
+    fn description(&self) -> &'static str {
+        "Suggests `brew uninstall --force` when other installed versions prevent uninstalling"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        matches!(command.argument(0), Some("uninstall" | "rm" | "remove"))
+            && result.output().contains("brew uninstall --force")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let Some(subcommand) = command.argument(0) else {
+            return Vec::new();
+        };
+        let script = utils::replace_argument(command.trimmed(), subcommand, "uninstall");
+        vec![utils::insert_after_argument(
+            &script,
+            "uninstall",
+            "--force",
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Uninstalling /usr/local/Cellar/tbb/4.4-20160916... (118 files, 1.9M)\n\
+        tbb 4.4-20160526, 4.4-20160722 are still installed.\n\
+        Remove all versions with `brew uninstall --force tbb`.\n";
+
+    #[test]
+    fn test_match() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        for script in ["brew uninstall tbb", "brew rm tbb", "brew remove tbb"] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            assert!(BrewUninstall.is_match(&command, &result));
+        }
+
+        let command = Command::new("brew install tbb".to_string(), Shell::Bash);
+        assert!(!BrewUninstall.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        let command = Command::new("brew rm tbb".to_string(), Shell::Bash);
+        assert_eq!(
+            BrewUninstall.get_new_commands(&command, &result),
+            vec!["brew uninstall --force tbb"]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands shipped with every Homebrew installation, used when `brew commands` is unavailable
+#[allow(clippy::type_complexity)]
+const DEFAULT_BREW_COMMANDS: &[&str] = &[
+    "info",
+    "home",
+    "options",
+    "install",
+    "uninstall",
+    "search",
+    "list",
+    "update",
+    "upgrade",
+    "pin",
+    "unpin",
+    "doctor",
+    "create",
+    "edit",
+    "cleanup",
+    "link",
+    "unlink",
+    "tap",
+    "untap",
+    "outdated",
+    "services",
+    "bundle",
+];
+
+/// Fixes misspelled brew commands, e.g. `brew instal wget` -> `brew install wget`
+pub struct BrewUnknownCommand;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"Error: Unknown command: (\S+)").expect("valid regex"))
+}
+
+/// Gets the available brew commands, reusing what brew listed last time
+#[allow(clippy::type_complexity)]
+fn brew_commands() -> Vec<String> {
+    let mut commands: Vec<String> = DEFAULT_BREW_COMMANDS
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    if let Some(output) = utils::cached_output("brew", &["commands", "--quiet"]) {
+        for name in output
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            if !commands.iter().any(|c| c == name) {
+                commands.push(name.to_string());
+            }
+        }
+    }
+    commands
+}
+
+#[allow(clippy::type_complexity)]
+fn broken_command(result: &CommandResult) -> Option<String> {
+    unknown_command_regex()
+        .captures(&result.output())
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for BrewUnknownCommand {
+    fn name(&self) -> &'static str {
+        "brew_unknown_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled brew commands, e.g. `brew instal wget` -> `brew install wget`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        broken_command(result)
+            .is_some_and(|broken| utils::get_closest(&broken, &brew_commands()).is_some())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match broken_command(result) {
+            Some(broken) => utils::replace_command(command.trimmed(), &broken, &brew_commands()),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[allow(clippy::type_complexity)]
+    fn brew(script: &str, stderr: &str) -> (Command, CommandResult) {
+        (
+            Command::new(script.to_string(), Shell::Bash),
+            CommandResult::failure(1, stderr.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_match() {
+        let (command, result) = brew("brew instal wget", "Error: Unknown command: instal\n");
+        assert!(BrewUnknownCommand.is_match(&command, &result));
+
+        let (command, result) = brew("brew install wget", "Error: No such formula\n");
+        assert!(!BrewUnknownCommand.is_match(&command, &result));
+
+        let (command, result) = brew("brew zzzzzzzz", "Error: Unknown command: zzzzzzzz\n");
+        assert!(!BrewUnknownCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let (command, result) = brew("brew instal wget", "Error: Unknown command: instal\n");
+        let fixes = BrewUnknownCommand.get_new_commands(&command, &result);
+        assert_eq!(fixes[0], "brew install wget");
+
+        let (command, result) = brew("brew updte", "Error: Unknown command: updte\n");
+        let fixes = BrewUnknownCommand.get_new_commands(&command, &result);
+        assert_eq!(fixes[0], "brew update");
+    }
+}
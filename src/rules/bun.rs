@@ -0,0 +1,169 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Commands built into bun
+#[allow(clippy::type_complexity)]
+const BUN_COMMANDS: &[&str] = &[
+    "add", "audit", "build", "create", "exec", "info", "init", "install", "link", "outdated",
+    "patch", "pm", "publish", "remove", "run", "test", "unlink", "update", "upgrade", "why", "x",
+];
+
+/// Fixes misspelled bun commands and scripts, e.g. `bun isntall` -> `bun install`
+pub struct Bun;
+
+/// Runs package.json scripts with bun in bun projects, e.g. `npm run dev` -> `bun run dev`
+pub struct BunRunScript;
+
+#[allow(clippy::type_complexity)]
+fn missing_script(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"(?:Script|[Cc]ommand) not found "([^"]+)""#).expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Checks if the directory is managed by bun, from its lockfile
+fn is_bun_project(dir: &str) -> bool {
+    ["bun.lock", "bun.lockb"]
+        .iter()
+        .any(|name| Path::new(dir).join(name).is_file())
+}
+
+impl Rule for Bun {
+    fn name(&self) -> &'static str {
+        "bun"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled bun commands and scripts, e.g. `bun isntall` -> `bun install`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_script(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(broken) = missing_script(&result.output()) else {
+            return Vec::new();
+        };
+        let mut possibilities = utils::package_json_scripts(&command.cwd);
+        // `bun run <name>` only runs scripts and files, bare `bun <name>` also runs commands
+        if command.argument(0) != Some("run") {
+            possibilities.extend(BUN_COMMANDS.iter().map(|name| name.to_string()));
+        }
+        utils::replace_command(command.trimmed(), &broken, &possibilities)
+    }
+}
+
+impl Rule for BunRunScript {
+    fn name(&self) -> &'static str {
+        "bun_run_script"
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs package.json scripts with bun in bun projects, e.g. `npm run dev` -> `bun run dev`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        matches!(command.argument(0), Some("run" | "run-script"))
+            && output.contains("not found")
+            && is_bun_project(&command.cwd)
+            && command.argument(1).is_some_and(|script| {
+                utils::package_json_scripts(&command.cwd)
+                    .iter()
+                    .any(|s| s == script)
+            })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let arguments = command.arguments();
+        #[allow(clippy::type_complexity)]
+        let rest: Vec<&str> = arguments
+            .iter()
+            .skip(1)
+            .filter(|arg| **arg != "--")
+            .copied()
+            .collect();
+        vec![format!("bun run {}", rest.join(" "))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[allow(clippy::type_complexity)]
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"dev": "vite", "build": "vite build"}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("bun.lock"), "{}\n").unwrap();
+        dir
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn command(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_bun_match() {
+        let dir = project();
+        let result = CommandResult::failure(1, "error: Script not found \"biuld\"\n".to_string());
+        assert!(Bun.is_match(&command("bun biuld", &dir), &result));
+
+        let result = CommandResult::failure(1, "error: Cannot find module\n".to_string());
+        assert!(!Bun.is_match(&command("bun biuld", &dir), &result));
+    }
+
+    #[test]
+    fn test_bun_get_new_commands() {
+        let dir = project();
+        let result = CommandResult::failure(1, "error: Script not found \"biuld\"\n".to_string());
+        assert_eq!(
+            Bun.get_new_commands(&command("bun run biuld", &dir), &result),
+            vec!["bun run build"]
+        );
+
+        let result = CommandResult::failure(1, "error: Script not found \"isntall\"\n".to_string());
+        assert_eq!(
+            Bun.get_new_commands(&command("bun isntall", &dir), &result)[0],
+            "bun install"
+        );
+    }
+
+    #[test]
+    fn test_bun_run_script() {
+        let dir = project();
+        let result = CommandResult::failure(127, "bash: npm: command not found\n".to_string());
+        assert!(BunRunScript.is_match(&command("npm run dev", &dir), &result));
+        assert!(!BunRunScript.is_match(&command("npm run lint", &dir), &result));
+        assert_eq!(
+            BunRunScript.get_new_commands(&command("npm run dev -- --port 3000", &dir), &result),
+            vec!["bun run dev --port 3000"]
+        );
+
+        let other = tempfile::tempdir().unwrap();
+        std::fs::write(
+            other.path().join("package.json"),
+            r#"{"scripts": {"dev": "vite"}}"#,
+        )
+        .unwrap();
+        assert!(!BunRunScript.is_match(&command("npm run dev", &other), &result));
+    }
+}
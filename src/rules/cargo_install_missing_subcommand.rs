@@ -0,0 +1,152 @@
+use crate::config::Settings;
+use crate::rules::{DEFAULT_PRIORITY, Rule};
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Popular external cargo subcommands and the crates that provide them
+#[allow(clippy::type_complexity)]
+const KNOWN_SUBCOMMANDS: &[(&str, &str)] = &[
+    ("audit", "cargo-audit"),
+    ("bloat", "cargo-bloat"),
+    ("deny", "cargo-deny"),
+    ("expand", "cargo-expand"),
+    ("flamegraph", "flamegraph"),
+    ("generate", "cargo-generate"),
+    ("insta", "cargo-insta"),
+    ("llvm-cov", "cargo-llvm-cov"),
+    ("machete", "cargo-machete"),
+    ("make", "cargo-make"),
+    ("nextest", "cargo-nextest"),
+    ("outdated", "cargo-outdated"),
+    ("release", "cargo-release"),
+    ("set-version", "cargo-edit"),
+    ("tarpaulin", "cargo-tarpaulin"),
+    ("udeps", "cargo-udeps"),
+    ("upgrade", "cargo-edit"),
+    ("watch", "cargo-watch"),
+];
+
+/// Installs a missing external cargo subcommand before running it,
+/// e.g. `cargo watch` -> `cargo install cargo-watch && cargo watch`
+pub struct CargoInstallMissingSubcommand {
+    /// Subcommands mapped to the crate that provides them
+    #[allow(clippy::type_complexity)]
+    packages: HashMap<String, String>,
+}
+
+impl CargoInstallMissingSubcommand {
+    /// Creates the rule with the built-in table extended by `cargo_subcommands` from the settings
+    pub fn new(settings: &Settings) -> Self {
+        #[allow(clippy::type_complexity)]
+        let mut packages: HashMap<String, String> = KNOWN_SUBCOMMANDS
+            .iter()
+            .map(|(subcommand, package)| (subcommand.to_string(), package.to_string()))
+            .collect();
+        packages.extend(settings.cargo_subcommands.clone());
+        Self { packages }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn missing_package(&self, result: &CommandResult) -> Option<&str> {
+        static REGEX: OnceLock<Regex> = OnceLock::new();
+        let regex = REGEX.get_or_init(|| {
+            Regex::new(r"no such (?:sub)?command: [`']([^`']+)[`']").expect("valid regex")
+        });
+        let output = result.output();
+        let captures = regex.captures(&output)?;
+        self.packages.get(&captures[1]).map(String::as_str)
+    }
+}
+
+impl Default for CargoInstallMissingSubcommand {
+    fn default() -> Self {
+        Self::new(&Settings::default())
+    }
+}
+
+impl Rule for CargoInstallMissingSubcommand {
+    fn name(&self) -> &'static str {
+        "cargo_install_missing_subcommand"
+    }
+
+    fn description(&self) -> &'static str {
+        "Installs a missing external cargo subcommand before running it, e.g. `cargo watch` -> `cargo install cargo-watch && cargo watch`"
+    }
+
+    // Ranks above `cargo_no_command`, whose fuzzy match would pick an unrelated built-in command
+    fn priority(&self) -> u32 {
+        DEFAULT_PRIORITY + 100
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        self.missing_package(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        self.missing_package(result)
+            .map(|package| format!("cargo install {package} && {}", command.trimmed()))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    fn no_such_command(subcommand: &str) -> CommandResult {
+        CommandResult::failure(
+            101,
+            format!(
+                "error: no such command: `{subcommand}`\n\n\
+                 \tView all installed commands with `cargo --list`\n\
+                 \tFind a package to install `{subcommand}` with `cargo search cargo-{subcommand}`\n"
+            ),
+        )
+    }
+
+    #[test]
+    fn test_match() {
+        let rule = CargoInstallMissingSubcommand::default();
+        let command = Command::new("cargo watch -x test".to_string(), Shell::Bash);
+        assert!(rule.is_match(&command, &no_such_command("watch")));
+
+        let command = Command::new("cargo frobnicate".to_string(), Shell::Bash);
+        assert!(!rule.is_match(&command, &no_such_command("frobnicate")));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let rule = CargoInstallMissingSubcommand::default();
+        let command = Command::new("cargo watch -x test".to_string(), Shell::Bash);
+        assert_eq!(
+            rule.get_new_commands(&command, &no_such_command("watch")),
+            vec!["cargo install cargo-watch && cargo watch -x test"]
+        );
+    }
+
+    #[test]
+    fn test_settings_extend_table() {
+        let settings = Settings::from_toml(
+            "[cargo_subcommands]\nsweep = \"cargo-sweep\"\nwatch = \"cargo-watch-fork\"\n",
+        )
+        .unwrap();
+        let rule = CargoInstallMissingSubcommand::new(&settings);
+
+        let command = Command::new("cargo sweep -t 30".to_string(), Shell::Bash);
+        assert_eq!(
+            rule.get_new_commands(&command, &no_such_command("sweep")),
+            vec!["cargo install cargo-sweep && cargo sweep -t 30"]
+        );
+
+        let command = Command::new("cargo watch".to_string(), Shell::Bash);
+        assert_eq!(
+            rule.get_new_commands(&command, &no_such_command("watch")),
+            vec!["cargo install cargo-watch-fork && cargo watch"]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands shipped with cargo, used when `cargo --list` is unavailable
+#[allow(clippy::type_complexity)]
+const CARGO_COMMANDS: &[&str] = &[
+    "add",
+    "bench",
+    "build",
+    "check",
+    "clean",
+    "clippy",
+    "doc",
+    "fetch",
+    "fix",
+    "fmt",
+    "generate-lockfile",
+    "init",
+    "install",
+    "locate-project",
+    "login",
+    "logout",
+    "metadata",
+    "new",
+    "owner",
+    "package",
+    "pkgid",
+    "publish",
+    "remove",
+    "report",
+    "run",
+    "rustc",
+    "rustdoc",
+    "search",
+    "test",
+    "tree",
+    "uninstall",
+    "update",
+    "vendor",
+    "verify-project",
+    "version",
+    "yank",
+];
+
+/// Fixes misspelled cargo commands, e.g. `cargo biuld` -> `cargo build`
+pub struct CargoNoCommand;
+
+#[allow(clippy::type_complexity)]
+fn broken_command(result: &CommandResult) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"no such (?:sub)?command: [`']([^`']+)[`']").expect("valid regex")
+    });
+    regex
+        .captures(&result.output())
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the command cargo itself suggests, in either the old or the new wording
+#[allow(clippy::type_complexity)]
+fn suggested_command(result: &CommandResult) -> Option<String> {
+    utils::extract_suggestions(&result.output())
+        .into_iter()
+        .next()
+}
+
+/// Parses the command names from `cargo --list` output
+#[allow(clippy::type_complexity)]
+fn parse_cargo_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Gets the installed cargo commands, reusing what cargo listed last time
+#[allow(clippy::type_complexity)]
+fn cargo_commands() -> Vec<String> {
+    let mut commands: Vec<String> = CARGO_COMMANDS.iter().map(|c| c.to_string()).collect();
+    if let Some(output) = utils::cached_output("cargo", &["--list"]) {
+        for name in parse_cargo_list(&output) {
+            if !commands.contains(&name) {
+                commands.push(name);
+            }
+        }
+    }
+    commands
+}
+
+impl Rule for CargoNoCommand {
+    fn name(&self) -> &'static str {
+        "cargo_no_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled cargo commands, e.g. `cargo biuld` -> `cargo build`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        broken_command(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(broken) = broken_command(result) else {
+            return Vec::new();
+        };
+        let script = command.trimmed();
+        match suggested_command(result) {
+            Some(fixed) => vec![command.replace_argument(&broken, &fixed)],
+            None => utils::replace_command(script, &broken, &cargo_commands()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("cargo biuld --release".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            101,
+            "error: no such command: `biuld`\n\n\tDid you mean `build`?\n".to_string(),
+        );
+        assert!(CargoNoCommand.is_match(&command, &result));
+
+        let result = CommandResult::failure(101, "error: could not compile `app`\n".to_string());
+        assert!(!CargoNoCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands_from_hint() {
+        let command = Command::new("cargo biuld --release".to_string(), Shell::Bash);
+        for output in [
+            "error: no such command: `biuld`\n\n\tDid you mean `build`?\n",
+            "error: no such command: `biuld`\n\nhelp: a command with a similar name exists: `build`\n",
+            "error: no such subcommand: `biuld`\n\n\tDid you mean `build`?\n",
+        ] {
+            let result = CommandResult::failure(101, output.to_string());
+            assert_eq!(
+                CargoNoCommand.get_new_commands(&command, &result),
+                vec!["cargo build --release"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_new_commands_without_hint() {
+        let command = Command::new("cargo tsst".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            101,
+            "error: no such command: `tsst`\n\n\tView all installed commands with `cargo --list`\n"
+                .to_string(),
+        );
+        assert_eq!(
+            CargoNoCommand.get_new_commands(&command, &result)[0],
+            "cargo test"
+        );
+    }
+
+    #[test]
+    fn test_parse_cargo_list() {
+        let output = "Installed Commands:\n    add                  Add dependencies\n    \
+            b                    alias: build\n    watch\n";
+        assert_eq!(parse_cargo_list(output), vec!["add", "b", "watch"]);
+    }
+}
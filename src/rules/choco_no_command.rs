@@ -0,0 +1,101 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into chocolatey
+#[allow(clippy::type_complexity)]
+const CHOCO_COMMANDS: &[&str] = &[
+    "apikey",
+    "cache",
+    "config",
+    "export",
+    "feature",
+    "find",
+    "help",
+    "info",
+    "install",
+    "list",
+    "new",
+    "outdated",
+    "pack",
+    "pin",
+    "push",
+    "rule",
+    "search",
+    "source",
+    "support",
+    "template",
+    "uninstall",
+    "unpackself",
+    "upgrade",
+];
+
+/// Fixes misspelled chocolatey commands, e.g. `choco isntall git` -> `choco install git`
+pub struct ChocoNoCommand;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(result: &CommandResult) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"Could not find a command registered that meets '([^']+)'")
+            .expect("valid regex")
+    });
+    regex
+        .captures(&result.output())
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for ChocoNoCommand {
+    fn name(&self) -> &'static str {
+        "choco_no_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled chocolatey commands, e.g. `choco isntall git` -> `choco install git`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        unknown_command(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match unknown_command(result) {
+            Some(broken) => utils::replace_command(command.trimmed(), &broken, CHOCO_COMMANDS),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Chocolatey v2.2.2\n\
+        Could not find a command registered that meets 'isntall'.\n \
+        Try choco -? for command reference/help.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("choco isntall git".to_string(), Shell::PowerShell);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(ChocoNoCommand.is_match(&command, &result));
+
+        let command = Command::new("choco install git".to_string(), Shell::PowerShell);
+        let result = CommandResult::failure(1, "git not installed.\n".to_string());
+        assert!(!ChocoNoCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("choco isntall git -y".to_string(), Shell::PowerShell);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            ChocoNoCommand.get_new_commands(&command, &result)[0],
+            "choco install git -y"
+        );
+    }
+}
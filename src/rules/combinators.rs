@@ -0,0 +1,369 @@
+//! Wrappers adding common behavior to rules, like the decorators of the original thefuck.
+//!
+//! [`for_app`] only lets a rule match commands of some programs, [`sudo_support`] lets it
+//! correct `sudo` commands as if they were run without it, and [`git_support`] expands git
+//! aliases before the rule sees the command. They nest, outermost first:
+//! `sudo_support(for_app(&["ln"], LnSOrder))`.
+
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult, CorrectedCommand};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Programs the git rules match, `hub` wrapping git
+#[allow(clippy::type_complexity)]
+pub(crate) const GIT_PROGRAMS: &[&str] = &["git", "hub"];
+
+/// Checks if a command runs one of the programs
+#[allow(clippy::type_complexity)]
+pub fn is_app(command: &Command, programs: &[&str]) -> bool {
+    programs.iter().any(|program| command.starts_with(program))
+}
+
+/// A rule that only matches commands of some programs
+pub struct ForApp<R> {
+    #[allow(clippy::type_complexity)]
+    programs: &'static [&'static str],
+    rule: R,
+}
+
+/// Only lets a rule match commands of some programs, e.g. `for_app(&["go"], GoRun)`
+#[allow(clippy::type_complexity)]
+pub fn for_app<R: Rule>(programs: &'static [&'static str], rule: R) -> ForApp<R> {
+    ForApp { programs, rule }
+}
+
+impl<R: Rule> Rule for ForApp<R> {
+    fn name(&self) -> &'static str {
+        self.rule.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.rule.description()
+    }
+
+    fn priority(&self) -> u32 {
+        self.rule.priority()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        self.programs
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        self.rule.enabled_by_default()
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        is_app(command, self.programs) && self.rule.is_match(command, result)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        self.rule.get_new_commands(command, result)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn matched_pattern(&self, command: &Command, result: &CommandResult) -> Option<String> {
+        self.rule.matched_pattern(command, result)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_corrections(&self, command: &Command, result: &CommandResult) -> Vec<CorrectedCommand> {
+        self.rule.get_corrections(command, result)
+    }
+}
+
+/// A rule that corrects `sudo` commands as if they were run without it
+pub struct SudoSupport<R> {
+    rule: R,
+}
+
+/// Lets a rule correct `sudo` commands, e.g. `sudo ln dir link` -> `sudo ln -s dir link`
+#[allow(clippy::type_complexity)]
+pub fn sudo_support<R: Rule>(rule: R) -> SudoSupport<R> {
+    SudoSupport { rule }
+}
+
+/// Gets the command without its leading `sudo`, if it has one
+#[allow(clippy::type_complexity)]
+fn strip_sudo(command: &Command) -> Option<Command> {
+    let rest = command.trimmed().strip_prefix("sudo")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(command.with_text(rest.trim_start().to_string()))
+}
+
+/// Puts `sudo` back in front of a correction, unless it's already there
+fn add_sudo(text: &str) -> String {
+    if text.starts_with("sudo ") {
+        text.to_string()
+    } else {
+        format!("sudo {text}")
+    }
+}
+
+impl<R: Rule> Rule for SudoSupport<R> {
+    fn name(&self) -> &'static str {
+        self.rule.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.rule.description()
+    }
+
+    fn priority(&self) -> u32 {
+        self.rule.priority()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        self.rule.programs()
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        self.rule.enabled_by_default()
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        match strip_sudo(command) {
+            Some(command) => self.rule.is_match(&command, result),
+            None => self.rule.is_match(command, result),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match strip_sudo(command) {
+            Some(command) => self
+                .rule
+                .get_new_commands(&command, result)
+                .iter()
+                .map(|text| add_sudo(text))
+                .collect(),
+            None => self.rule.get_new_commands(command, result),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn matched_pattern(&self, command: &Command, result: &CommandResult) -> Option<String> {
+        match strip_sudo(command) {
+            Some(command) => self.rule.matched_pattern(&command, result),
+            None => self.rule.matched_pattern(command, result),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_corrections(&self, command: &Command, result: &CommandResult) -> Vec<CorrectedCommand> {
+        let Some(stripped) = strip_sudo(command) else {
+            return self.rule.get_corrections(command, result);
+        };
+        self.rule
+            .get_corrections(&stripped, result)
+            .into_iter()
+            .map(|mut correction| {
+                correction.text = add_sudo(&correction.text);
+                correction.original = command.clone();
+                correction
+            })
+            .collect()
+    }
+}
+
+/// A git rule that sees git aliases expanded
+pub struct GitSupport<R> {
+    rule: R,
+}
+
+/// Only lets a rule match git commands, expanding the alias git reported running first
+///
+/// Git reports alias expansions in its trace output (`GIT_TRACE=1`), e.g.
+/// `trace: alias expansion: ci => commit -v`, so `git ci --amnd` reaches the rule as
+/// `git commit -v --amnd`.
+#[allow(clippy::type_complexity)]
+pub fn git_support<R: Rule>(rule: R) -> GitSupport<R> {
+    GitSupport { rule }
+}
+
+/// Gets the command with the alias git expanded replaced by its expansion
+///
+/// Only the subcommand, the word right after the program, is an alias git expands.
+#[allow(clippy::type_complexity)]
+fn expand_git_alias(command: &Command, result: &CommandResult) -> Option<Command> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"trace: alias expansion: (\S+) => ([^\n]+)").expect("valid regex")
+    });
+    let output = result.output();
+    let captures = regex.captures(&output)?;
+    let script = command.trimmed();
+    let words = utils::script_words(script);
+    let subcommand = words.get(1).filter(|word| word.value == captures[1])?;
+    let expansion = utils::join_script(&utils::split_script(captures[2].trim())?);
+    Some(command.with_text(format!(
+        "{}{expansion}{}",
+        &script[..subcommand.start],
+        &script[subcommand.end..]
+    )))
+}
+
+impl<R: Rule> Rule for GitSupport<R> {
+    fn name(&self) -> &'static str {
+        self.rule.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.rule.description()
+    }
+
+    fn priority(&self) -> u32 {
+        self.rule.priority()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        GIT_PROGRAMS
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        self.rule.enabled_by_default()
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        if !is_app(command, GIT_PROGRAMS) {
+            return false;
+        }
+        match expand_git_alias(command, result) {
+            Some(command) => self.rule.is_match(&command, result),
+            None => self.rule.is_match(command, result),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match expand_git_alias(command, result) {
+            Some(command) => self.rule.get_new_commands(&command, result),
+            None => self.rule.get_new_commands(command, result),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn matched_pattern(&self, command: &Command, result: &CommandResult) -> Option<String> {
+        match expand_git_alias(command, result) {
+            Some(command) => self.rule.matched_pattern(&command, result),
+            None => self.rule.matched_pattern(command, result),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_corrections(&self, command: &Command, result: &CommandResult) -> Vec<CorrectedCommand> {
+        let Some(expanded) = expand_git_alias(command, result) else {
+            return self.rule.get_corrections(command, result);
+        };
+        self.rule
+            .get_corrections(&expanded, result)
+            .into_iter()
+            .map(|mut correction| {
+                correction.original = command.clone();
+                correction
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    /// Adds `--amend` to commands whose output mentions it
+    struct Amend;
+
+    impl Rule for Amend {
+        fn name(&self) -> &'static str {
+            "amend"
+        }
+
+        fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+            command.argument(0) == Some("commit") && result.output().contains("--amend")
+        }
+
+        #[allow(clippy::type_complexity)]
+        fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+            vec![format!("{} --amend", command.trimmed())]
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn failed(script: &str, output: &str) -> (Command, CommandResult) {
+        (
+            Command::new(script.to_string(), Shell::Bash),
+            CommandResult::failure(1, output.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_for_app() {
+        let rule = for_app(&["git", "yadm"], Amend);
+        assert_eq!(rule.name(), "amend");
+        assert_eq!(rule.programs(), &["git", "yadm"]);
+
+        let (command, result) = failed("yadm commit", "use --amend");
+        assert!(rule.is_match(&command, &result));
+        let (command, result) = failed("hg commit", "use --amend");
+        assert!(Amend.is_match(&command, &result));
+        assert!(!rule.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_sudo_support() {
+        let rule = sudo_support(for_app(&["git"], Amend));
+        let (command, result) = failed("sudo git commit", "use --amend");
+        assert!(!Amend.is_match(&command, &result));
+        assert!(rule.is_match(&command, &result));
+        assert_eq!(
+            rule.get_new_commands(&command, &result),
+            vec!["sudo git commit --amend"]
+        );
+        let corrections = rule.get_corrections(&command, &result);
+        assert_eq!(corrections[0].text, "sudo git commit --amend");
+        assert_eq!(corrections[0].original.text, "sudo git commit");
+        assert_eq!(corrections[0].rule, "amend");
+
+        let (command, result) = failed("git commit", "use --amend");
+        assert_eq!(
+            rule.get_new_commands(&command, &result),
+            vec!["git commit --amend"]
+        );
+        let (command, result) = failed("sudoedit commit", "use --amend");
+        assert!(!rule.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_git_support() {
+        let rule = git_support(Amend);
+        assert_eq!(rule.programs(), GIT_PROGRAMS);
+        let output = "trace: alias expansion: ci => 'commit' '-v'\nuse --amend\n";
+        let (command, result) = failed("git ci -m 'ci fix'", output);
+        assert!(!Amend.is_match(&command, &result));
+        assert!(rule.is_match(&command, &result));
+        let corrections = rule.get_corrections(&command, &result);
+        assert_eq!(corrections[0].text, "git commit -v -m 'ci fix' --amend");
+        assert_eq!(corrections[0].original.text, "git ci -m 'ci fix'");
+
+        let output = "trace: alias expansion: co => checkout\nuse --amend\n";
+        let (command, result) = failed("git commit -m co", output);
+        assert_eq!(
+            rule.get_new_commands(&command, &result),
+            vec!["git commit -m co --amend"]
+        );
+
+        let (command, result) = failed("git commit", "use --amend");
+        assert!(rule.is_match(&command, &result));
+        let (command, result) = failed("svn commit", "use --amend");
+        assert!(!rule.is_match(&command, &result));
+    }
+}
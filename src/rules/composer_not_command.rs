@@ -0,0 +1,113 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Applies composer's suggestions for an undefined command,
+/// e.g. `composer udpate` -> `composer update`
+pub struct ComposerNotCommand;
+
+#[allow(clippy::type_complexity)]
+fn broken_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"Command ["']([^"']+)["'] is not defined"#).expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for ComposerNotCommand {
+    fn name(&self) -> &'static str {
+        "composer_not_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Applies composer's suggestions for an undefined command, e.g. `composer udpate` -> `composer update`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        (broken_command(&output).is_some() && !utils::extract_suggestions(&output).is_empty())
+            || (command.contains_argument("install")
+                && output.to_lowercase().contains("composer require"))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        match broken_command(&output) {
+            Some(broken) => utils::extract_suggestions(&output)
+                .iter()
+                .map(|fixed| command.replace_argument(&broken, fixed))
+                .collect(),
+            None => vec![command.replace_argument("install", "require")],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const SINGLE: &str = "\n  [Symfony\\Component\\Console\\Exception\\CommandNotFoundException]\n  \
+        Command \"udpate\" is not defined.\n  Did you mean this?\n      update\n\n";
+
+    const MULTIPLE: &str = "\n  [InvalidArgumentException]\n  Command \"pdate\" is not defined.\n  \
+        Did you mean one of these?\n      selfupdate\n      self-update\n      update\n\n";
+
+    const INLINE: &str = "Command 'instal' is not defined. Did you mean install?\n";
+
+    const REQUIRE: &str = "Invalid argument vlucas/phpdotenv. Use \"composer require \
+        vlucas/phpdotenv\" instead to add packages to your composer.json.\n";
+
+    #[test]
+    fn test_match() {
+        for (script, output) in [
+            ("composer udpate", SINGLE),
+            ("composer pdate", MULTIPLE),
+            ("composer instal", INLINE),
+            ("composer install vlucas/phpdotenv", REQUIRE),
+        ] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            let result = CommandResult::failure(1, output.to_string());
+            assert!(ComposerNotCommand.is_match(&command, &result));
+        }
+
+        let command = Command::new("composer update".to_string(), Shell::Bash);
+        let result = CommandResult::success("Nothing to install or update\n".to_string());
+        assert!(!ComposerNotCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        for (script, output, expected) in [
+            ("composer udpate", SINGLE, vec!["composer update"]),
+            (
+                "composer pdate",
+                MULTIPLE,
+                vec![
+                    "composer selfupdate",
+                    "composer self-update",
+                    "composer update",
+                ],
+            ),
+            ("composer instal", INLINE, vec!["composer install"]),
+            (
+                "composer install vlucas/phpdotenv",
+                REQUIRE,
+                vec!["composer require vlucas/phpdotenv"],
+            ),
+        ] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            let result = CommandResult::failure(1, output.to_string());
+            assert_eq!(
+                ComposerNotCommand.get_new_commands(&command, &result),
+                expected
+            );
+        }
+    }
+}
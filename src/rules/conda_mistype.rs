@@ -0,0 +1,72 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Applies conda's suggestion for a mistyped command, e.g. `conda lst` -> `conda list`
+pub struct CondaMistype;
+
+/// Gets the mistyped and the suggested command from conda's error output
+#[allow(clippy::type_complexity)]
+fn broken_and_fixed(result: &CommandResult) -> Option<(String, String)> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"No command 'conda ([^']*)'[\s\S]*Did you mean 'conda ([^']*)'")
+            .expect("valid regex")
+    });
+    regex
+        .captures(&result.output())
+        .map(|captures| (captures[1].to_string(), captures[2].to_string()))
+}
+
+impl Rule for CondaMistype {
+    fn name(&self) -> &'static str {
+        "conda_mistype"
+    }
+
+    fn description(&self) -> &'static str {
+        "Applies conda's suggestion for a mistyped command, e.g. `conda lst` -> `conda list`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        broken_and_fixed(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        broken_and_fixed(result)
+            .map(|(broken, fixed)| command.replace_argument(&broken, &fixed))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "\n\nCommandNotFoundError: No command 'conda lst'.\n\
+        Did you mean 'conda list'?\n\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("conda lst".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(CondaMistype.is_match(&command, &result));
+
+        let command = Command::new("conda list".to_string(), Shell::Bash);
+        let result = CommandResult::success("# packages in environment\n".to_string());
+        assert!(!CondaMistype.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("conda lst --explicit".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            CondaMistype.get_new_commands(&command, &result),
+            vec!["conda list --explicit"]
+        );
+    }
+}
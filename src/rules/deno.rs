@@ -0,0 +1,190 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Commands built into deno
+#[allow(clippy::type_complexity)]
+const DENO_COMMANDS: &[&str] = &[
+    "add",
+    "bench",
+    "cache",
+    "check",
+    "clean",
+    "compile",
+    "completions",
+    "coverage",
+    "doc",
+    "eval",
+    "fmt",
+    "info",
+    "init",
+    "install",
+    "jupyter",
+    "lint",
+    "lsp",
+    "outdated",
+    "publish",
+    "remove",
+    "repl",
+    "run",
+    "serve",
+    "task",
+    "test",
+    "types",
+    "uninstall",
+    "upgrade",
+];
+
+/// Fixes misspelled deno commands and runs deno.json tasks,
+/// e.g. `deno rn main.ts` -> `deno run main.ts`, `deno dev` -> `deno task dev`
+pub struct Deno;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX
+        .get_or_init(|| Regex::new(r"unrecognized subcommand '([^']+)'").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+#[allow(clippy::type_complexity)]
+fn missing_task(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"Task not found: (\S+)").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].trim_matches('"').to_string())
+}
+
+/// Gets the names of the tasks in the deno.json or deno.jsonc of a directory
+#[allow(clippy::type_complexity)]
+fn deno_tasks(dir: &str) -> Vec<String> {
+    for name in ["deno.json", "deno.jsonc"] {
+        let Ok(content) = std::fs::read_to_string(Path::new(dir).join(name)) else {
+            continue;
+        };
+        // Only whole-line comments are dropped, which covers the usual deno.jsonc
+        let content: String = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("//"))
+            .collect::<Vec<&str>>()
+            .join("\n");
+        let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) else {
+            continue;
+        };
+        return config
+            .get("tasks")
+            .and_then(|tasks| tasks.as_object())
+            .map(|tasks| tasks.keys().cloned().collect())
+            .unwrap_or_default();
+    }
+    Vec::new()
+}
+
+impl Rule for Deno {
+    fn name(&self) -> &'static str {
+        "deno"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled deno commands and runs deno.json tasks, e.g. `deno rn main.ts` -> `deno run main.ts`, `deno dev` -> `deno task dev`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        unknown_command(&output).is_some()
+            || missing_task(&output).is_some()
+            || command.argument(0).is_some_and(|name| {
+                !DENO_COMMANDS.contains(&name) && deno_tasks(&command.cwd).iter().any(|t| t == name)
+            })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let script = command.trimmed();
+        let tasks = deno_tasks(&command.cwd);
+
+        if let Some(task) = missing_task(&output) {
+            return utils::replace_command(script, &task, &tasks);
+        }
+        let Some(name) = command.argument(0) else {
+            return Vec::new();
+        };
+        let mut fixes: Vec<String> = Vec::new();
+        if tasks.iter().any(|task| task == name) {
+            fixes.push(command.insert_before_argument(name, "task"));
+        }
+        if let Some(broken) = unknown_command(&output) {
+            fixes.extend(utils::replace_command(script, &broken, DENO_COMMANDS));
+        }
+        fixes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const UNKNOWN_OUTPUT: &str = "error: unrecognized subcommand 'rn'\n\n  \
+        tip: a similar subcommand exists: 'run'\n\nUsage: deno [OPTIONS] [COMMAND]\n";
+
+    #[allow(clippy::type_complexity)]
+    fn deno(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("deno.jsonc"),
+            "{\n  // scripts\n  \"tasks\": {\"dev\": \"deno run -A main.ts\", \"build\": \"deno compile main.ts\"}\n}\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = project();
+        let result = CommandResult::failure(1, UNKNOWN_OUTPUT.to_string());
+        assert!(Deno.is_match(&deno("deno rn main.ts", &dir), &result));
+
+        let result = CommandResult::failure(
+            1,
+            "error: Module not found \"file:///app/dev\".\n".to_string(),
+        );
+        assert!(Deno.is_match(&deno("deno dev", &dir), &result));
+        assert!(!Deno.is_match(&deno("deno run main.ts", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = project();
+        let result = CommandResult::failure(1, UNKNOWN_OUTPUT.to_string());
+        assert_eq!(
+            Deno.get_new_commands(&deno("deno rn main.ts", &dir), &result)[0],
+            "deno run main.ts"
+        );
+
+        let result = CommandResult::failure(1, "error: Module not found\n".to_string());
+        assert_eq!(
+            Deno.get_new_commands(&deno("deno dev --watch", &dir), &result),
+            vec!["deno task dev --watch"]
+        );
+
+        let result = CommandResult::failure(1, "Task not found: buidl\n".to_string());
+        assert_eq!(
+            Deno.get_new_commands(&deno("deno task buidl", &dir), &result),
+            vec!["deno task build"]
+        );
+    }
+}
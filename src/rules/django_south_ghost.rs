@@ -0,0 +1,69 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+
+/// Deletes South's ghost migrations, e.g. `./manage.py migrate` -> `./manage.py migrate --delete-ghost-migrations`
+pub struct DjangoSouthGhost;
+
+/// Checks if the command runs Django's `migrate`
+pub(crate) fn is_migrate(command: &Command) -> bool {
+    let script = command.trimmed();
+    script.contains("manage.py") && command.arguments().contains(&"migrate")
+}
+
+impl Rule for DjangoSouthGhost {
+    fn name(&self) -> &'static str {
+        "django_south_ghost"
+    }
+
+    fn description(&self) -> &'static str {
+        "Deletes South's ghost migrations, e.g. `./manage.py migrate` -> `./manage.py migrate --delete-ghost-migrations`"
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        &["manage.py"]
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        is_migrate(command)
+            && result
+                .output()
+                .contains("or pass --delete-ghost-migrations")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        vec![format!("{} --delete-ghost-migrations", command.trimmed())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = " ! These migrations are in the database but not on disk:\n    \
+        <app: 0003_auto_add_field>\n ! I'm not trusting myself; either fix this yourself by \
+        fiddling\n ! with the south_migrationhistory table, or pass --delete-ghost-migrations\n \
+        ! to South to have it delete ALL of these records (this may not be good).\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("./manage.py migrate app".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(DjangoSouthGhost.is_match(&command, &result));
+
+        let command = Command::new("./manage.py runserver".to_string(), Shell::Bash);
+        assert!(!DjangoSouthGhost.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("python manage.py migrate app".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            DjangoSouthGhost.get_new_commands(&command, &result),
+            vec!["python manage.py migrate app --delete-ghost-migrations"]
+        );
+    }
+}
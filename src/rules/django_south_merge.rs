@@ -0,0 +1,67 @@
+use crate::rules::Rule;
+use crate::rules::django_south_ghost::is_migrate;
+use crate::types::{Command, CommandResult};
+
+/// Merges South migrations applied out of order, e.g. `./manage.py migrate` -> `./manage.py migrate --merge`
+pub struct DjangoSouthMerge;
+
+impl Rule for DjangoSouthMerge {
+    fn name(&self) -> &'static str {
+        "django_south_merge"
+    }
+
+    fn description(&self) -> &'static str {
+        "Merges South migrations applied out of order, e.g. `./manage.py migrate` -> `./manage.py migrate --merge`"
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        &["manage.py"]
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        is_migrate(command)
+            && result
+                .output()
+                .contains("--merge: will just attempt the migration")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        vec![format!("{} --merge", command.trimmed())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Running migrations for app:\n\
+        Migration 'app:0003_auto' is marked for no-dry-run.\n \
+        ! Migration app:0003_auto should not have been applied before app:0002_auto but was.\n\
+        Traceback (most recent call last):\nsouth.exceptions.InconsistentMigrationHistory: \
+        Inconsistent migration history\nThe following options are available:\n    \
+        --merge: will just attempt the migration ignoring any potential dependency conflicts.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("./manage.py migrate".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(DjangoSouthMerge.is_match(&command, &result));
+
+        let command = Command::new("./manage.py migrate".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "No migrations to apply.\n".to_string());
+        assert!(!DjangoSouthMerge.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("./manage.py migrate app".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            DjangoSouthMerge.get_new_commands(&command, &result),
+            vec!["./manage.py migrate app --merge"]
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into dnf, used when `dnf --help` is unavailable
+#[allow(clippy::type_complexity)]
+const DNF_COMMANDS: &[&str] = &[
+    "alias",
+    "autoremove",
+    "check",
+    "check-update",
+    "clean",
+    "deplist",
+    "distro-sync",
+    "downgrade",
+    "group",
+    "help",
+    "history",
+    "info",
+    "install",
+    "list",
+    "makecache",
+    "mark",
+    "module",
+    "provides",
+    "reinstall",
+    "remove",
+    "repolist",
+    "repoquery",
+    "repository-packages",
+    "search",
+    "shell",
+    "swap",
+    "updateinfo",
+    "upgrade",
+    "upgrade-minimal",
+];
+
+/// Commands built into yum, used when `yum --help` is unavailable
+#[allow(clippy::type_complexity)]
+const YUM_COMMANDS: &[&str] = &[
+    "check",
+    "check-update",
+    "clean",
+    "deplist",
+    "distribution-synchronization",
+    "downgrade",
+    "erase",
+    "fs",
+    "fssnapshot",
+    "groups",
+    "help",
+    "history",
+    "info",
+    "install",
+    "list",
+    "load-transaction",
+    "makecache",
+    "provides",
+    "reinstall",
+    "repolist",
+    "search",
+    "shell",
+    "update",
+    "update-minimal",
+    "updateinfo",
+    "upgrade",
+    "version",
+];
+
+/// Fixes misspelled dnf commands, e.g. `dnf isntall vim` -> `dnf install vim`
+pub struct DnfNoSuchCommand;
+
+/// Fixes misspelled yum commands, e.g. `yum isntall vim` -> `yum install vim`
+pub struct YumNoSuchCommand;
+
+#[allow(clippy::type_complexity)]
+fn no_such_command_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"No such command: ([^\s.]+)").expect("valid regex"))
+}
+
+/// Parses the command names listed at the start of the lines of `--help` output
+#[allow(clippy::type_complexity)]
+fn parse_operations(help: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"(?m)^([a-z-]+) +").expect("valid regex"));
+    regex
+        .captures_iter(help)
+        .map(|captures| captures[1].to_string())
+        .collect()
+}
+
+/// Gets the known commands plus the ones listed by `<program> --help`
+#[allow(clippy::type_complexity)]
+fn load_operations(program: &str, defaults: &[&str]) -> Vec<String> {
+    let mut operations: Vec<String> = defaults.iter().map(|c| c.to_string()).collect();
+    if let Some(help) = utils::cached_output(program, &["--help"]) {
+        for operation in parse_operations(&help) {
+            if !operations.contains(&operation) {
+                operations.push(operation);
+            }
+        }
+    }
+    operations
+}
+
+#[allow(clippy::type_complexity)]
+fn operations(program: &str) -> Vec<String> {
+    match program {
+        "dnf" => load_operations("dnf", DNF_COMMANDS),
+        _ => load_operations("yum", YUM_COMMANDS),
+    }
+}
+
+/// Checks that the package manager reported an unknown command
+fn is_no_such_command(result: &CommandResult) -> bool {
+    result.output().to_lowercase().contains("no such command")
+}
+
+#[allow(clippy::type_complexity)]
+fn fix_command(program: &str, command: &Command, result: &CommandResult) -> Vec<String> {
+    match no_such_command_regex().captures(&result.output()) {
+        Some(captures) => {
+            utils::replace_command(command.trimmed(), &captures[1], &operations(program))
+        }
+        None => Vec::new(),
+    }
+}
+
+impl Rule for DnfNoSuchCommand {
+    fn name(&self) -> &'static str {
+        "dnf_no_such_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled dnf commands, e.g. `dnf isntall vim` -> `dnf install vim`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        is_no_such_command(result)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        fix_command("dnf", command, result)
+    }
+}
+
+impl Rule for YumNoSuchCommand {
+    fn name(&self) -> &'static str {
+        "yum_no_such_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled yum commands, e.g. `yum isntall vim` -> `yum install vim`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        is_no_such_command(result)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        fix_command("yum", command, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Rule, for_app, sudo_support};
+    use crate::types::Shell;
+
+    #[allow(clippy::type_complexity)]
+    fn dnf() -> impl Rule {
+        sudo_support(for_app(&["dnf"], DnfNoSuchCommand))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn yum() -> impl Rule {
+        sudo_support(for_app(&["yum"], YumNoSuchCommand))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn failed(script: &str, typo: &str) -> (Command, CommandResult) {
+        let output = format!("No such command: {typo}. Please use /usr/bin/dnf --help\n");
+        (
+            Command::new(script.to_string(), Shell::Bash),
+            CommandResult::failure(1, output),
+        )
+    }
+
+    #[test]
+    fn test_match() {
+        let (command, result) = failed("dnf isntall vim", "isntall");
+        assert!(dnf().is_match(&command, &result));
+        assert!(!yum().is_match(&command, &result));
+
+        let (command, result) = failed("sudo yum isntall vim", "isntall");
+        assert!(yum().is_match(&command, &result));
+
+        let command = Command::new("dnf install vim".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "Error: Unable to find a match\n".to_string());
+        assert!(!dnf().is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let (command, result) = failed("sudo dnf isntall vim", "isntall");
+        assert_eq!(
+            dnf().get_new_commands(&command, &result)[0],
+            "sudo dnf install vim"
+        );
+
+        let (command, result) = failed("yum upgade", "upgade");
+        assert_eq!(yum().get_new_commands(&command, &result)[0], "yum upgrade");
+    }
+
+    #[test]
+    fn test_parse_operations() {
+        let help = "usage: dnf [options] COMMAND\n\nList of Main Commands:\n\n\
+            autoremove                remove all unneeded packages\n\
+            check                     check for problems in the packagedb\n\n\
+            Optional arguments:\n  -c [config file], --config [config file]\n";
+        assert_eq!(parse_operations(help), vec!["autoremove", "check"]);
+    }
+}
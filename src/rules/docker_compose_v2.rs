@@ -0,0 +1,156 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Switches between the standalone and plugin compose, whichever is installed,
+/// e.g. `docker-compose up` -> `docker compose up`
+pub struct DockerComposeV2;
+
+/// Checks if docker's compose plugin is installed, reusing what docker answered last time
+fn has_compose_plugin() -> bool {
+    utils::cached_output("docker", &["compose", "version"]).is_some()
+}
+
+/// Checks if the standalone `docker-compose` is installed, or stood in for by an alias or function
+fn has_standalone_compose(command: &Command) -> bool {
+    utils::is_available("docker-compose", command)
+}
+
+/// Rewrites `docker-compose ...` as `docker compose ...`
+#[allow(clippy::type_complexity)]
+fn to_plugin(script: &str) -> Option<String> {
+    let rest = script.strip_prefix("docker-compose")?;
+    (rest.is_empty() || rest.starts_with(char::is_whitespace))
+        .then(|| format!("docker compose{rest}"))
+}
+
+/// Rewrites `docker compose ...` as `docker-compose ...`
+#[allow(clippy::type_complexity)]
+fn to_standalone(script: &str) -> Option<String> {
+    let mut words = script.splitn(3, char::is_whitespace);
+    if words.next() != Some("docker") || words.next() != Some("compose") {
+        return None;
+    }
+    Some(match words.next() {
+        Some(rest) => format!("docker-compose {rest}"),
+        None => "docker-compose".to_string(),
+    })
+}
+
+/// Checks if the shell couldn't find `docker-compose`
+fn standalone_missing(output: &str) -> bool {
+    output.contains("docker-compose: command not found")
+        || output.contains("docker-compose: not found")
+        || output.contains("'docker-compose' not found")
+        || output.contains("'docker-compose' is not recognized")
+}
+
+/// Checks if docker doesn't know the `compose` command
+fn plugin_missing(output: &str) -> bool {
+    output.contains("'compose' is not a docker command")
+        || output.contains("unknown command: docker compose")
+        || output.contains("unknown shorthand flag")
+}
+
+impl Rule for DockerComposeV2 {
+    fn name(&self) -> &'static str {
+        "docker_compose_v2"
+    }
+
+    fn description(&self) -> &'static str {
+        "Switches between the standalone and plugin compose, whichever is installed, e.g. `docker-compose up` -> `docker compose up`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        let script = command.trimmed();
+        if to_plugin(script).is_some() {
+            standalone_missing(&output) && has_compose_plugin()
+        } else {
+            to_standalone(script).is_some()
+                && plugin_missing(&output)
+                && has_standalone_compose(command)
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        to_plugin(script)
+            .or_else(|| to_standalone(script))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::sudo_support;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_rewrite() {
+        assert_eq!(
+            to_plugin("docker-compose up -d"),
+            Some("docker compose up -d".to_string())
+        );
+        assert_eq!(to_plugin("docker-composer up"), None);
+
+        assert_eq!(
+            to_standalone("docker compose up -d"),
+            Some("docker-compose up -d".to_string())
+        );
+        assert_eq!(
+            to_standalone("docker compose"),
+            Some("docker-compose".to_string())
+        );
+        assert_eq!(to_standalone("docker ps"), None);
+    }
+
+    #[test]
+    fn test_missing_outputs() {
+        assert!(standalone_missing(
+            "bash: docker-compose: command not found\n"
+        ));
+        assert!(!standalone_missing("bash: docker: command not found\n"));
+        assert!(plugin_missing(
+            "docker: 'compose' is not a docker command.\nSee 'docker --help'\n"
+        ));
+        assert!(plugin_missing("unknown shorthand flag: 'd' in -d\n"));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("docker-compose up -d".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, String::new());
+        assert_eq!(
+            DockerComposeV2.get_new_commands(&command, &result),
+            vec!["docker compose up -d"]
+        );
+
+        let command = Command::new("docker compose up -d".to_string(), Shell::Bash);
+        assert_eq!(
+            DockerComposeV2.get_new_commands(&command, &result),
+            vec!["docker-compose up -d"]
+        );
+    }
+
+    #[test]
+    fn test_sudo() {
+        let rule = sudo_support(DockerComposeV2);
+        let command = Command::new("sudo docker-compose logs".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, String::new());
+        assert_eq!(
+            rule.get_new_commands(&command, &result),
+            vec!["sudo docker compose logs"]
+        );
+    }
+
+    #[test]
+    fn test_no_match_for_other_commands() {
+        let command = Command::new("docker ps".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "'compose' is not a docker command\n".to_string());
+        assert!(!DockerComposeV2.is_match(&command, &result));
+    }
+}
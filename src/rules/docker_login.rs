@@ -0,0 +1,83 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+
+/// Messages docker prints when a registry needs the user to log in
+#[allow(clippy::type_complexity)]
+const LOGIN_MESSAGES: &[&str] = &[
+    "access to the resource is denied",
+    "authentication required",
+    "pull access denied",
+];
+
+/// Logs in to the registry before retrying, e.g. `docker push app` -> `docker login && docker push app`
+pub struct DockerLogin;
+
+impl Rule for DockerLogin {
+    fn name(&self) -> &'static str {
+        "docker_login"
+    }
+
+    fn description(&self) -> &'static str {
+        "Logs in to the registry before retrying, e.g. `docker push app` -> `docker login && docker push app`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output().to_lowercase();
+        command
+            .arguments()
+            .iter()
+            .any(|arg| matches!(*arg, "push" | "pull"))
+            && LOGIN_MESSAGES
+                .iter()
+                .any(|message| output.contains(message))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        vec![format!("docker login && {}", command.trimmed())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("docker push me/app".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "The push refers to repository [docker.io/me/app]\n\
+             denied: requested access to the resource is denied\n"
+                .to_string(),
+        );
+        assert!(DockerLogin.is_match(&command, &result));
+
+        let command = Command::new("docker pull ghcr.io/me/app".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "Error response from daemon: Head \"https://ghcr.io/v2/me/app/manifests/latest\": \
+             unauthorized: authentication required\n"
+                .to_string(),
+        );
+        assert!(DockerLogin.is_match(&command, &result));
+
+        let command = Command::new("docker build .".to_string(), Shell::Bash);
+        assert!(!DockerLogin.is_match(&command, &result));
+
+        let command = Command::new("docker push me/app".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "no space left on device\n".to_string());
+        assert!(!DockerLogin.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("docker push me/app".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, String::new());
+        assert_eq!(
+            DockerLogin.get_new_commands(&command, &result),
+            vec!["docker login && docker push me/app"]
+        );
+    }
+}
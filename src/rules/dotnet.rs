@@ -0,0 +1,150 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into the .NET CLI
+#[allow(clippy::type_complexity)]
+const DOTNET_COMMANDS: &[&str] = &[
+    "add",
+    "build",
+    "build-server",
+    "clean",
+    "format",
+    "help",
+    "list",
+    "msbuild",
+    "new",
+    "nuget",
+    "pack",
+    "publish",
+    "remove",
+    "restore",
+    "run",
+    "sdk",
+    "sln",
+    "store",
+    "test",
+    "tool",
+    "vstest",
+    "watch",
+    "workload",
+];
+
+/// Fixes misspelled dotnet commands and restores missing packages,
+/// e.g. `dotnet biuld` -> `dotnet build`
+pub struct Dotnet;
+
+/// Gets the command dotnet couldn't find, e.g. from `dotnet-biuld does not exist`
+#[allow(clippy::type_complexity)]
+fn unknown_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"dotnet-(\S+) does not exist").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the commands listed after "Did you mean the following command?"
+#[allow(clippy::type_complexity)]
+fn suggested_commands(output: &str) -> Vec<String> {
+    utils::extract_suggestions(output)
+        .iter()
+        .map(|suggestion| suggestion.trim_start_matches("dotnet ").to_string())
+        .collect()
+}
+
+/// Checks if the build failed because the NuGet packages weren't restored
+fn needs_restore(output: &str) -> bool {
+    output.contains("NETSDK1004") || output.contains("Run a NuGet package restore")
+}
+
+impl Rule for Dotnet {
+    fn name(&self) -> &'static str {
+        "dotnet"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled dotnet commands and restores missing packages, e.g. `dotnet biuld` -> `dotnet build`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        unknown_command(&output).is_some() || needs_restore(&output)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let script = command.trimmed();
+        let Some(broken) = unknown_command(&output) else {
+            return if needs_restore(&output) {
+                vec![format!("dotnet restore && {script}")]
+            } else {
+                Vec::new()
+            };
+        };
+
+        utils::suggested_fixes(
+            script,
+            &broken,
+            &suggested_commands(&output),
+            DOTNET_COMMANDS,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const UNKNOWN_OUTPUT: &str = "Could not execute because the specified command or file was \
+        not found.\nPossible reasons for this include:\n  * You misspelled a built-in dotnet \
+        command.\n  * You intended to execute a .NET program, but dotnet-biuld does not exist.\n  \
+        * You intended to run a global tool, but a dotnet-prefixed executable with this name \
+        could not be found on the PATH.\n";
+
+    const RESTORE_OUTPUT: &str = "/src/App/App.csproj : error NETSDK1004: Assets file \
+        '/src/App/obj/project.assets.json' not found. Run a NuGet package restore to generate \
+        this file.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("dotnet biuld".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, UNKNOWN_OUTPUT.to_string());
+        assert!(Dotnet.is_match(&command, &result));
+
+        let command = Command::new("dotnet build --no-restore".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, RESTORE_OUTPUT.to_string());
+        assert!(Dotnet.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "error CS1002: ; expected\n".to_string());
+        assert!(!Dotnet.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("dotnet biuld -c Release".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, UNKNOWN_OUTPUT.to_string());
+        assert_eq!(
+            Dotnet.get_new_commands(&command, &result)[0],
+            "dotnet build -c Release"
+        );
+
+        let command = Command::new("dotnet test --no-restore".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, RESTORE_OUTPUT.to_string());
+        assert_eq!(
+            Dotnet.get_new_commands(&command, &result),
+            vec!["dotnet restore && dotnet test --no-restore"]
+        );
+    }
+
+    #[test]
+    fn test_suggested_commands() {
+        let output = "Did you mean the following command?\n    dotnet build\n\n";
+        assert_eq!(suggested_commands(output), vec!["build"]);
+    }
+}
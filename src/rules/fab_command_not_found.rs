@@ -0,0 +1,91 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Fixes misspelled Fabric commands, e.g. `fab deloyp` -> `fab deploy`
+pub struct FabCommandNotFound;
+
+/// Gets the first word of each indented line after the `header` line
+#[allow(clippy::type_complexity)]
+fn listed_after(output: &str, header: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with(header))
+        .skip(1)
+        .skip_while(|line| line.trim().is_empty())
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+impl Rule for FabCommandNotFound {
+    fn name(&self) -> &'static str {
+        "fab_command_not_found"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled Fabric commands, e.g. `fab deloyp` -> `fab deploy`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        result.output().contains("Warning: Command(s) not found:")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let available = listed_after(&output, "Available commands:");
+        let mut script = command.trimmed().to_string();
+        let mut changed = false;
+        for broken in listed_after(&output, "Warning: Command(s) not found:") {
+            if let Some(fixed) = utils::get_closest(&broken, &available) {
+                // Commands can take arguments, as in `fab deploy:prod`
+                let argument = script
+                    .split_whitespace()
+                    .find(|word| word.split(':').next() == Some(broken.as_str()))
+                    .map(str::to_string);
+                if let Some(argument) = argument {
+                    let replaced = argument.replacen(&broken, &fixed, 1);
+                    script = utils::replace_argument(&script, &argument, &replaced);
+                    changed = true;
+                }
+            }
+        }
+        if changed { vec![script] } else { Vec::new() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "\nWarning: Command(s) not found:\n    extenson\n    deloyp\n\n\
+        Available commands:\n\n    update_config\n    prepare_extension\n    \
+        Template               A string class for supporting $-substitutions.\n    \
+        deploy\n    glob                   Return a list of paths matching a pathname pattern.\n    \
+        install_web\n    set_version\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("fab extenson".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(FabCommandNotFound.is_match(&command, &result));
+
+        let command = Command::new("fab deploy".to_string(), Shell::Bash);
+        let result =
+            CommandResult::failure(1, "Fatal error: local() encountered an error\n".to_string());
+        assert!(!FabCommandNotFound.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("fab extenson deloyp:prod".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            FabCommandNotFound.get_new_commands(&command, &result),
+            vec!["fab prepare_extension deploy:prod"]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into flutter
+#[allow(clippy::type_complexity)]
+const FLUTTER_COMMANDS: &[&str] = &[
+    "analyze",
+    "assemble",
+    "attach",
+    "build",
+    "channel",
+    "clean",
+    "config",
+    "create",
+    "custom-devices",
+    "devices",
+    "doctor",
+    "downgrade",
+    "drive",
+    "emulators",
+    "gen-l10n",
+    "install",
+    "logs",
+    "precache",
+    "pub",
+    "run",
+    "screenshot",
+    "symbolize",
+    "test",
+    "upgrade",
+];
+
+/// Fixes misspelled flutter commands and fetches missing packages,
+/// e.g. `flutter biuld apk` -> `flutter build apk`
+pub struct Flutter;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"Could not find a command named "([^"]+)""#).expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the commands flutter suggests, e.g. from `Did you mean "build"?`
+#[allow(clippy::type_complexity)]
+fn suggested_commands(output: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX
+        .get_or_init(|| Regex::new(r#"Did you mean (?:one of )?([^?]+)\?"#).expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| {
+            captures[1]
+                .split(',')
+                .map(|name| name.trim().trim_matches('"').to_string())
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Checks if flutter asks to fetch the packages first
+fn needs_pub_get(output: &str) -> bool {
+    output.contains("Run 'flutter pub get'")
+        || output.contains("run \"flutter pub get\"")
+        || output.contains("Run \"flutter pub get\"")
+}
+
+impl Rule for Flutter {
+    fn name(&self) -> &'static str {
+        "flutter"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled flutter commands and fetches missing packages, e.g. `flutter biuld apk` -> `flutter build apk`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        unknown_command(&output).is_some() || needs_pub_get(&output)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let script = command.trimmed();
+        let Some(broken) = unknown_command(&output) else {
+            return if needs_pub_get(&output) {
+                vec![format!("flutter pub get && {script}")]
+            } else {
+                Vec::new()
+            };
+        };
+
+        utils::suggested_fixes(
+            script,
+            &broken,
+            &suggested_commands(&output),
+            FLUTTER_COMMANDS,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const UNKNOWN_OUTPUT: &str = "Could not find a command named \"biuld\".\n\n\
+        Run 'flutter -h' (or 'flutter <command> -h') for available flutter commands and options.\n";
+
+    const PUB_GET_OUTPUT: &str = "Error: Couldn't resolve the package 'http' in \
+        'package:http/http.dart'.\nRun 'flutter pub get' to fetch the missing packages.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("flutter biuld apk".to_string(), Shell::Bash);
+        let result = CommandResult::failure(64, UNKNOWN_OUTPUT.to_string());
+        assert!(Flutter.is_match(&command, &result));
+
+        let command = Command::new("flutter run".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, PUB_GET_OUTPUT.to_string());
+        assert!(Flutter.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "No supported devices connected.\n".to_string());
+        assert!(!Flutter.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("flutter biuld apk".to_string(), Shell::Bash);
+        let result = CommandResult::failure(64, UNKNOWN_OUTPUT.to_string());
+        assert_eq!(
+            Flutter.get_new_commands(&command, &result)[0],
+            "flutter build apk"
+        );
+
+        let command = Command::new("flutter run -d chrome".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, PUB_GET_OUTPUT.to_string());
+        assert_eq!(
+            Flutter.get_new_commands(&command, &result),
+            vec!["flutter pub get && flutter run -d chrome"]
+        );
+    }
+
+    #[test]
+    fn test_suggested_commands() {
+        assert_eq!(
+            suggested_commands("Could not find a command named \"tst\". Did you mean \"test\"?"),
+            vec!["test"]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands shipped with RubyGems, used when `gem help commands` is unavailable
+#[allow(clippy::type_complexity)]
+const GEM_COMMANDS: &[&str] = &[
+    "build",
+    "cert",
+    "check",
+    "cleanup",
+    "contents",
+    "dependency",
+    "environment",
+    "exec",
+    "fetch",
+    "generate_index",
+    "help",
+    "info",
+    "install",
+    "list",
+    "lock",
+    "mirror",
+    "open",
+    "outdated",
+    "owner",
+    "pristine",
+    "push",
+    "query",
+    "rdoc",
+    "rebuild",
+    "search",
+    "signin",
+    "signout",
+    "sources",
+    "specification",
+    "stale",
+    "uninstall",
+    "unpack",
+    "update",
+    "which",
+    "yank",
+];
+
+/// Fixes misspelled gem commands, e.g. `gem isntall rails` -> `gem install rails`
+pub struct GemUnknownCommand;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(result: &CommandResult) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"Unknown command (\S+)").expect("valid regex"));
+    regex
+        .captures(&result.output())
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses the command names listed by `gem help commands`
+#[allow(clippy::type_complexity)]
+fn parse_gem_help(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.starts_with("    ") && !line.starts_with("     "))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Gets the available gem commands, reusing what gem listed last time
+#[allow(clippy::type_complexity)]
+fn gem_commands() -> Vec<String> {
+    let mut commands: Vec<String> = GEM_COMMANDS.iter().map(|c| c.to_string()).collect();
+    if let Some(output) = utils::cached_output("gem", &["help", "commands"]) {
+        for name in parse_gem_help(&output) {
+            if !commands.contains(&name) {
+                commands.push(name);
+            }
+        }
+    }
+    commands
+}
+
+impl Rule for GemUnknownCommand {
+    fn name(&self) -> &'static str {
+        "gem_unknown_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled gem commands, e.g. `gem isntall rails` -> `gem install rails`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        unknown_command(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match unknown_command(result) {
+            Some(broken) => utils::replace_command(command.trimmed(), &broken, &gem_commands()),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("gem isntall rails".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "ERROR:  While executing gem ... \
+            (Gem::CommandLineError)\n    Unknown command isntall\n"
+                .to_string(),
+        );
+        assert!(GemUnknownCommand.is_match(&command, &result));
+
+        let command = Command::new("gem install rails".to_string(), Shell::Bash);
+        let result = CommandResult::failure(2, "ERROR:  Could not find a valid gem\n".to_string());
+        assert!(!GemUnknownCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("gem isntall rails".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "    Unknown command isntall\n".to_string());
+        assert_eq!(
+            GemUnknownCommand.get_new_commands(&command, &result)[0],
+            "gem install rails"
+        );
+    }
+
+    #[test]
+    fn test_parse_gem_help() {
+        let output = "GEM commands are:\n\n    build             Build a gem from a gemspec\n    \
+            cert              Manage RubyGems certificates\n                      and signing settings\n";
+        assert_eq!(parse_gem_help(output), vec!["build", "cert"]);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use std::path::Path;
+
+/// Messages go prints when `go run` can't make sense of its target
+#[allow(clippy::type_complexity)]
+const TARGET_ERRORS: &[&str] = &[
+    "is not in std",
+    "is not in GOROOT",
+    "cannot find package",
+    "no go files listed",
+    "no Go files in",
+    "directory not found",
+];
+
+/// Points `go run` at an existing file or package, e.g. `go run hello` -> `go run hello.go`
+pub struct GoRun;
+
+/// Gets the first argument after `run` that isn't a flag
+#[allow(clippy::type_complexity)]
+fn target(command: &Command) -> Option<&str> {
+    command
+        .arguments()
+        .into_iter()
+        .skip_while(|arg| *arg != "run")
+        .skip(1)
+        .find(|arg| !arg.starts_with('-'))
+}
+
+/// Checks if the directory holds any Go source files
+fn has_go_files(dir: &Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .any(|entry| entry.path().extension().is_some_and(|ext| ext == "go"))
+        })
+        .unwrap_or(false)
+}
+
+impl Rule for GoRun {
+    fn name(&self) -> &'static str {
+        "go_run"
+    }
+
+    fn description(&self) -> &'static str {
+        "Points `go run` at an existing file or package, e.g. `go run hello` -> `go run hello.go`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        command.argument(0) == Some("run")
+            && target(command).map_or(true, |target| !target.ends_with(".go"))
+            && TARGET_ERRORS.iter().any(|error| output.contains(error))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        let cwd = Path::new(&command.cwd);
+        let Some(target) = target(command) else {
+            return if has_go_files(cwd) {
+                vec![format!("{script} .")]
+            } else {
+                Vec::new()
+            };
+        };
+
+        let mut fixes: Vec<String> = Vec::new();
+        if cwd.join(format!("{target}.go")).is_file() {
+            fixes.push(command.replace_argument(target, &format!("{target}.go")));
+        }
+        if !target.starts_with('.') && has_go_files(&cwd.join(target)) {
+            fixes.push(command.replace_argument(target, &format!("./{target}")));
+        }
+        if fixes.is_empty() && has_go_files(cwd) {
+            fixes.push(command.replace_argument(target, "."));
+        }
+        fixes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[allow(clippy::type_complexity)]
+    fn go(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = CommandResult::failure(
+            1,
+            "package hello is not in std (/usr/local/go/src/hello)\n".to_string(),
+        );
+        assert!(GoRun.is_match(&go("go run hello", &dir), &result));
+        assert!(!GoRun.is_match(&go("go run hello.go", &dir), &result));
+        assert!(!GoRun.is_match(&go("go build hello", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("hello.go"), "package main\n").unwrap();
+        std::fs::create_dir(dir.path().join("server")).unwrap();
+        std::fs::write(dir.path().join("server").join("main.go"), "package main\n").unwrap();
+        let result = CommandResult::failure(1, String::new());
+
+        assert_eq!(
+            GoRun.get_new_commands(&go("go run hello -v", &dir), &result),
+            vec!["go run hello.go -v"]
+        );
+        assert_eq!(
+            GoRun.get_new_commands(&go("go run server", &dir), &result),
+            vec!["go run ./server"]
+        );
+        assert_eq!(
+            GoRun.get_new_commands(&go("go run main", &dir), &result),
+            vec!["go run ."]
+        );
+        assert_eq!(
+            GoRun.get_new_commands(&go("go run", &dir), &result),
+            vec!["go run ."]
+        );
+    }
+}
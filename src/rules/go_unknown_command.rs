@@ -0,0 +1,130 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands shipped with every Go toolchain, used when `go help` is unavailable
+#[allow(clippy::type_complexity)]
+const DEFAULT_GO_COMMANDS: &[&str] = &[
+    "bug",
+    "build",
+    "clean",
+    "doc",
+    "env",
+    "fix",
+    "fmt",
+    "generate",
+    "get",
+    "install",
+    "list",
+    "mod",
+    "work",
+    "run",
+    "telemetry",
+    "test",
+    "tool",
+    "version",
+    "vet",
+];
+
+/// Fixes misspelled go commands, e.g. `go biuld` -> `go build`
+pub struct GoUnknownCommand;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"go (\S+): unknown command").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses the commands listed after "The commands are:" in `go help`
+#[allow(clippy::type_complexity)]
+fn parse_help(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("The commands are:"))
+        .skip(1)
+        .skip_while(|line| line.trim().is_empty())
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Gets the available go commands, reusing what go listed last time
+#[allow(clippy::type_complexity)]
+fn go_commands() -> Vec<String> {
+    let mut commands: Vec<String> = DEFAULT_GO_COMMANDS.iter().map(|c| c.to_string()).collect();
+    let output = utils::cached_output("go", &["help"]).unwrap_or_default();
+    for name in parse_help(&output) {
+        if !commands.contains(&name) {
+            commands.push(name);
+        }
+    }
+    commands
+}
+
+impl Rule for GoUnknownCommand {
+    fn name(&self) -> &'static str {
+        "go_unknown_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled go commands, e.g. `go biuld` -> `go build`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        unknown_command(&result.output())
+            .is_some_and(|broken| utils::get_closest(&broken, &go_commands()).is_some())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match unknown_command(&result.output()) {
+            Some(broken) => utils::replace_command(command.trimmed(), &broken, &go_commands()),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "go biuld: unknown command\nRun 'go help' for usage.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("go biuld ./...".to_string(), Shell::Bash);
+        let result = CommandResult::failure(2, OUTPUT.to_string());
+        assert!(GoUnknownCommand.is_match(&command, &result));
+
+        let command = Command::new("go build ./...".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "main.go:3:1: syntax error\n".to_string());
+        assert!(!GoUnknownCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("go biuld ./...".to_string(), Shell::Bash);
+        let result = CommandResult::failure(2, OUTPUT.to_string());
+        assert_eq!(
+            GoUnknownCommand.get_new_commands(&command, &result)[0],
+            "go build ./..."
+        );
+    }
+
+    #[test]
+    fn test_parse_help() {
+        let help = "Go is a tool for managing Go source code.\n\nUsage:\n\n\tgo <command> \
+            [arguments]\n\nThe commands are:\n\n\tbug         start a bug report\n\t\
+            build       compile packages and dependencies\n\nUse \"go help <command>\" for \
+            more information about a command.\n";
+        assert_eq!(parse_help(help), vec!["bug", "build"]);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Files whose changes can change the tasks of a gradle project
+#[allow(clippy::type_complexity)]
+const BUILD_FILES: &[&str] = &[
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+];
+
+/// Fixes misspelled gradle tasks, e.g. `gradle cmpileJava` -> `gradle compileJava`
+pub struct GradleNoTask;
+
+#[allow(clippy::type_complexity)]
+fn missing_task(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"Task '([^']+)' (?:is ambiguous|not found)").expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses the task names listed under the dashed headers of `gradle tasks --all`
+#[allow(clippy::type_complexity)]
+fn parse_tasks(output: &str) -> Vec<String> {
+    let mut tasks: Vec<String> = Vec::new();
+    let mut in_section = false;
+    for line in output.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            in_section = false;
+        } else if line.chars().all(|c| c == '-') {
+            in_section = true;
+        } else if in_section {
+            if let Some(task) = line.split(" - ").next().map(str::trim) {
+                if !task.contains(' ') && !tasks.iter().any(|t| t == task) {
+                    tasks.push(task.to_string());
+                }
+            }
+        }
+    }
+    tasks
+}
+
+/// Gets the tasks of the project in `dir`, reusing what gradle listed until a build file changes
+#[allow(clippy::type_complexity)]
+fn project_tasks(program: &str, dir: &str) -> Vec<String> {
+    utils::cached_output_in(dir, program, &["tasks", "--all", "--quiet"], BUILD_FILES)
+        .map(|output| parse_tasks(&output))
+        .unwrap_or_default()
+}
+
+impl Rule for GradleNoTask {
+    fn name(&self) -> &'static str {
+        "gradle_no_task"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled gradle tasks, e.g. `gradle cmpileJava` -> `gradle compileJava`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_task(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let (Some(program), Some(task)) = (command.program(), missing_task(&result.output()))
+        else {
+            return Vec::new();
+        };
+        let tasks = project_tasks(program, &command.cwd);
+        utils::replace_command(command.trimmed(), &task, &tasks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "FAILURE: Build failed with an exception.\n\n\
+        * What went wrong:\nTask 'cmpileJava' not found in root project 'app'.\n";
+
+    const TASKS: &str = "\n------------------------------------------------------------\n\
+        Tasks runnable from root project 'app'\n\
+        ------------------------------------------------------------\n\n\
+        Build tasks\n-----------\nassemble - Assembles the outputs of this project.\n\
+        build - Assembles and tests this project.\n\n\
+        Other tasks\n-----------\ncompileJava - Compiles main Java source.\n\
+        app:processResources - Processes main resources.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("./gradlew cmpileJava".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(GradleNoTask.is_match(&command, &result));
+
+        let command = Command::new("gradle build".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "BUILD FAILED in 2s\n".to_string());
+        assert!(!GradleNoTask.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_parse_tasks() {
+        assert_eq!(
+            parse_tasks(TASKS),
+            vec!["assemble", "build", "compileJava", "app:processResources"]
+        );
+    }
+
+    #[test]
+    fn test_missing_task() {
+        assert_eq!(missing_task(OUTPUT), Some("cmpileJava".to_string()));
+        assert_eq!(
+            missing_task("Task 'comp' is ambiguous in root project 'app'."),
+            Some("comp".to_string())
+        );
+    }
+}
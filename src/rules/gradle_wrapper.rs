@@ -0,0 +1,68 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use std::path::Path;
+
+/// Uses the project's gradle wrapper when gradle isn't installed, e.g. `gradle build` -> `./gradlew build`
+pub struct GradleWrapper;
+
+/// Checks if the directory has a gradle wrapper script
+fn has_wrapper(dir: &str) -> bool {
+    Path::new(dir).join("gradlew").is_file()
+}
+
+impl Rule for GradleWrapper {
+    fn name(&self) -> &'static str {
+        "gradle_wrapper"
+    }
+
+    fn description(&self) -> &'static str {
+        "Uses the project's gradle wrapper when gradle isn't installed, e.g. `gradle build` -> `./gradlew build`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        result.output().contains("not found")
+            && has_wrapper(&command.cwd)
+            && !utils::is_available("gradle", command)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        vec![command.replace_command("./gradlew")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_has_wrapper() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        assert!(!has_wrapper(&path));
+
+        std::fs::write(dir.path().join("gradlew"), "#!/bin/sh\n").unwrap();
+        assert!(has_wrapper(&path));
+    }
+
+    #[test]
+    fn test_no_match_without_wrapper() {
+        let dir = tempfile::tempdir().unwrap();
+        let command = Command::new("gradle build".to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string());
+        let result = CommandResult::failure(127, "gradle: command not found\n".to_string());
+        assert!(!GradleWrapper.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("gradle assemble --info".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, "gradle: command not found\n".to_string());
+        assert_eq!(
+            GradleWrapper.get_new_commands(&command, &result),
+            vec!["./gradlew assemble --info"]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use std::path::Path;
+
+/// Puts the file after the pattern, e.g. `grep file.txt pattern` -> `grep pattern file.txt`
+pub struct GrepArgumentsOrder;
+
+/// Gets the index of the first argument that names an existing file
+#[allow(clippy::type_complexity)]
+fn file_index(words: &[utils::Word], cwd: &str) -> Option<usize> {
+    words
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_, word)| {
+            !word.value.starts_with('-') && Path::new(cwd).join(&word.value).is_file()
+        })
+        .map(|(index, _)| index)
+}
+
+impl Rule for GrepArgumentsOrder {
+    fn name(&self) -> &'static str {
+        "grep_arguments_order"
+    }
+
+    fn description(&self) -> &'static str {
+        "Puts the file after the pattern, e.g. `grep file.txt pattern` -> `grep pattern file.txt`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        result.output().contains(": No such file or directory") && {
+            let words = utils::script_words(command.trimmed());
+            file_index(&words, &command.cwd).is_some_and(|index| index + 1 < words.len())
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        let words = utils::script_words(script);
+        let Some(index) = file_index(&words, &command.cwd) else {
+            return Vec::new();
+        };
+        // The words from the file on each take the place of the next, as written
+        #[allow(clippy::type_complexity)]
+        let moved: Vec<&utils::Word> = words[index..].iter().collect();
+        vec![utils::rewrite_words(script, &moved, |word| {
+            let position = moved.iter().position(|moved| moved.start == word.start);
+            let next = moved[position.map_or(0, |position| (position + 1) % moved.len())];
+            script[next.start..next.end].to_string()
+        })]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[allow(clippy::type_complexity)]
+    fn grep(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "todo\n").unwrap();
+        let result =
+            CommandResult::failure(2, "grep: todo: No such file or directory\n".to_string());
+        assert!(GrepArgumentsOrder.is_match(&grep("grep notes.txt todo", &dir), &result));
+        assert!(!GrepArgumentsOrder.is_match(&grep("grep todo notes.txt", &dir), &result));
+        assert!(!GrepArgumentsOrder.is_match(&grep("grep todo missing.txt", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "todo\n").unwrap();
+        let result =
+            CommandResult::failure(2, "grep: fix me: No such file or directory\n".to_string());
+        assert_eq!(
+            GrepArgumentsOrder.get_new_commands(&grep("grep -i notes.txt 'fix me'", &dir), &result),
+            vec!["grep -i 'fix me' notes.txt"]
+        );
+        assert_eq!(
+            GrepArgumentsOrder.get_new_commands(&grep("grep  notes.txt \"$USER\"", &dir), &result),
+            vec!["grep  \"$USER\" notes.txt"]
+        );
+    }
+}
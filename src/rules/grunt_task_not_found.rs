@@ -0,0 +1,95 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled grunt tasks, e.g. `grunt buld:dev` -> `grunt build:dev`
+pub struct GruntTaskNotFound;
+
+#[allow(clippy::type_complexity)]
+fn missing_task(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX
+        .get_or_init(|| Regex::new(r#"Warning: Task "([^"]+)" not found"#).expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses the tasks listed under "Available tasks" in `grunt --help`
+#[allow(clippy::type_complexity)]
+fn parse_tasks(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("Available tasks"))
+        .skip(1)
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+impl Rule for GruntTaskNotFound {
+    fn name(&self) -> &'static str {
+        "grunt_task_not_found"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled grunt tasks, e.g. `grunt buld:dev` -> `grunt build:dev`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_task(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(missing) = missing_task(&result.output()) else {
+            return Vec::new();
+        };
+        // Only the task is misspelled, the `:target` part belongs to its config
+        let (task, target) = match missing.split_once(':') {
+            Some((task, target)) => (task.to_string(), format!(":{target}")),
+            None => (missing.clone(), String::new()),
+        };
+        let tasks = utils::get_output_in(&command.cwd, "grunt", &["--help"])
+            .map(|output| parse_tasks(&output))
+            .unwrap_or_default();
+        utils::get_close_matches(&task, &tasks, utils::DEFAULT_MATCHES, utils::DEFAULT_CUTOFF)
+            .iter()
+            .map(|fixed| command.replace_argument(&missing, &format!("{fixed}{target}")))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Warning: Task \"buld:dev\" not found. Use --force to continue.\n\n\
+        Aborted due to warnings.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("grunt buld:dev".to_string(), Shell::Bash);
+        let result = CommandResult::failure(3, OUTPUT.to_string());
+        assert!(GruntTaskNotFound.is_match(&command, &result));
+
+        let command = Command::new("grunt build".to_string(), Shell::Bash);
+        let result =
+            CommandResult::failure(6, "Fatal error: Unable to find local grunt.\n".to_string());
+        assert!(!GruntTaskNotFound.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_parse_tasks() {
+        let help = "Grunt: The JavaScript Task Runner (v1.6.1)\n\n\
+            Available tasks\n        uglify  Minify files with UglifyJS. *\n         \
+            watch  Run predefined tasks whenever watched files change.\n       \
+            default  Alias for \"uglify\" task.\n\nTasks run in the order specified.\n";
+        assert_eq!(parse_tasks(help), vec!["uglify", "watch", "default"]);
+        assert_eq!(missing_task(OUTPUT), Some("buld:dev".to_string()));
+    }
+}
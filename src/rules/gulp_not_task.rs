@@ -0,0 +1,85 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled gulp tasks, e.g. `gulp srve` -> `gulp serve`
+pub struct GulpNotTask;
+
+#[allow(clippy::type_complexity)]
+fn missing_task(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"Task '([^']+)' is not in your gulpfile").expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses `gulp --tasks-simple`, which prints one task per line
+#[allow(clippy::type_complexity)]
+fn parse_tasks(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl Rule for GulpNotTask {
+    fn name(&self) -> &'static str {
+        "gulp_not_task"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled gulp tasks, e.g. `gulp srve` -> `gulp serve`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_task(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(task) = missing_task(&result.output()) else {
+            return Vec::new();
+        };
+        let tasks = utils::get_output_in(&command.cwd, "gulp", &["--tasks-simple"])
+            .map(|output| parse_tasks(&output))
+            .unwrap_or_default();
+        utils::replace_command(command.trimmed(), &task, &tasks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "[10:21:03] Using gulpfile ~/app/gulpfile.js\n\
+        [10:21:03] Task 'srve' is not in your gulpfile\n\
+        [10:21:03] Please check the documentation for proper gulpfile formatting\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("gulp srve".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(GulpNotTask.is_match(&command, &result));
+
+        let command = Command::new("gulp serve".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "Error: Cannot find module 'sass'\n".to_string());
+        assert!(!GulpNotTask.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_parse_tasks() {
+        assert_eq!(
+            parse_tasks("default\nserve\n  build\n\n"),
+            vec!["default", "serve", "build"]
+        );
+        assert_eq!(missing_task(OUTPUT), Some("srve".to_string()));
+    }
+}
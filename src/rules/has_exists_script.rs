@@ -0,0 +1,61 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use std::path::Path;
+
+/// Runs a script from the current directory, e.g. `manage.py runserver` -> `./manage.py runserver`
+pub struct HasExistsScript;
+
+impl Rule for HasExistsScript {
+    fn name(&self) -> &'static str {
+        "has_exists_script"
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs a script from the current directory, e.g. `manage.py runserver` -> `./manage.py runserver`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        command.program().is_some_and(|program| {
+            !program.contains('/') && Path::new(&command.cwd).join(program).is_file()
+        }) && result.output().contains("command not found")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        vec![format!("./{}", command.trimmed())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[allow(clippy::type_complexity)]
+    fn script(text: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(text.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("manage.py"), "#!/usr/bin/env python\n").unwrap();
+        let result =
+            CommandResult::failure(127, "bash: manage.py: command not found\n".to_string());
+        assert!(HasExistsScript.is_match(&script("manage.py runserver", &dir), &result));
+        assert!(!HasExistsScript.is_match(&script("./manage.py runserver", &dir), &result));
+        assert!(!HasExistsScript.is_match(&script("build.sh", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let result =
+            CommandResult::failure(127, "bash: manage.py: command not found\n".to_string());
+        assert_eq!(
+            HasExistsScript.get_new_commands(&script("manage.py runserver", &dir), &result),
+            vec!["./manage.py runserver"]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into helm, used on top of the ones helm suggests
+#[allow(clippy::type_complexity)]
+const HELM_COMMANDS: &[&str] = &[
+    "completion",
+    "create",
+    "dependency",
+    "env",
+    "get",
+    "help",
+    "history",
+    "install",
+    "lint",
+    "list",
+    "package",
+    "plugin",
+    "pull",
+    "push",
+    "registry",
+    "repo",
+    "rollback",
+    "search",
+    "show",
+    "status",
+    "template",
+    "test",
+    "uninstall",
+    "upgrade",
+    "verify",
+    "version",
+];
+
+/// URLs of popular chart repositories, used when a chart's repo isn't added yet
+#[allow(clippy::type_complexity)]
+const KNOWN_REPOS: &[(&str, &str)] = &[
+    ("bitnami", "https://charts.bitnami.com/bitnami"),
+    ("grafana", "https://grafana.github.io/helm-charts"),
+    (
+        "ingress-nginx",
+        "https://kubernetes.github.io/ingress-nginx",
+    ),
+    ("jetstack", "https://charts.jetstack.io"),
+    (
+        "prometheus-community",
+        "https://prometheus-community.github.io/helm-charts",
+    ),
+];
+
+/// Fixes misspelled helm commands and missing chart repositories,
+/// e.g. `helm isntall web ./chart` -> `helm install web ./chart`
+pub struct Helm;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX
+        .get_or_init(|| Regex::new(r#"unknown command "([^"]+)" for "helm"#).expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the repository helm couldn't find, e.g. from `repo bitnami not found`
+#[allow(clippy::type_complexity)]
+fn missing_repo(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"repo (\S+) not found").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the command adding the missing repository, when its URL is known
+#[allow(clippy::type_complexity)]
+fn repo_add_command(output: &str) -> Option<String> {
+    let repo = missing_repo(output)?;
+    KNOWN_REPOS
+        .iter()
+        .find(|(name, _)| *name == repo)
+        .map(|(name, url)| format!("helm repo add {name} {url}"))
+}
+
+/// Checks if helm asks for its repository index to be refreshed
+fn needs_repo_update(output: &str) -> bool {
+    output.contains("helm repo update") || output.contains("no cached repo found")
+}
+
+impl Rule for Helm {
+    fn name(&self) -> &'static str {
+        "helm"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled helm commands and missing chart repositories, e.g. `helm isntall web ./chart` -> `helm install web ./chart`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        unknown_command(&output).is_some()
+            || repo_add_command(&output).is_some()
+            || needs_repo_update(&output)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let script = command.trimmed();
+
+        if let Some(broken) = unknown_command(&output) {
+            return utils::suggested_fixes(
+                script,
+                &broken,
+                &utils::extract_suggestions(&output),
+                HELM_COMMANDS,
+            );
+        }
+
+        match repo_add_command(&output) {
+            Some(repo_add) => vec![format!("{repo_add} && {script}")],
+            None if needs_repo_update(&output) => vec![format!("helm repo update && {script}")],
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const UNKNOWN_OUTPUT: &str = "Error: unknown command \"isntall\" for \"helm\"\n\n\
+        Did you mean this?\n\tinstall\n\nRun 'helm --help' for usage.\n";
+
+    const REPO_OUTPUT: &str = "Error: INSTALLATION FAILED: repo bitnami not found\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("helm isntall web ./chart".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, UNKNOWN_OUTPUT.to_string());
+        assert!(Helm.is_match(&command, &result));
+
+        let command = Command::new(
+            "helm install db bitnami/postgresql".to_string(),
+            Shell::Bash,
+        );
+        let result = CommandResult::failure(1, REPO_OUTPUT.to_string());
+        assert!(Helm.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            1,
+            "Error: INSTALLATION FAILED: cannot re-use a name that is still in use\n".to_string(),
+        );
+        assert!(!Helm.is_match(&command, &result));
+        let result = CommandResult::failure(
+            1,
+            "Error: INSTALLATION FAILED: repo acme not found\n".to_string(),
+        );
+        assert!(!Helm.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("helm isntall web ./chart".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, UNKNOWN_OUTPUT.to_string());
+        assert_eq!(
+            Helm.get_new_commands(&command, &result)[0],
+            "helm install web ./chart"
+        );
+
+        let command = Command::new(
+            "helm install db bitnami/postgresql".to_string(),
+            Shell::Bash,
+        );
+        let result = CommandResult::failure(1, REPO_OUTPUT.to_string());
+        assert_eq!(
+            Helm.get_new_commands(&command, &result),
+            vec![
+                "helm repo add bitnami https://charts.bitnami.com/bitnami && \
+                 helm install db bitnami/postgresql"
+            ]
+        );
+
+        let command = Command::new("helm install db acme/postgresql".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "Error: INSTALLATION FAILED: no cached repo found. (try 'helm repo update')\n"
+                .to_string(),
+        );
+        assert_eq!(
+            Helm.get_new_commands(&command, &result),
+            vec!["helm repo update && helm install db acme/postgresql"]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Picks the app when several are in the git remotes, e.g. `heroku pg` -> `heroku pg --app myapp`
+pub struct HerokuMultipleApps;
+
+/// Gets the apps listed as `myapp (heroku)` under "Heroku remotes in repo:"
+#[allow(clippy::type_complexity)]
+fn remote_apps(output: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"(\S+) \([^)\s]+\)\s*$").expect("valid regex"));
+    output
+        .lines()
+        .skip_while(|line| !line.contains("Heroku remotes in repo:"))
+        .skip(1)
+        .filter_map(|line| regex.captures(line).map(|captures| captures[1].to_string()))
+        .collect()
+}
+
+impl Rule for HerokuMultipleApps {
+    fn name(&self) -> &'static str {
+        "heroku_multiple_apps"
+    }
+
+    fn description(&self) -> &'static str {
+        "Picks the app when several are in the git remotes, e.g. `heroku pg` -> `heroku pg --app myapp`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        output.contains("Multiple apps in git remotes") && !remote_apps(&output).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        remote_apps(&result.output())
+            .iter()
+            .map(|app| format!("{} --app {app}", command.trimmed()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = " ▸    Multiple apps in git remotes\n ▸    Usage: --remote heroku-dev\n \
+        ▸    or: --app myapp-dev\n ▸    Your local git repository has more than 1 app \
+        referenced in git remotes.\n ▸    Because of this, we can't determine which app you \
+        want to run this command against.\n ▸    Specify the app you want with --app or \
+        --remote.\n ▸    Heroku remotes in repo:\n ▸    myapp (heroku)\n ▸    myapp-dev \
+        (heroku-dev)\n ▸\n ▸    https://devcenter.heroku.com/articles/multiple-environments\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("heroku pg".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(HerokuMultipleApps.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, " ▸    Couldn't find that app.\n".to_string());
+        assert!(!HerokuMultipleApps.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("heroku pg".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            HerokuMultipleApps.get_new_commands(&command, &result),
+            vec!["heroku pg --app myapp", "heroku pg --app myapp-dev"]
+        );
+    }
+}
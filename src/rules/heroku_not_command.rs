@@ -0,0 +1,93 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled heroku commands, e.g. `heroku log` -> `heroku logs`
+pub struct HerokuNotCommand;
+
+#[allow(clippy::type_complexity)]
+fn broken_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"(\S+) is not a heroku command").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for HerokuNotCommand {
+    fn name(&self) -> &'static str {
+        "heroku_not_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled heroku commands, e.g. `heroku log` -> `heroku logs`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        broken_command(&output).is_some() && !utils::extract_suggestions(&output).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        match broken_command(&output) {
+            Some(broken) => utils::suggested_fixes(
+                command.trimmed(),
+                &broken,
+                &utils::extract_suggestions(&output),
+                &[] as &[&str],
+            ),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = " ▸    log is not a heroku command.\n ▸    Perhaps you meant logs.\n \
+        ▸    Run heroku _ to run heroku logs.\n ▸    Run heroku help for a list of available \
+        commands.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("heroku log --tail".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, OUTPUT.to_string());
+        assert!(HerokuNotCommand.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            127,
+            " ▸    zzz is not a heroku command.\n ▸    Run heroku help for a list of available \
+             commands.\n"
+                .to_string(),
+        );
+        assert!(!HerokuNotCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("heroku log --tail".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, OUTPUT.to_string());
+        assert_eq!(
+            HerokuNotCommand.get_new_commands(&command, &result),
+            vec!["heroku logs --tail"]
+        );
+
+        let result = CommandResult::failure(
+            127,
+            " ▸    pg:infoo is not a heroku command.\n ▸    Perhaps you meant pg:info\n"
+                .to_string(),
+        );
+        let command = Command::new("heroku pg:infoo".to_string(), Shell::Bash);
+        assert_eq!(
+            HerokuNotCommand.get_new_commands(&command, &result),
+            vec!["heroku pg:info"]
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+
+/// Runs the class instead of its compiled file, e.g. `java Foo.class` -> `java Foo`
+pub struct Java;
+
+impl Rule for Java {
+    fn name(&self) -> &'static str {
+        "java"
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs the class instead of its compiled file, e.g. `java Foo.class` -> `java Foo`"
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        &["java"]
+    }
+
+    fn is_match(&self, command: &Command, _result: &CommandResult) -> bool {
+        command.trimmed().ends_with(".class")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        match command.trimmed().strip_suffix(".class") {
+            Some(fixed) => vec![fixed.to_string()],
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::for_app;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Error: Could not find or load main class Foo.class\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("java Foo.class".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(Java.is_match(&command, &result));
+
+        let command = Command::new("java Foo".to_string(), Shell::Bash);
+        assert!(!Java.is_match(&command, &result));
+        let command = Command::new("javac Foo.class".to_string(), Shell::Bash);
+        assert!(!for_app(&["java"], Java).is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("java -cp build Foo.class".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            Java.get_new_commands(&command, &result),
+            vec!["java -cp build Foo"]
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use std::path::Path;
+
+/// Compiles the source file of a class, e.g. `javac Foo` -> `javac Foo.java`
+pub struct Javac;
+
+/// Gets the arguments naming a class whose `.java` source is in the directory
+#[allow(clippy::type_complexity)]
+fn classes_with_sources(command: &Command) -> Vec<&str> {
+    command
+        .arguments()
+        .into_iter()
+        .filter(|arg| !arg.starts_with('-') && !arg.ends_with(".java"))
+        .filter(|arg| {
+            Path::new(&command.cwd)
+                .join(format!("{arg}.java"))
+                .is_file()
+        })
+        .collect()
+}
+
+impl Rule for Javac {
+    fn name(&self) -> &'static str {
+        "javac"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compiles the source file of a class, e.g. `javac Foo` -> `javac Foo.java`"
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        &["javac"]
+    }
+
+    fn is_match(&self, command: &Command, _result: &CommandResult) -> bool {
+        !classes_with_sources(command).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let classes = classes_with_sources(command);
+        if classes.is_empty() {
+            return Vec::new();
+        }
+        let mut fixed = command.trimmed().to_string();
+        for class in classes {
+            fixed = utils::replace_argument(&fixed, class, &format!("{class}.java"));
+        }
+        vec![fixed]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "error: Class names, 'Foo', are only accepted if annotation \
+        processing is explicitly requested\n1 error\n";
+
+    #[allow(clippy::type_complexity)]
+    fn javac(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Foo.java"), "class Foo {}\n").unwrap();
+        let result = CommandResult::failure(2, OUTPUT.to_string());
+        assert!(Javac.is_match(&javac("javac Foo", &dir), &result));
+        assert!(!Javac.is_match(&javac("javac Bar", &dir), &result));
+        assert!(!Javac.is_match(&javac("javac Foo.java", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Foo.java"), "class Foo {}\n").unwrap();
+        std::fs::write(dir.path().join("Bar.java"), "class Bar {}\n").unwrap();
+        let result = CommandResult::failure(2, OUTPUT.to_string());
+        assert_eq!(
+            Javac.get_new_commands(&javac("javac -d out Foo Bar", &dir), &result),
+            vec!["javac -d out Foo.java Bar.java"]
+        );
+    }
+}
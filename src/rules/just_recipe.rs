@@ -0,0 +1,78 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled just recipes, e.g. `just buidl` -> `just build`
+pub struct JustRecipe;
+
+#[allow(clippy::type_complexity)]
+fn missing_recipe(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"(?i)justfile does not contain recipe [`']([^`']+)[`']").expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses `just --summary`, which prints the recipes separated by spaces
+#[allow(clippy::type_complexity)]
+fn parse_summary(output: &str) -> Vec<String> {
+    output.split_whitespace().map(str::to_string).collect()
+}
+
+impl Rule for JustRecipe {
+    fn name(&self) -> &'static str {
+        "just_recipe"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled just recipes, e.g. `just buidl` -> `just build`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_recipe(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(recipe) = missing_recipe(&result.output()) else {
+            return Vec::new();
+        };
+        let recipes = utils::get_output_in(&command.cwd, "just", &["--summary"])
+            .map(|output| parse_summary(&output))
+            .unwrap_or_default();
+        utils::replace_command(command.trimmed(), &recipe, &recipes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str =
+        "error: Justfile does not contain recipe `buidl`.\nDid you mean `build`?\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("just buidl".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(JustRecipe.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "error: No justfile found\n".to_string());
+        assert!(!JustRecipe.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_parse_output() {
+        assert_eq!(missing_recipe(OUTPUT), Some("buidl".to_string()));
+        assert_eq!(
+            parse_summary("build fmt lint test\n"),
+            vec!["build", "fmt", "lint", "test"]
+        );
+    }
+}
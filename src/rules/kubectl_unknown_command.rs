@@ -0,0 +1,138 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into kubectl, used on top of the ones kubectl suggests
+#[allow(clippy::type_complexity)]
+const KUBECTL_COMMANDS: &[&str] = &[
+    "annotate",
+    "api-resources",
+    "api-versions",
+    "apply",
+    "attach",
+    "auth",
+    "autoscale",
+    "certificate",
+    "cluster-info",
+    "completion",
+    "config",
+    "cordon",
+    "cp",
+    "create",
+    "debug",
+    "delete",
+    "describe",
+    "diff",
+    "drain",
+    "edit",
+    "events",
+    "exec",
+    "explain",
+    "expose",
+    "get",
+    "kustomize",
+    "label",
+    "logs",
+    "patch",
+    "plugin",
+    "port-forward",
+    "proxy",
+    "replace",
+    "rollout",
+    "run",
+    "scale",
+    "set",
+    "taint",
+    "top",
+    "uncordon",
+    "version",
+    "wait",
+];
+
+/// Fixes misspelled kubectl commands and plugins, e.g. `kubectl descrbe pods` -> `kubectl describe pods`
+pub struct KubectlUnknownCommand;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"unknown command "([^"]+)" for "kubectl"#).expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for KubectlUnknownCommand {
+    fn name(&self) -> &'static str {
+        "kubectl_unknown_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled kubectl commands and plugins, e.g. `kubectl descrbe pods` -> `kubectl describe pods`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        unknown_command(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let Some(broken) = unknown_command(&output) else {
+            return Vec::new();
+        };
+        let script = command.trimmed();
+
+        utils::suggested_fixes(
+            script,
+            &broken,
+            &utils::extract_suggestions(&output),
+            KUBECTL_COMMANDS,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "error: unknown command \"descrbe\" for \"kubectl\"\n\n\
+        Did you mean this?\n\tdescribe\n\nRun 'kubectl --help' for usage.\n";
+
+    const PLUGIN_OUTPUT: &str = "error: unknown command \"ctz\" for \"kubectl\"\n\n\
+        Did you mean this?\n\tctx\n\tcp\n\nRun 'kubectl --help' for usage.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("kubectl descrbe pods".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(KubectlUnknownCommand.is_match(&command, &result));
+
+        let command = Command::new("kubectl get pods".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "error: the server doesn't have a resource type \"podz\"\n".to_string(),
+        );
+        assert!(!KubectlUnknownCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("kubectl descrbe pods".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            KubectlUnknownCommand.get_new_commands(&command, &result),
+            vec!["kubectl describe pods"]
+        );
+
+        let command = Command::new("kubectl ctz staging".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, PLUGIN_OUTPUT.to_string());
+        let fixes = KubectlUnknownCommand.get_new_commands(&command, &result);
+        assert_eq!(fixes[0], "kubectl ctx staging");
+        assert_eq!(fixes[1], "kubectl cp staging");
+    }
+}
@@ -0,0 +1,162 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils::{self, CacheKey};
+use regex::Regex;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// How long the resource types a cluster serves are reused, as CRDs come and go
+const RESOURCES_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Resource types every cluster serves, used when `kubectl api-resources` is unavailable
+#[allow(clippy::type_complexity)]
+const DEFAULT_RESOURCES: &[&str] = &[
+    "configmaps",
+    "cronjobs",
+    "daemonsets",
+    "deployments",
+    "endpoints",
+    "events",
+    "horizontalpodautoscalers",
+    "ingresses",
+    "jobs",
+    "namespaces",
+    "networkpolicies",
+    "nodes",
+    "persistentvolumeclaims",
+    "persistentvolumes",
+    "pods",
+    "replicasets",
+    "roles",
+    "rolebindings",
+    "secrets",
+    "serviceaccounts",
+    "services",
+    "statefulsets",
+    "storageclasses",
+];
+
+/// Fixes misspelled kubectl resource types, e.g. `kubectl get podz` -> `kubectl get pods`
+pub struct KubectlUnknownResource;
+
+#[allow(clippy::type_complexity)]
+fn unknown_resource(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"doesn't have a resource type "([^"]+)""#).expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses `kubectl api-resources -o name`, dropping the API group of each resource
+#[allow(clippy::type_complexity)]
+fn parse_api_resources(output: &str) -> Vec<String> {
+    let mut resources: Vec<String> = Vec::new();
+    for name in output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split('.').next().unwrap_or(line))
+    {
+        if !resources.iter().any(|r| r == name) {
+            resources.push(name.to_string());
+        }
+    }
+    resources
+}
+
+/// Gets the kubeconfig files that pick the cluster, from `KUBECONFIG` or `~/.kube/config`
+#[allow(clippy::type_complexity)]
+fn kubeconfigs() -> Vec<PathBuf> {
+    match std::env::var_os("KUBECONFIG") {
+        Some(paths) => std::env::split_paths(&paths).collect(),
+        None => dirs::home_dir()
+            .map(|home| home.join(".kube").join("config"))
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Gets the resource types the cluster serves, reusing what kubectl listed within the hour
+#[allow(clippy::type_complexity)]
+fn resources() -> Vec<String> {
+    let key = kubeconfigs().iter().fold(
+        CacheKey::new("output of `kubectl api-resources -o name`").program("kubectl"),
+        |key, config| key.file(config),
+    );
+    let output = utils::cached(&key, RESOURCES_TTL, || {
+        utils::get_output("kubectl", &["api-resources", "-o", "name"])
+    });
+    let mut resources: Vec<String> = DEFAULT_RESOURCES.iter().map(|r| r.to_string()).collect();
+    for name in parse_api_resources(&output.unwrap_or_default()) {
+        if !resources.contains(&name) {
+            resources.push(name);
+        }
+    }
+    resources
+}
+
+impl Rule for KubectlUnknownResource {
+    fn name(&self) -> &'static str {
+        "kubectl_unknown_resource"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled kubectl resource types, e.g. `kubectl get podz` -> `kubectl get pods`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        unknown_resource(&result.output())
+            .is_some_and(|broken| utils::get_closest(&broken, &resources()).is_some())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match unknown_resource(&result.output()) {
+            Some(broken) => utils::replace_command(command.trimmed(), &broken, &resources()),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "error: the server doesn't have a resource type \"deploymnts\"\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("kubectl get deploymnts -n web".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(KubectlUnknownResource.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            1,
+            "Error from server (NotFound): pods \"web\" not found\n".to_string(),
+        );
+        assert!(!KubectlUnknownResource.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("kubectl get deploymnts -n web".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            KubectlUnknownResource.get_new_commands(&command, &result)[0],
+            "kubectl get deployments -n web"
+        );
+    }
+
+    #[test]
+    fn test_parse_api_resources() {
+        assert_eq!(
+            parse_api_resources("pods\ndeployments.apps\ningresses.networking.k8s.io\npods\n"),
+            vec!["pods", "deployments", "ingresses"]
+        );
+    }
+}
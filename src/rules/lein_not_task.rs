@@ -0,0 +1,117 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Tasks built into Leiningen, used on top of the ones lein suggests
+#[allow(clippy::type_complexity)]
+const LEIN_TASKS: &[&str] = &[
+    "change",
+    "check",
+    "classpath",
+    "clean",
+    "compile",
+    "deploy",
+    "deps",
+    "do",
+    "help",
+    "install",
+    "jar",
+    "javac",
+    "new",
+    "pom",
+    "release",
+    "repl",
+    "retest",
+    "run",
+    "search",
+    "show-profiles",
+    "test",
+    "trampoline",
+    "uberjar",
+    "update-in",
+    "upgrade",
+    "vcs",
+    "version",
+    "with-profile",
+];
+
+/// Fixes misspelled lein tasks, e.g. `lein rpl` -> `lein repl`
+pub struct LeinNotTask;
+
+#[allow(clippy::type_complexity)]
+fn broken_task(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"'([^']+)' is not a task").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for LeinNotTask {
+    fn name(&self) -> &'static str {
+        "lein_not_task"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled lein tasks, e.g. `lein rpl` -> `lein repl`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        broken_task(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let Some(broken) = broken_task(&output) else {
+            return Vec::new();
+        };
+        let script = command.trimmed();
+
+        utils::suggested_fixes(
+            script,
+            &broken,
+            &utils::extract_suggestions(&output),
+            LEIN_TASKS,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "'rpl' is not a task. See 'lein help'.\n\n\
+        Did you mean this?\n         repl\n         jar\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("lein rpl".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(LeinNotTask.is_match(&command, &result));
+
+        let command = Command::new("lein repl".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "Could not find artifact\n".to_string());
+        assert!(!LeinNotTask.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("lein rpl :headless".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        let fixes = LeinNotTask.get_new_commands(&command, &result);
+        assert_eq!(fixes[0], "lein repl :headless");
+        assert_eq!(fixes[1], "lein jar :headless");
+
+        let command = Command::new("lein uberjr".to_string(), Shell::Bash);
+        let result =
+            CommandResult::failure(1, "'uberjr' is not a task. See 'lein help'.\n".to_string());
+        assert_eq!(
+            LeinNotTask.get_new_commands(&command, &result)[0],
+            "lein uberjar"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+
+/// Links directories symbolically, e.g. `ln dir link` -> `ln -s dir link`
+pub struct LnNoHardLink;
+
+/// Inserts `-s` after `ln`
+#[allow(clippy::type_complexity)]
+fn add_symbolic_flag(script: &str) -> Option<String> {
+    let arguments = script.strip_prefix("ln")?;
+    (arguments.is_empty() || arguments.starts_with(char::is_whitespace))
+        .then(|| format!("ln -s{arguments}"))
+}
+
+impl Rule for LnNoHardLink {
+    fn name(&self) -> &'static str {
+        "ln_no_hard_link"
+    }
+
+    fn description(&self) -> &'static str {
+        "Links directories symbolically, e.g. `ln dir link` -> `ln -s dir link`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        result
+            .output()
+            .contains("hard link not allowed for directory")
+            && add_symbolic_flag(command.trimmed()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        add_symbolic_flag(command.trimmed()).into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::sudo_support;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "ln: ‘src’: hard link not allowed for directory\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("ln src dst".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(LnNoHardLink.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "ln: failed to create hard link\n".to_string());
+        assert!(!LnNoHardLink.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        for (script, expected) in [
+            ("ln src dst", "ln -s src dst"),
+            ("sudo ln -f src /opt/dst", "sudo ln -s -f src /opt/dst"),
+        ] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            assert_eq!(
+                sudo_support(LnNoHardLink).get_new_commands(&command, &result),
+                vec![expected]
+            );
+        }
+    }
+}
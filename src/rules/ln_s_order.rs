@@ -0,0 +1,107 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use std::path::Path;
+
+/// Swaps reversed symlink arguments, e.g. `ln -s link file.txt` -> `ln -s file.txt link`
+pub struct LnSOrder;
+
+/// Gets the words of a symbolic `ln` invocation
+#[allow(clippy::type_complexity)]
+fn ln_words(command: &Command) -> Option<Vec<utils::Word>> {
+    let words = utils::script_words(command.trimmed());
+    let symbolic = words
+        .iter()
+        .skip(1)
+        .any(|word| word.value == "-s" || word.value == "--symbolic");
+    symbolic.then_some(words)
+}
+
+/// Gets the indices of the target and link name when the link name is the one that exists
+#[allow(clippy::type_complexity)]
+fn reversed_paths(words: &[utils::Word], cwd: &str) -> Option<(usize, usize)> {
+    #[allow(clippy::type_complexity)]
+    let paths: Vec<usize> = (1..words.len())
+        .filter(|index| !words[*index].value.starts_with('-'))
+        .collect();
+    match paths[..] {
+        [target, link] if Path::new(cwd).join(&words[link].value).exists() => Some((target, link)),
+        _ => None,
+    }
+}
+
+impl Rule for LnSOrder {
+    fn name(&self) -> &'static str {
+        "ln_s_order"
+    }
+
+    fn description(&self) -> &'static str {
+        "Swaps reversed symlink arguments, e.g. `ln -s link file.txt` -> `ln -s file.txt link`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        result.output().contains("File exists")
+            && ln_words(command).is_some_and(|words| reversed_paths(&words, &command.cwd).is_some())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let Some(words) = ln_words(command) else {
+            return Vec::new();
+        };
+        let Some((target, link)) = reversed_paths(&words, &command.cwd) else {
+            return Vec::new();
+        };
+        let script = command.trimmed();
+        let (target, link) = (&words[target], &words[link]);
+        vec![utils::rewrite_words(script, &[target, link], |word| {
+            let other = if word.start == target.start {
+                link
+            } else {
+                target
+            };
+            script[other.start..other.end].to_string()
+        })]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::sudo_support;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "ln: failed to create symbolic link 'source.txt': File exists\n";
+
+    #[allow(clippy::type_complexity)]
+    fn ln(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("source.txt"), "").unwrap();
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(LnSOrder.is_match(&ln("ln -s link source.txt", &dir), &result));
+        assert!(!LnSOrder.is_match(&ln("ln -s source.txt link", &dir), &result));
+        assert!(!LnSOrder.is_match(&ln("ln link source.txt", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("source.txt"), "").unwrap();
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            sudo_support(LnSOrder)
+                .get_new_commands(&ln("sudo ln -s -f 'my link' source.txt", &dir), &result),
+            vec!["sudo ln -s -f source.txt 'my link'"]
+        );
+        assert_eq!(
+            LnSOrder.get_new_commands(&ln("ln -s ~/link \"source.txt\"", &dir), &result),
+            vec!["ln -s \"source.txt\" ~/link"]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Names GNU make looks for, in order
+#[allow(clippy::type_complexity)]
+const MAKEFILE_NAMES: &[&str] = &["GNUmakefile", "makefile", "Makefile"];
+
+/// Fixes misspelled make targets, e.g. `make biuld` -> `make build`
+pub struct MakeTarget;
+
+#[allow(clippy::type_complexity)]
+fn missing_target(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"No rule to make target [`'‘]([^'’]+)['’]").expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Parses the targets of a Makefile, skipping pattern rules, special targets and variables
+#[allow(clippy::type_complexity)]
+fn parse_targets(makefile: &str) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for line in makefile.lines() {
+        if line.starts_with(['\t', ' ', '#']) {
+            continue;
+        }
+        let Some((names, rest)) = line.split_once(':') else {
+            continue;
+        };
+        if rest.starts_with('=') || names.contains('=') || names.contains('$') {
+            continue;
+        }
+        let names: Vec<&str> = if names.trim() == ".PHONY" {
+            rest.split_whitespace().collect()
+        } else {
+            names.split_whitespace().collect()
+        };
+        for name in names {
+            if !name.starts_with('.') && !name.contains('%') && !targets.iter().any(|t| t == name) {
+                targets.push(name.to_string());
+            }
+        }
+    }
+    targets
+}
+
+/// Gets the targets of the Makefile make would read in the directory
+#[allow(clippy::type_complexity)]
+fn makefile_targets(dir: &str) -> Vec<String> {
+    MAKEFILE_NAMES
+        .iter()
+        .find_map(|name| std::fs::read_to_string(Path::new(dir).join(name)).ok())
+        .map(|makefile| parse_targets(&makefile))
+        .unwrap_or_default()
+}
+
+impl Rule for MakeTarget {
+    fn name(&self) -> &'static str {
+        "make_target"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled make targets, e.g. `make biuld` -> `make build`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_target(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match missing_target(&result.output()) {
+            Some(target) => {
+                utils::replace_command(command.trimmed(), &target, &makefile_targets(&command.cwd))
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const MAKEFILE: &str = "CC := gcc\nOUT=app\n.PHONY: build test lint\n\n\
+        build: main.o\n\t$(CC) -o $(OUT) main.o\n\n%.o: %.c\n\t$(CC) -c $<\n\n\
+        test install: build\n\t./run-tests\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("make biuld".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            2,
+            "make: *** No rule to make target 'biuld'.  Stop.\n".to_string(),
+        );
+        assert!(MakeTarget.is_match(&command, &result));
+
+        let result = CommandResult::failure(2, "make: *** [build] Error 1\n".to_string());
+        assert!(!MakeTarget.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        assert_eq!(
+            parse_targets(MAKEFILE),
+            vec!["build", "test", "lint", "install"]
+        );
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Makefile"), MAKEFILE).unwrap();
+        let command = Command::new("make -j4 biuld".to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string());
+        let result = CommandResult::failure(
+            2,
+            "make: *** No rule to make target `biuld'.  Stop.\n".to_string(),
+        );
+        assert_eq!(
+            MakeTarget.get_new_commands(&command, &result),
+            vec!["make -j4 build"]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes unknown or ambiguous hg commands, e.g. `hg base` -> `hg rebase`
+pub struct Mercurial;
+
+/// Gets the command hg didn't know or couldn't pick, e.g. from `hg: unknown command 'base'`
+#[allow(clippy::type_complexity)]
+fn broken_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"hg: (?:unknown command '([^']+)'|command '([^']+)' is ambiguous)")
+            .expect("valid regex")
+    });
+    let captures = regex.captures(output)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(2))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Gets the candidates from `(did you mean one of a, b?)` or the list after "is ambiguous:"
+#[allow(clippy::type_complexity)]
+fn candidates(output: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"\(did you mean(?: one of)? ([^?)]+)\?\)").expect("valid regex")
+    });
+    if let Some(captures) = regex.captures(output) {
+        return captures[1]
+            .split(',')
+            .map(|candidate| candidate.trim().to_string())
+            .filter(|candidate| !candidate.is_empty())
+            .collect();
+    }
+    output
+        .lines()
+        .skip_while(|line| !line.contains("is ambiguous:"))
+        .nth(1)
+        .map(|line| line.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+impl Rule for Mercurial {
+    fn name(&self) -> &'static str {
+        "mercurial"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes unknown or ambiguous hg commands, e.g. `hg base` -> `hg rebase`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        broken_command(&output).is_some() && !candidates(&output).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let Some(broken) = broken_command(&output) else {
+            return Vec::new();
+        };
+        let mut candidates = candidates(&output);
+        candidates.sort_by(|a, b| {
+            utils::similarity(&broken, b).total_cmp(&utils::similarity(&broken, a))
+        });
+        candidates
+            .iter()
+            .map(|fixed| command.replace_argument(&broken, fixed))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const UNKNOWN_OUTPUT: &str =
+        "hg: unknown command 'base'\n(did you mean one of blame, phase, rebase?)\n";
+
+    const AMBIGUOUS_OUTPUT: &str =
+        "hg: command 're' is ambiguous:\n    rebase recover remove rename resolve revert\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("hg base".to_string(), Shell::Bash);
+        let result = CommandResult::failure(255, UNKNOWN_OUTPUT.to_string());
+        assert!(Mercurial.is_match(&command, &result));
+
+        let command = Command::new("hg re".to_string(), Shell::Bash);
+        let result = CommandResult::failure(255, AMBIGUOUS_OUTPUT.to_string());
+        assert!(Mercurial.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            255,
+            "abort: no repository found in '/tmp' (.hg not found)!\n".to_string(),
+        );
+        assert!(!Mercurial.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("hg base -s 12".to_string(), Shell::Bash);
+        let result = CommandResult::failure(255, UNKNOWN_OUTPUT.to_string());
+        assert_eq!(
+            Mercurial.get_new_commands(&command, &result),
+            vec!["hg rebase -s 12", "hg blame -s 12", "hg phase -s 12"]
+        );
+
+        let command = Command::new("hg re".to_string(), Shell::Bash);
+        let result = CommandResult::failure(255, AMBIGUOUS_OUTPUT.to_string());
+        assert_eq!(Mercurial.get_new_commands(&command, &result).len(), 6);
+    }
+
+    #[test]
+    fn test_candidates() {
+        assert_eq!(
+            candidates("hg: unknown command 'brnch'\n(did you mean branch?)\n"),
+            vec!["branch"]
+        );
+    }
+}
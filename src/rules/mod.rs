@@ -1 +1,713 @@
+use crate::config::Settings;
+use crate::types::{Command, CommandResult, CorrectedCommand, Explanation};
+use crate::utils::{lookups, profile};
+use serde::Serialize;
+use std::time::Instant;
 
+pub(crate) use combinators::GIT_PROGRAMS;
+pub use combinators::{
+    ForApp, GitSupport, SudoSupport, for_app, git_support, is_app, sudo_support,
+};
+
+pub mod adb_unknown_command;
+pub mod apk;
+pub mod az_cli;
+pub mod brew_cask_dependency;
+pub mod brew_link;
+pub mod brew_uninstall;
+pub mod brew_unknown_command;
+pub mod bun;
+pub mod cargo_install_missing_subcommand;
+pub mod cargo_no_command;
+pub mod choco_no_command;
+pub mod combinators;
+pub mod composer_not_command;
+pub mod conda_mistype;
+pub mod deno;
+pub mod django_south_ghost;
+pub mod django_south_merge;
+pub mod dnf_no_such_command;
+pub mod docker_compose_v2;
+pub mod docker_login;
+pub mod dotnet;
+pub mod fab_command_not_found;
+pub mod flutter;
+pub mod gem_unknown_command;
+pub mod go_run;
+pub mod go_unknown_command;
+pub mod gradle_no_task;
+pub mod gradle_wrapper;
+pub mod grep_arguments_order;
+pub mod grunt_task_not_found;
+pub mod gulp_not_task;
+pub mod has_exists_script;
+pub mod helm;
+pub mod heroku_multiple_apps;
+pub mod heroku_not_command;
+pub mod java;
+pub mod javac;
+pub mod just_recipe;
+pub mod kubectl_unknown_command;
+pub mod kubectl_unknown_resource;
+pub mod lein_not_task;
+pub mod ln_no_hard_link;
+pub mod ln_s_order;
+pub mod make_target;
+pub mod mercurial;
+pub mod mvn_no_command;
+pub mod mvn_unknown_lifecycle_phase;
+pub mod nixos_cmd_not_found;
+pub mod npm_missing_script;
+pub mod npm_wrong_command;
+pub mod nvm;
+pub mod pacman;
+pub mod pacman_invalid_option;
+pub mod php_s;
+pub mod pip_install;
+pub mod pip_unknown_command;
+pub mod pnpm_missing_script;
+pub mod pnpm_run_script;
+pub mod pnpm_wrong_command;
+pub mod prove_recursively;
+pub mod pyenv_no_such_command;
+pub mod python_execute;
+pub mod rails_migrations_pending;
+pub mod rbenv_no_such_command;
+pub mod rbenv_version_not_installed;
+pub mod sed_unterminated_s;
+pub mod snap_install;
+pub mod terraform_no_command;
+pub mod tsuru_login;
+pub mod tsuru_not_command;
+pub mod vagrant_up;
+pub mod windows_command_not_found;
+pub mod winget_no_command;
+pub mod wrong_hyphen_before_subcommand;
+pub mod yarn_alias;
+pub mod yarn_command_not_found;
+pub mod yarn_help;
+pub mod zypper;
+
+/// Priority used by rules that don't override it
+pub const DEFAULT_PRIORITY: u32 = 1000;
+
+/// A rule that recognizes a failed command and suggests corrections for it
+pub trait Rule: Send + Sync {
+    /// Gets the unique name of the rule
+    fn name(&self) -> &'static str;
+
+    /// Gets what the rule corrects, with an example when it helps
+    fn description(&self) -> &'static str {
+        ""
+    }
+
+    /// Gets the priority of the rule's corrections (higher = more likely to be correct)
+    fn priority(&self) -> u32 {
+        DEFAULT_PRIORITY
+    }
+
+    /// Gets the programs the rule targets, empty if it can match any command
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        &[]
+    }
+
+    /// Checks if the rule is enabled when the user hasn't configured it explicitly
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// Checks if the rule applies to the failed command
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool;
+
+    /// Gets the corrected command texts, most likely first
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String>;
+
+    /// Gets the part of the output that made the rule match, if the rule knows it
+    #[allow(clippy::type_complexity)]
+    fn matched_pattern(&self, _command: &Command, _result: &CommandResult) -> Option<String> {
+        None
+    }
+
+    /// Gets the corrections, decreasing the priority of each subsequent suggestion
+    #[allow(clippy::type_complexity)]
+    fn get_corrections(&self, command: &Command, result: &CommandResult) -> Vec<CorrectedCommand> {
+        self.get_new_commands(command, result)
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let priority = self.priority().saturating_sub(index as u32);
+                CorrectedCommand::new(text, command.clone(), priority).with_rule(self.name())
+            })
+            .collect()
+    }
+}
+
+/// Checks if a rule is enabled by default and not excluded by the settings
+pub fn is_enabled(rule: &dyn Rule, settings: &Settings) -> bool {
+    rule.enabled_by_default()
+        && !settings
+            .exclude_rules
+            .iter()
+            .any(|name| name == rule.name())
+}
+
+/// Description of a rule, as listed to the user
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuleInfo {
+    /// Name of the rule
+    pub name: String,
+    /// What the rule corrects
+    pub description: String,
+    /// Whether the rule is used to correct commands
+    pub enabled: bool,
+    /// Priority of the rule's corrections
+    pub priority: u32,
+    /// Programs the rule targets, empty if it can match any command
+    #[allow(clippy::type_complexity)]
+    pub programs: Vec<String>,
+}
+
+impl RuleInfo {
+    /// Describes a rule, configured by the settings
+    pub fn new(rule: &dyn Rule, settings: &Settings) -> Self {
+        Self {
+            name: rule.name().to_string(),
+            description: rule.description().to_string(),
+            enabled: is_enabled(rule, settings),
+            priority: rule.priority(),
+            programs: rule.programs().iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+/// Boosts the corrections a user is known to prefer, e.g. from their previous picks
+pub trait Ranking: Send {
+    /// Gets the priority boost of a correction
+    fn boost(&self, correction: &CorrectedCommand) -> u32;
+}
+
+/// Registry of the rules used to correct commands
+#[derive(Default)]
+pub struct RuleRegistry {
+    #[allow(clippy::type_complexity)]
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl RuleRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with all built-in rules that are enabled by default
+    pub fn with_builtin_rules() -> Self {
+        Self::from_settings(&Settings::default())
+    }
+
+    /// Creates a registry with the enabled built-in rules, configured by the settings
+    pub fn from_settings(settings: &Settings) -> Self {
+        let mut registry = Self::new();
+        for rule in builtin_rules(settings) {
+            if is_enabled(rule.as_ref(), settings) {
+                registry.register(rule);
+            }
+        }
+        registry
+    }
+
+    /// Registers a rule
+    #[allow(clippy::type_complexity)]
+    pub fn register(&mut self, rule: Box<dyn Rule>) {
+        self.rules.push(rule);
+    }
+
+    /// Registers the declarative TOML rules in a directory, returning how many were loaded
+    #[allow(clippy::type_complexity)]
+    pub fn register_declarative_rules(&mut self, dir: &std::path::Path) -> usize {
+        let rules = crate::declarative::DeclarativeRule::load_dir(dir);
+        let count = rules.len();
+        for rule in rules {
+            // Rules for git see the aliases git expanded, see `git_support`
+            if rule
+                .programs()
+                .iter()
+                .any(|program| GIT_PROGRAMS.contains(program))
+            {
+                self.register(Box::new(git_support(rule)));
+            } else {
+                self.register(Box::new(rule));
+            }
+        }
+        count
+    }
+
+    /// Registers the WASM plugins in a directory, returning how many were loaded
+    #[cfg(feature = "wasm-plugins")]
+    #[allow(clippy::type_complexity)]
+    pub fn register_plugins(&mut self, dir: &std::path::Path) -> crate::TheFuckResult<usize> {
+        let plugins = crate::plugins::PluginLoader::new()?.load_dir(dir);
+        let count = plugins.len();
+        for plugin in plugins {
+            self.register(Box::new(plugin));
+        }
+        Ok(count)
+    }
+
+    /// Registers the Rhai rule scripts in a directory, returning how many were loaded
+    #[cfg(feature = "rhai-rules")]
+    #[allow(clippy::type_complexity)]
+    pub fn register_scripts(&mut self, dir: &std::path::Path) -> usize {
+        let scripts = crate::scripting::ScriptLoader::new().load_dir(dir);
+        let count = scripts.len();
+        for script in scripts {
+            self.register(Box::new(script));
+        }
+        count
+    }
+
+    /// Registers the legacy Python rules in a directory, returning how many were found
+    #[cfg(feature = "python-rules")]
+    #[allow(clippy::type_complexity)]
+    pub fn register_python_rules(&mut self, dir: &std::path::Path) -> usize {
+        let Some(python) = crate::legacy::find_python() else {
+            return 0;
+        };
+        let rules = crate::legacy::PythonRule::load_dir(dir, &python);
+        let count = rules.len();
+        for rule in rules {
+            self.register(Box::new(rule));
+        }
+        count
+    }
+
+    /// Unregisters the rules with the given names
+    #[allow(clippy::type_complexity)]
+    pub fn exclude(&mut self, names: &[String]) {
+        self.rules
+            .retain(|rule| !names.iter().any(|name| name == rule.name()));
+    }
+
+    /// Gets all registered rules
+    #[allow(clippy::type_complexity)]
+    pub fn rules(&self) -> &[Box<dyn Rule>] {
+        &self.rules
+    }
+
+    /// Gets a registered rule by name
+    #[allow(clippy::type_complexity)]
+    pub fn get(&self, name: &str) -> Option<&dyn Rule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.name() == name)
+            .map(|rule| rule.as_ref())
+    }
+
+    /// Gets the number of registered rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Checks if no rules are registered
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Gets the corrections of all matching rules, highest priority first and without duplicates
+    #[allow(clippy::type_complexity)]
+    pub fn get_corrections(
+        &self,
+        command: &Command,
+        result: &CommandResult,
+    ) -> Vec<CorrectedCommand> {
+        rank(self.matching_corrections(command, result))
+    }
+
+    /// Gets the corrections of all matching rules, boosted by the user's previous picks
+    #[allow(clippy::type_complexity)]
+    pub fn get_ranked_corrections(
+        &self,
+        command: &Command,
+        result: &CommandResult,
+        ranking: &dyn Ranking,
+    ) -> Vec<CorrectedCommand> {
+        let mut corrections = self.matching_corrections(command, result);
+        for correction in &mut corrections {
+            correction.priority = correction
+                .priority
+                .saturating_add(ranking.boost(correction));
+        }
+        rank(corrections)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn matching_corrections(
+        &self,
+        command: &Command,
+        result: &CommandResult,
+    ) -> Vec<CorrectedCommand> {
+        self.rules
+            .iter()
+            .flat_map(|rule| {
+                let (corrections, lookups) = lookups::track(|| {
+                    let started = Instant::now();
+                    let matched = rule.is_match(command, result);
+                    let matching = started.elapsed();
+                    let started = Instant::now();
+                    let corrections = if matched {
+                        rule.get_corrections(command, result)
+                    } else {
+                        Vec::new()
+                    };
+                    profile::record(rule.name(), matching, started.elapsed());
+                    if matched {
+                        tracing::debug!(
+                            "rule {} matched: {:?}",
+                            rule.name(),
+                            corrections
+                                .iter()
+                                .map(|correction| correction.text.as_str())
+                                .collect::<Vec<&str>>()
+                        );
+                    } else {
+                        tracing::debug!("rule {} didn't match", rule.name());
+                    }
+                    corrections
+                });
+                if corrections.is_empty() {
+                    return corrections;
+                }
+                let explanation = Explanation {
+                    rule: rule.name().to_string(),
+                    pattern: rule.matched_pattern(command, result),
+                    lookups,
+                };
+                corrections
+                    .into_iter()
+                    .map(|correction| correction.with_explanation(explanation.clone()))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Sorts corrections by priority, highest first, keeping the first of each duplicate
+#[allow(clippy::type_complexity)]
+fn rank(mut corrections: Vec<CorrectedCommand>) -> Vec<CorrectedCommand> {
+    corrections.sort_by_key(|correction| std::cmp::Reverse(correction.priority));
+
+    let mut unique: Vec<CorrectedCommand> = Vec::with_capacity(corrections.len());
+    for correction in corrections {
+        if !unique.iter().any(|c| c.text == correction.text) {
+            unique.push(correction);
+        }
+    }
+    unique
+}
+
+/// Gets every built-in rule, configured by the settings
+#[allow(clippy::type_complexity)]
+pub fn builtin_rules(settings: &Settings) -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(for_app(&["adb"], adb_unknown_command::AdbUnknownCommand)),
+        Box::new(sudo_support(apk::Apk)),
+        Box::new(for_app(&["az"], az_cli::AzCli)),
+        Box::new(for_app(&["brew"], brew_cask_dependency::BrewCaskDependency)),
+        Box::new(for_app(&["brew"], brew_link::BrewLink)),
+        Box::new(for_app(&["brew"], brew_uninstall::BrewUninstall)),
+        Box::new(for_app(&["brew"], brew_unknown_command::BrewUnknownCommand)),
+        Box::new(for_app(&["bun"], bun::Bun)),
+        Box::new(for_app(&["npm"], bun::BunRunScript)),
+        Box::new(for_app(
+            &["cargo"],
+            cargo_install_missing_subcommand::CargoInstallMissingSubcommand::new(settings),
+        )),
+        Box::new(for_app(&["cargo"], cargo_no_command::CargoNoCommand)),
+        Box::new(for_app(
+            &["choco", "choco.exe"],
+            choco_no_command::ChocoNoCommand,
+        )),
+        Box::new(for_app(
+            &["composer"],
+            composer_not_command::ComposerNotCommand,
+        )),
+        Box::new(for_app(&["conda"], conda_mistype::CondaMistype)),
+        Box::new(for_app(&["deno"], deno::Deno)),
+        Box::new(django_south_ghost::DjangoSouthGhost),
+        Box::new(django_south_merge::DjangoSouthMerge),
+        Box::new(sudo_support(for_app(
+            &["dnf"],
+            dnf_no_such_command::DnfNoSuchCommand,
+        ))),
+        Box::new(sudo_support(for_app(
+            &["yum"],
+            dnf_no_such_command::YumNoSuchCommand,
+        ))),
+        Box::new(sudo_support(for_app(
+            &["docker", "docker-compose"],
+            docker_compose_v2::DockerComposeV2,
+        ))),
+        Box::new(for_app(&["docker"], docker_login::DockerLogin)),
+        Box::new(for_app(&["dotnet"], dotnet::Dotnet)),
+        Box::new(for_app(&["fab"], fab_command_not_found::FabCommandNotFound)),
+        Box::new(for_app(&["flutter"], flutter::Flutter)),
+        Box::new(for_app(&["gem"], gem_unknown_command::GemUnknownCommand)),
+        Box::new(for_app(&["go"], go_run::GoRun)),
+        Box::new(for_app(&["go"], go_unknown_command::GoUnknownCommand)),
+        Box::new(for_app(
+            &["gradle", "gradlew", "./gradlew"],
+            gradle_no_task::GradleNoTask,
+        )),
+        Box::new(for_app(&["gradle"], gradle_wrapper::GradleWrapper)),
+        Box::new(for_app(
+            &["grep", "egrep", "fgrep", "rg"],
+            grep_arguments_order::GrepArgumentsOrder,
+        )),
+        Box::new(for_app(&["grunt"], grunt_task_not_found::GruntTaskNotFound)),
+        Box::new(for_app(&["gulp"], gulp_not_task::GulpNotTask)),
+        Box::new(has_exists_script::HasExistsScript),
+        Box::new(for_app(&["helm"], helm::Helm)),
+        Box::new(for_app(
+            &["heroku"],
+            heroku_multiple_apps::HerokuMultipleApps,
+        )),
+        Box::new(for_app(&["heroku"], heroku_not_command::HerokuNotCommand)),
+        Box::new(for_app(&["java"], java::Java)),
+        Box::new(for_app(&["javac"], javac::Javac)),
+        Box::new(for_app(&["just"], just_recipe::JustRecipe)),
+        Box::new(for_app(
+            &["kubectl"],
+            kubectl_unknown_command::KubectlUnknownCommand,
+        )),
+        Box::new(for_app(
+            &["kubectl"],
+            kubectl_unknown_resource::KubectlUnknownResource,
+        )),
+        Box::new(for_app(&["lein"], lein_not_task::LeinNotTask)),
+        Box::new(sudo_support(for_app(
+            &["ln"],
+            ln_no_hard_link::LnNoHardLink,
+        ))),
+        Box::new(sudo_support(for_app(&["ln"], ln_s_order::LnSOrder))),
+        Box::new(for_app(&["make"], make_target::MakeTarget)),
+        Box::new(for_app(&["hg"], mercurial::Mercurial)),
+        Box::new(for_app(&["mvn"], mvn_no_command::MvnNoCommand)),
+        Box::new(for_app(
+            &["mvn"],
+            mvn_unknown_lifecycle_phase::MvnUnknownLifecyclePhase,
+        )),
+        Box::new(nixos_cmd_not_found::NixosCmdNotFound),
+        Box::new(for_app(&["npm"], npm_missing_script::NpmMissingScript)),
+        Box::new(for_app(&["npm"], npm_wrong_command::NpmWrongCommand)),
+        Box::new(for_app(&["nvm"], nvm::Nvm)),
+        Box::new(pacman::Pacman),
+        Box::new(sudo_support(for_app(
+            &["pacman", "yay", "paru", "pikaur"],
+            pacman_invalid_option::PacmanInvalidOption,
+        ))),
+        Box::new(for_app(&["php"], php_s::PhpS)),
+        Box::new(pip_install::PipInstall),
+        Box::new(sudo_support(for_app(
+            &["pip", "pip2", "pip3"],
+            pip_unknown_command::PipUnknownCommand,
+        ))),
+        Box::new(for_app(&["pnpm"], pnpm_missing_script::PnpmMissingScript)),
+        Box::new(for_app(&["pnpm"], pnpm_run_script::PnpmRunScript)),
+        Box::new(for_app(&["pnpm"], pnpm_wrong_command::PnpmWrongCommand)),
+        Box::new(for_app(&["prove"], prove_recursively::ProveRecursively)),
+        Box::new(for_app(
+            &["pyenv"],
+            pyenv_no_such_command::PyenvNoSuchCommand,
+        )),
+        Box::new(for_app(
+            &["python", "python2", "python3"],
+            python_execute::PythonExecute,
+        )),
+        Box::new(rails_migrations_pending::RailsMigrationsPending),
+        Box::new(for_app(
+            &["rbenv"],
+            rbenv_no_such_command::RbenvNoSuchCommand,
+        )),
+        Box::new(rbenv_version_not_installed::RbenvVersionNotInstalled),
+        Box::new(for_app(&["sed"], sed_unterminated_s::SedUnterminatedS)),
+        Box::new(snap_install::SnapInstall),
+        Box::new(for_app(
+            &["terraform"],
+            terraform_no_command::TerraformNoCommand,
+        )),
+        Box::new(for_app(&["tsuru"], tsuru_login::TsuruLogin)),
+        Box::new(for_app(&["tsuru"], tsuru_not_command::TsuruNotCommand)),
+        Box::new(for_app(&["vagrant"], vagrant_up::VagrantUp)),
+        Box::new(windows_command_not_found::WindowsCommandNotFound),
+        Box::new(for_app(&["winget"], winget_no_command::WingetNoCommand)),
+        Box::new(wrong_hyphen_before_subcommand::WrongHyphenBeforeSubcommand),
+        Box::new(for_app(&["yarn"], yarn_alias::YarnAlias)),
+        Box::new(for_app(
+            &["yarn"],
+            yarn_command_not_found::YarnCommandNotFound,
+        )),
+        Box::new(for_app(&["yarn"], yarn_help::YarnHelp)),
+        Box::new(sudo_support(zypper::Zypper)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    struct FixedRule {
+        name: &'static str,
+        priority: u32,
+        #[allow(clippy::type_complexity)]
+        fixes: Vec<&'static str>,
+    }
+
+    impl Rule for FixedRule {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn priority(&self) -> u32 {
+            self.priority
+        }
+
+        fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+            !result.success
+        }
+
+        fn get_new_commands(&self, _command: &Command, _result: &CommandResult) -> Vec<String> {
+            self.fixes.iter().map(|fix| fix.to_string()).collect()
+        }
+    }
+
+    struct LookupRule;
+
+    impl Rule for LookupRule {
+        fn name(&self) -> &'static str {
+            "lookup"
+        }
+
+        fn is_match(&self, _command: &Command, _result: &CommandResult) -> bool {
+            lookups::record(|| "ran `git branch`".to_string());
+            true
+        }
+
+        fn get_new_commands(&self, _command: &Command, _result: &CommandResult) -> Vec<String> {
+            vec!["git push".to_string()]
+        }
+    }
+
+    #[test]
+    fn test_registry_explains_corrections() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(LookupRule));
+        let command = Command::new("git psh".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, String::new());
+        let corrections = registry.get_corrections(&command, &result);
+        assert_eq!(
+            corrections[0].explanation,
+            Some(Explanation {
+                rule: "lookup".to_string(),
+                pattern: None,
+                lookups: vec!["ran `git branch`".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_registry_orders_and_dedups_corrections() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(FixedRule {
+            name: "low",
+            priority: 10,
+            fixes: vec!["git push", "git pull"],
+        }));
+        registry.register(Box::new(FixedRule {
+            name: "high",
+            priority: 100,
+            fixes: vec!["git push"],
+        }));
+
+        let command = Command::new("git psh".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, String::new());
+        let corrections = registry.get_corrections(&command, &result);
+
+        #[allow(clippy::type_complexity)]
+        let texts: Vec<&str> = corrections.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["git push", "git pull"]);
+        assert_eq!(corrections[0].priority, 100);
+        assert_eq!(corrections[1].priority, 9);
+    }
+
+    #[test]
+    fn test_registry_boosts_learned_corrections() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(FixedRule {
+            name: "fixed",
+            priority: 100,
+            fixes: vec!["git push", "git pull"],
+        }));
+        let command = Command::new("git psh".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, String::new());
+
+        let store = crate::learning::LearningStore::in_memory().unwrap();
+        let corrections = registry.get_ranked_corrections(&command, &result, &store);
+        assert_eq!(corrections[0].text, "git push");
+        assert_eq!(corrections[0].rule, "fixed");
+
+        store.record(&corrections[1]).unwrap();
+        let corrections = registry.get_ranked_corrections(&command, &result, &store);
+        assert_eq!(corrections[0].text, "git pull");
+        assert_eq!(corrections[1].text, "git push");
+    }
+
+    #[test]
+    fn test_registry_skips_unmatched_rules() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(FixedRule {
+            name: "fixed",
+            priority: DEFAULT_PRIORITY,
+            fixes: vec!["git push"],
+        }));
+
+        let command = Command::new("git push".to_string(), Shell::Bash);
+        let result = CommandResult::success(String::new());
+        assert!(registry.get_corrections(&command, &result).is_empty());
+        assert!(registry.get("fixed").is_some());
+        assert!(registry.get("missing").is_none());
+    }
+
+    #[test]
+    fn test_builtin_rules_have_unique_names() {
+        let rules = builtin_rules(&Settings::default());
+        for (index, rule) in rules.iter().enumerate() {
+            assert!(
+                rules[index + 1..]
+                    .iter()
+                    .all(|other| other.name() != rule.name())
+            );
+        }
+    }
+
+    #[test]
+    fn test_excluded_rules() {
+        let settings = Settings {
+            exclude_rules: vec!["sed_unterminated_s".to_string()],
+            ..Settings::default()
+        };
+        let mut registry = RuleRegistry::from_settings(&settings);
+        assert!(registry.get("sed_unterminated_s").is_none());
+        assert!(registry.get("make_target").is_some());
+
+        let rule = for_app(&["sed"], sed_unterminated_s::SedUnterminatedS);
+        let info = RuleInfo::new(&rule, &settings);
+        assert!(!info.enabled);
+        assert_eq!(info.programs, vec!["sed"]);
+        assert_eq!(info.priority, DEFAULT_PRIORITY);
+
+        registry.exclude(&["make_target".to_string()]);
+        assert!(registry.get("make_target").is_none());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+
+/// Adds the usual goals when maven is run without any, e.g. `mvn` -> `mvn clean package`
+pub struct MvnNoCommand;
+
+impl Rule for MvnNoCommand {
+    fn name(&self) -> &'static str {
+        "mvn_no_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds the usual goals when maven is run without any, e.g. `mvn` -> `mvn clean package`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        result
+            .output()
+            .contains("No goals have been specified for this build")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        vec![
+            format!("{script} clean package"),
+            format!("{script} clean install"),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "[ERROR] No goals have been specified for this build. You must specify \
+        a valid lifecycle phase or a goal in the format <plugin-prefix>:<goal>.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("mvn".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(MvnNoCommand.is_match(&command, &result));
+
+        let command = Command::new("mvn package".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "[ERROR] BUILD FAILURE\n".to_string());
+        assert!(!MvnNoCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("mvn -DskipTests".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            MvnNoCommand.get_new_commands(&command, &result),
+            vec![
+                "mvn -DskipTests clean package",
+                "mvn -DskipTests clean install"
+            ]
+        );
+    }
+}
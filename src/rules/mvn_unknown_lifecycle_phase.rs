@@ -0,0 +1,148 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Phases of maven's default, clean and site lifecycles
+#[allow(clippy::type_complexity)]
+const LIFECYCLE_PHASES: &[&str] = &[
+    "validate",
+    "initialize",
+    "generate-sources",
+    "process-sources",
+    "generate-resources",
+    "process-resources",
+    "compile",
+    "process-classes",
+    "generate-test-sources",
+    "process-test-sources",
+    "generate-test-resources",
+    "process-test-resources",
+    "test-compile",
+    "process-test-classes",
+    "test",
+    "prepare-package",
+    "package",
+    "pre-integration-test",
+    "integration-test",
+    "post-integration-test",
+    "verify",
+    "install",
+    "deploy",
+    "pre-clean",
+    "clean",
+    "post-clean",
+    "pre-site",
+    "site",
+    "post-site",
+    "site-deploy",
+];
+
+/// Fixes misspelled maven lifecycle phases, e.g. `mvn cmpile` -> `mvn compile`
+pub struct MvnUnknownLifecyclePhase;
+
+#[allow(clippy::type_complexity)]
+fn unknown_phase(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"Unknown lifecycle phase ["']([^"']+)["']"#).expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the phases maven lists after "Available lifecycle phases are:"
+#[allow(clippy::type_complexity)]
+fn available_phases(output: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"Available lifecycle phases are: ([^.>]+)").expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| {
+            captures[1]
+                .split(',')
+                .map(str::trim)
+                .filter(|phase| !phase.is_empty() && !phase.contains(' '))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Rule for MvnUnknownLifecyclePhase {
+    fn name(&self) -> &'static str {
+        "mvn_unknown_lifecycle_phase"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled maven lifecycle phases, e.g. `mvn cmpile` -> `mvn compile`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        unknown_phase(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let Some(phase) = unknown_phase(&output) else {
+            return Vec::new();
+        };
+        let mut phases = available_phases(&output);
+        if phases.is_empty() {
+            phases = LIFECYCLE_PHASES.iter().map(|p| p.to_string()).collect();
+        }
+        utils::replace_command(command.trimmed(), &phase, &phases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "[ERROR] Unknown lifecycle phase \"cmpile\". You must specify a valid \
+        lifecycle phase or a goal in the format <plugin-prefix>:<goal>. Available lifecycle \
+        phases are: validate, initialize, compile, test, package, verify, install, deploy, \
+        pre-clean, clean, post-clean. -> [Help 1]\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("mvn cmpile".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(MvnUnknownLifecyclePhase.is_match(&command, &result));
+
+        let command = Command::new("mvn compile".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "[ERROR] COMPILATION ERROR\n".to_string());
+        assert!(!MvnUnknownLifecyclePhase.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("mvn clean cmpile -q".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            MvnUnknownLifecyclePhase.get_new_commands(&command, &result)[0],
+            "mvn clean compile -q"
+        );
+
+        let command = Command::new("mvn pakage".to_string(), Shell::Bash);
+        let result =
+            CommandResult::failure(1, "[ERROR] Unknown lifecycle phase 'pakage'.\n".to_string());
+        assert_eq!(
+            MvnUnknownLifecyclePhase.get_new_commands(&command, &result)[0],
+            "mvn package"
+        );
+    }
+
+    #[test]
+    fn test_available_phases() {
+        let phases = available_phases(OUTPUT);
+        assert_eq!(phases.first().map(String::as_str), Some("validate"));
+        assert_eq!(phases.last().map(String::as_str), Some("post-clean"));
+    }
+}
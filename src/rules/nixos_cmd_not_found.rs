@@ -0,0 +1,108 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Installs the package NixOS' command-not-found handler suggests,
+/// e.g. `vim` -> `nix-env -iA nixos.vim && vim`
+pub struct NixosCmdNotFound;
+
+/// Gets the attribute path from `nix-env -iA nixos.vim`
+#[allow(clippy::type_complexity)]
+fn attribute_path(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"nix-env -iA (\S+)").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the package name, from the attribute path without its channel or from `nix-shell -p vim`
+#[allow(clippy::type_complexity)]
+fn package_name(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"nix-shell -p (\S+)").expect("valid regex"));
+    attribute_path(output)
+        .and_then(|path| path.split_once('.').map(|(_, name)| name.to_string()))
+        .or_else(|| {
+            regex
+                .captures(output)
+                .map(|captures| captures[1].to_string())
+        })
+}
+
+impl Rule for NixosCmdNotFound {
+    fn name(&self) -> &'static str {
+        "nixos_cmd_not_found"
+    }
+
+    fn description(&self) -> &'static str {
+        "Installs the package NixOS' command-not-found handler suggests, e.g. `vim` -> `nix-env -iA nixos.vim && vim`"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        utils::which("nix-env").is_some() || utils::which("nix").is_some()
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        package_name(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let script = command.trimmed();
+        let mut fixes: Vec<String> = Vec::new();
+        if let Some(path) = attribute_path(&output) {
+            fixes.push(format!("nix-env -iA {path} && {script}"));
+        }
+        if let Some(name) = package_name(&output) {
+            fixes.push(format!("nix shell nixpkgs#{name} --command {script}"));
+        }
+        fixes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "The program 'vim' is currently not installed. \
+        You can install it by typing:\n  nix-env -iA nixos.vim\n\n\
+        Or run it once with:\n  nix-shell -p vim\n";
+
+    const EPHEMERAL_OUTPUT: &str = "The program 'htop' is not in your PATH. \
+        You can make it available in an\nephemeral shell by typing:\n  nix-shell -p htop\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("vim file".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, OUTPUT.to_string());
+        assert!(NixosCmdNotFound.is_match(&command, &result));
+
+        let result = CommandResult::failure(127, "vim: command not found\n".to_string());
+        assert!(!NixosCmdNotFound.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("vim file".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, OUTPUT.to_string());
+        assert_eq!(
+            NixosCmdNotFound.get_new_commands(&command, &result),
+            vec![
+                "nix-env -iA nixos.vim && vim file",
+                "nix shell nixpkgs#vim --command vim file"
+            ]
+        );
+
+        let command = Command::new("htop".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, EPHEMERAL_OUTPUT.to_string());
+        assert_eq!(
+            NixosCmdNotFound.get_new_commands(&command, &result),
+            vec!["nix shell nixpkgs#htop --command htop"]
+        );
+    }
+}
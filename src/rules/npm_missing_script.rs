@@ -0,0 +1,88 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled package.json scripts, e.g. `npm run buil` -> `npm run build`
+pub struct NpmMissingScript;
+
+#[allow(clippy::type_complexity)]
+fn missing_script_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r#"(?i)missing script: "?([^"\s]+)"?"#).expect("valid regex"))
+}
+
+#[allow(clippy::type_complexity)]
+fn missing_script(result: &CommandResult) -> Option<String> {
+    missing_script_regex()
+        .captures(&result.output())
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for NpmMissingScript {
+    fn name(&self) -> &'static str {
+        "npm_missing_script"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled package.json scripts, e.g. `npm run buil` -> `npm run build`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        command.arguments().iter().any(|arg| arg.starts_with("ru"))
+            && missing_script(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match missing_script(result) {
+            Some(script) => utils::replace_command(
+                command.trimmed(),
+                &script,
+                &utils::package_json_scripts(&command.cwd),
+            ),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "npm ERR! Missing script: \"buil\"\nnpm ERR!\n\
+        npm ERR! To see a list of scripts, run:\nnpm ERR!   npm run\n";
+
+    #[test]
+    fn test_match() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        let command = Command::new("npm run buil".to_string(), Shell::Bash);
+        assert!(NpmMissingScript.is_match(&command, &result));
+        let command = Command::new("npm run-script buil".to_string(), Shell::Bash);
+        assert!(NpmMissingScript.is_match(&command, &result));
+
+        let command = Command::new("npm install".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "npm ERR! code E404\n".to_string());
+        assert!(!NpmMissingScript.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"build": "tsc", "lint": "eslint ."}}"#,
+        )
+        .unwrap();
+
+        let command = Command::new("npm run buil -- --watch".to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string());
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            NpmMissingScript.get_new_commands(&command, &result),
+            vec!["npm run build -- --watch"]
+        );
+    }
+}
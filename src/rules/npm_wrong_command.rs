@@ -0,0 +1,198 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Commands built into npm
+#[allow(clippy::type_complexity)]
+const NPM_COMMANDS: &[&str] = &[
+    "access",
+    "adduser",
+    "audit",
+    "bugs",
+    "cache",
+    "ci",
+    "completion",
+    "config",
+    "dedupe",
+    "deprecate",
+    "diff",
+    "dist-tag",
+    "docs",
+    "doctor",
+    "edit",
+    "exec",
+    "explain",
+    "explore",
+    "find-dupes",
+    "fund",
+    "help",
+    "help-search",
+    "init",
+    "install",
+    "install-ci-test",
+    "install-test",
+    "link",
+    "login",
+    "logout",
+    "ls",
+    "org",
+    "outdated",
+    "owner",
+    "pack",
+    "ping",
+    "pkg",
+    "prefix",
+    "profile",
+    "prune",
+    "publish",
+    "query",
+    "rebuild",
+    "repo",
+    "restart",
+    "root",
+    "run",
+    "run-script",
+    "sbom",
+    "search",
+    "shrinkwrap",
+    "star",
+    "stars",
+    "start",
+    "stop",
+    "team",
+    "test",
+    "token",
+    "uninstall",
+    "unpublish",
+    "unstar",
+    "update",
+    "version",
+    "view",
+    "whoami",
+];
+
+/// Fixes misspelled npm commands, e.g. `npm isntall` -> `npm install`
+pub struct NpmWrongCommand;
+
+/// Gets the first argument that isn't a flag, which npm treats as the command
+#[allow(clippy::type_complexity)]
+fn wrong_command(command: &Command) -> Option<&str> {
+    command
+        .arguments()
+        .into_iter()
+        .find(|arg| !arg.starts_with('-'))
+}
+
+/// Gets the commands npm suggests after "Did you mean this?"
+#[allow(clippy::type_complexity)]
+fn suggested_commands(output: &str) -> Vec<String> {
+    utils::extract_suggestions(output)
+        .iter()
+        .filter_map(|suggestion| suggestion.strip_prefix("npm "))
+        .filter_map(|suggestion| suggestion.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Gets the commands listed after "where <command> is one of:" by older npm versions
+#[allow(clippy::type_complexity)]
+fn listed_commands(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("where <command> is one of:"))
+        .skip(1)
+        .take_while(|line| !line.trim().is_empty())
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl Rule for NpmWrongCommand {
+    fn name(&self) -> &'static str {
+        "npm_wrong_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled npm commands, e.g. `npm isntall` -> `npm install`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        wrong_command(command).is_some()
+            && (output.contains("Unknown command") || output.contains("where <command> is one of:"))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(wrong) = wrong_command(command) else {
+            return Vec::new();
+        };
+        let output = result.output();
+        let mut known: Vec<String> = listed_commands(&output);
+        known.extend(NPM_COMMANDS.iter().map(|name| name.to_string()));
+        utils::suggested_fixes(
+            command.trimmed(),
+            wrong,
+            &suggested_commands(&output),
+            &known,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Unknown command: \"isntall\"\n\n\
+        Did you mean this?\n    npm install # Install a package\n\n\
+        To see a list of supported npm commands, run:\n  npm help\n";
+
+    const LEGACY_OUTPUT: &str = "\nUsage: npm <command>\n\n\
+        where <command> is one of:\n    access, add-user, adduser, bin, bugs, c, cache,\n    \
+        install, isntall, ls, run-script, uninstall, update\n\n\
+        npm <cmd> -h     quick help on <cmd>\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("npm isntall react".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(NpmWrongCommand.is_match(&command, &result));
+
+        let command = Command::new("npm urgrdae".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, LEGACY_OUTPUT.to_string());
+        assert!(NpmWrongCommand.is_match(&command, &result));
+
+        let command = Command::new("npm".to_string(), Shell::Bash);
+        assert!(!NpmWrongCommand.is_match(&command, &result));
+
+        let command = Command::new("npm install react".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "npm ERR! code E404\n".to_string());
+        assert!(!NpmWrongCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("npm isntall react".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            NpmWrongCommand.get_new_commands(&command, &result)[0],
+            "npm install react"
+        );
+
+        let command = Command::new("npm -g urgrdae".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, LEGACY_OUTPUT.to_string());
+        assert_eq!(
+            NpmWrongCommand.get_new_commands(&command, &result)[0],
+            "npm -g update"
+        );
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(suggested_commands(OUTPUT), vec!["install"]);
+        assert!(listed_commands(LEGACY_OUTPUT).contains(&"run-script".to_string()));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into nvm
+#[allow(clippy::type_complexity)]
+const NVM_COMMANDS: &[&str] = &[
+    "alias",
+    "cache",
+    "current",
+    "deactivate",
+    "exec",
+    "help",
+    "install",
+    "install-latest-npm",
+    "list",
+    "ls",
+    "ls-remote",
+    "reinstall-packages",
+    "run",
+    "unalias",
+    "uninstall",
+    "unload",
+    "use",
+    "version",
+    "version-remote",
+    "which",
+];
+
+/// Installs missing Node versions and fixes misspelled nvm commands,
+/// e.g. `nvm use 18` -> `nvm install 18 && nvm use 18`
+pub struct Nvm;
+
+/// Gets the install command nvm asks to run, e.g. from `You need to run "nvm install 18"`
+#[allow(clippy::type_complexity)]
+fn install_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"You need to run "?(nvm install [^"\n]+?)"? to install"#).expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].trim().to_string())
+}
+
+/// Gets the first argument when it isn't an nvm command
+#[allow(clippy::type_complexity)]
+fn unknown_command(command: &Command) -> Option<&str> {
+    command
+        .argument(0)
+        .filter(|name| !name.starts_with('-') && !NVM_COMMANDS.contains(name))
+}
+
+impl Rule for Nvm {
+    fn name(&self) -> &'static str {
+        "nvm"
+    }
+
+    fn description(&self) -> &'static str {
+        "Installs missing Node versions and fixes misspelled nvm commands, e.g. `nvm use 18` -> `nvm install 18 && nvm use 18`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        install_command(&output).is_some()
+            || (output.contains("Node Version Manager")
+                && unknown_command(command)
+                    .is_some_and(|name| utils::get_closest(name, NVM_COMMANDS).is_some()))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        if let Some(install) = install_command(&result.output()) {
+            return vec![format!("{install} && {script}")];
+        }
+        match unknown_command(command) {
+            Some(broken) => utils::replace_command(script, broken, NVM_COMMANDS),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const NOT_INSTALLED_OUTPUT: &str = "N/A: version \"18 -> N/A\" is not yet installed.\n\n\
+        You need to run \"nvm install 18\" to install it before using it.\n";
+
+    const USAGE_OUTPUT: &str = "\nNode Version Manager (v0.39.7)\n\n\
+        Note: <version> refers to any version-like string nvm understands.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("nvm use 18".to_string(), Shell::Bash);
+        let result = CommandResult::failure(3, NOT_INSTALLED_OUTPUT.to_string());
+        assert!(Nvm.is_match(&command, &result));
+
+        let command = Command::new("nvm isntall 20".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, USAGE_OUTPUT.to_string());
+        assert!(Nvm.is_match(&command, &result));
+
+        let command = Command::new("nvm use 18".to_string(), Shell::Bash);
+        assert!(!Nvm.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("nvm use 18".to_string(), Shell::Bash);
+        let result = CommandResult::failure(3, NOT_INSTALLED_OUTPUT.to_string());
+        assert_eq!(
+            Nvm.get_new_commands(&command, &result),
+            vec!["nvm install 18 && nvm use 18"]
+        );
+
+        let command = Command::new("nvm isntall 20".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, USAGE_OUTPUT.to_string());
+        assert_eq!(Nvm.get_new_commands(&command, &result)[0], "nvm install 20");
+    }
+}
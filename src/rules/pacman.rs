@@ -0,0 +1,116 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Installs the package providing a missing command on Arch Linux,
+/// e.g. `vim file` -> `sudo pacman -S extra/vim && vim file`
+pub struct Pacman;
+
+/// Gets the program of the script, skipping a leading `sudo`
+#[allow(clippy::type_complexity)]
+fn missing_program(script: &str) -> Option<&str> {
+    let mut words = script.split_whitespace();
+    match words.next() {
+        Some("sudo") => words.next(),
+        program => program,
+    }
+}
+
+/// Parses `pkgfile -b -v` output lines like `extra/vim 9.0.1-1  /usr/bin/vim`
+#[allow(clippy::type_complexity)]
+fn parse_pkgfile_output(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Gets the packages providing the program, from pkgfile or pacman's file database
+#[allow(clippy::type_complexity)]
+fn get_packages(program: &str) -> Vec<String> {
+    utils::get_output("pkgfile", &["-b", "-v", program])
+        .or_else(|| utils::get_output("pacman", &["-Fq", program]))
+        .map(|output| parse_pkgfile_output(&output))
+        .unwrap_or_default()
+}
+
+#[allow(clippy::type_complexity)]
+fn install_commands(script: &str, packages: &[String]) -> Vec<String> {
+    packages
+        .iter()
+        .map(|package| format!("sudo pacman -S {package} && {script}"))
+        .collect()
+}
+
+impl Rule for Pacman {
+    fn name(&self) -> &'static str {
+        "pacman"
+    }
+
+    fn description(&self) -> &'static str {
+        "Installs the package providing a missing command on Arch Linux, e.g. `vim file` -> `sudo pacman -S extra/vim && vim file`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        result.output().contains("not found")
+            && utils::is_available("pacman", command)
+            && missing_program(command.trimmed()).is_some_and(|p| !get_packages(p).is_empty())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        match missing_program(command.trimmed()) {
+            Some(program) => install_commands(command.trimmed(), &get_packages(program)),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_match() {
+        let empty = tempfile::tempdir().unwrap();
+        let command =
+            Command::new("vim file".to_string(), Shell::Bash).with_env(HashMap::from([(
+                "PATH".to_string(),
+                empty.path().to_string_lossy().to_string(),
+            )]));
+        let result = CommandResult::failure(127, "bash: vim: command not found\n".to_string());
+        assert!(!Pacman.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_missing_program() {
+        assert_eq!(missing_program("vim file"), Some("vim"));
+        assert_eq!(missing_program("sudo vim file"), Some("vim"));
+        assert_eq!(missing_program(""), None);
+    }
+
+    #[test]
+    fn test_parse_pkgfile_output() {
+        let output = "extra/vim 9.0.1-1\t/usr/bin/vim\nextra/gvim 9.0.1-1\t/usr/bin/vim\n";
+        assert_eq!(
+            parse_pkgfile_output(output),
+            vec!["extra/vim", "extra/gvim"]
+        );
+        assert_eq!(parse_pkgfile_output("extra/vim\n"), vec!["extra/vim"]);
+    }
+
+    #[test]
+    fn test_install_commands() {
+        let packages = vec!["extra/vim".to_string(), "extra/gvim".to_string()];
+        assert_eq!(
+            install_commands("vim file", &packages),
+            vec![
+                "sudo pacman -S extra/vim && vim file",
+                "sudo pacman -S extra/gvim && vim file"
+            ]
+        );
+    }
+}
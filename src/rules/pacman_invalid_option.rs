@@ -0,0 +1,124 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Pacman operations, which must be given as uppercase flags
+const OPERATIONS: &str = "dfqrstuv";
+
+/// Fixes lowercase pacman operations, e.g. `pacman -s vim` -> `pacman -S vim`
+pub struct PacmanInvalidOption;
+
+#[allow(clippy::type_complexity)]
+fn invalid_option_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"error: invalid option '-(\w)'").expect("valid regex"))
+}
+
+/// Gets the lowercase operation pacman rejected, as reported in its error output
+#[allow(clippy::type_complexity)]
+fn invalid_operation(result: &CommandResult) -> Option<char> {
+    invalid_option_regex()
+        .captures(&result.output())
+        .and_then(|captures| captures[1].chars().next())
+        .filter(|option| OPERATIONS.contains(*option))
+}
+
+/// Uppercases the first flag group of the script that starts with the operation
+#[allow(clippy::type_complexity)]
+fn uppercase_operation(script: &str, operation: char) -> Option<String> {
+    let regex = Regex::new(&format!(r"(?:^|\s)-({operation}[a-zA-Z]*)")).ok()?;
+    let flags = regex.captures(script)?.get(1)?;
+    Some(format!(
+        "{}{}{}",
+        &script[..flags.start()],
+        operation.to_ascii_uppercase(),
+        &script[flags.start() + 1..]
+    ))
+}
+
+impl Rule for PacmanInvalidOption {
+    fn name(&self) -> &'static str {
+        "pacman_invalid_option"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes lowercase pacman operations, e.g. `pacman -s vim` -> `pacman -S vim`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        invalid_operation(result)
+            .is_some_and(|operation| uppercase_operation(command.trimmed(), operation).is_some())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        invalid_operation(result)
+            .and_then(|operation| uppercase_operation(command.trimmed(), operation))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Rule, for_app, sudo_support};
+    use crate::types::Shell;
+
+    fn invalid(option: char) -> CommandResult {
+        CommandResult::failure(1, format!("error: invalid option '-{option}'\n"))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn pacman() -> impl Rule {
+        sudo_support(for_app(
+            &["pacman", "yay", "paru", "pikaur"],
+            PacmanInvalidOption,
+        ))
+    }
+
+    #[test]
+    fn test_match() {
+        for (script, option) in [
+            ("pacman -s vim", 's'),
+            ("sudo pacman -r vim", 'r'),
+            ("pacman -ss vim", 's'),
+            ("yay -u", 'u'),
+        ] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            assert!(pacman().is_match(&command, &invalid(option)));
+        }
+
+        let command = Command::new("pacman -S vim".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "error: target not found: vim\n".to_string());
+        assert!(!pacman().is_match(&command, &result));
+
+        let command = Command::new("apt -s vim".to_string(), Shell::Bash);
+        assert!(!pacman().is_match(&command, &invalid('s')));
+
+        let command = Command::new("pacman -x vim".to_string(), Shell::Bash);
+        assert!(!pacman().is_match(&command, &invalid('x')));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        for (script, option, fixed) in [
+            ("pacman -s vim", 's', "pacman -S vim"),
+            ("sudo pacman -ss vim", 's', "sudo pacman -Ss vim"),
+            ("pacman -r vim", 'r', "pacman -R vim"),
+            ("pacman -syu", 's', "pacman -Syu"),
+            (
+                "pacman --needed -q -s vim",
+                's',
+                "pacman --needed -q -S vim",
+            ),
+        ] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            assert_eq!(
+                pacman().get_new_commands(&command, &invalid(option)),
+                vec![fixed]
+            );
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+
+/// Starts the built-in web server with the uppercase flag, e.g. `php -s localhost:8000` -> `php -S localhost:8000`
+pub struct PhpS;
+
+impl Rule for PhpS {
+    fn name(&self) -> &'static str {
+        "php_s"
+    }
+
+    fn description(&self) -> &'static str {
+        "Starts the built-in web server with the uppercase flag, e.g. `php -s localhost:8000` -> `php -S localhost:8000`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        command.arguments().contains(&"-s")
+            && command
+                .arguments()
+                .last()
+                .is_some_and(|arg| !arg.starts_with('-'))
+            && result.output().contains("Could not open input file")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        vec![command.replace_argument("-s", "-S")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Could not open input file: localhost:8000\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("php -s localhost:8000".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(PhpS.is_match(&command, &result));
+
+        let command = Command::new("php -S localhost:8000".to_string(), Shell::Bash);
+        assert!(!PhpS.is_match(&command, &result));
+        let command = Command::new("php -s".to_string(), Shell::Bash);
+        assert!(!PhpS.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("php -t public -s localhost:8000".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            PhpS.get_new_commands(&command, &result),
+            vec!["php -t public -S localhost:8000"]
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Retries a `pip install` that failed with a permission error, first with `--user`
+/// and then with sudo
+pub struct PipInstall;
+
+#[allow(clippy::type_complexity)]
+fn pip_install_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"^(?:pip[\d.]*|python[\d.]* -m pip) install\b").expect("valid regex")
+    })
+}
+
+/// Checks if the output is one of the permission errors pip reports
+fn is_permission_error(output: &str) -> bool {
+    output.contains("Permission denied")
+        || output.contains("EACCES")
+        || (output.contains("OSError") && output.contains("Errno 13"))
+}
+
+impl Rule for PipInstall {
+    fn name(&self) -> &'static str {
+        "pip_install"
+    }
+
+    fn description(&self) -> &'static str {
+        "Retries a `pip install` that failed with a permission error, first with `--user` and then with sudo"
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        &["pip", "python"]
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        pip_install_regex().is_match(command.trimmed()) && is_permission_error(&result.output())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        if command.contains_argument("--user") {
+            return vec![format!("sudo {}", command.remove_argument("--user"))];
+        }
+
+        vec![
+            command.insert_after_argument("install", "--user"),
+            format!("sudo {script}"),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "ERROR: Could not install packages due to an OSError: \
+        [Errno 13] Permission denied: '/usr/lib/python3/dist-packages/requests'\n\
+        Consider using the `--user` option or check the permissions.\n";
+
+    #[test]
+    fn test_match() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        for script in [
+            "pip install requests",
+            "pip3 install requests",
+            "python3 -m pip install requests",
+        ] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            assert!(PipInstall.is_match(&command, &result));
+        }
+
+        let command = Command::new("pip uninstall requests".to_string(), Shell::Bash);
+        assert!(!PipInstall.is_match(&command, &result));
+
+        let command = Command::new("pip install requests".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "ERROR: No matching distribution\n".to_string());
+        assert!(!PipInstall.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        let command = Command::new("pip install requests".to_string(), Shell::Bash);
+        assert_eq!(
+            PipInstall.get_new_commands(&command, &result),
+            vec!["pip install --user requests", "sudo pip install requests"]
+        );
+
+        let command = Command::new("pip install --user requests".to_string(), Shell::Bash);
+        assert_eq!(
+            PipInstall.get_new_commands(&command, &result),
+            vec!["sudo pip install requests"]
+        );
+    }
+}
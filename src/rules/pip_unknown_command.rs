@@ -0,0 +1,105 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Applies pip's own suggestion for a misspelled command, e.g. `pip instatl` -> `pip install`
+pub struct PipUnknownCommand;
+
+#[allow(clippy::type_complexity)]
+fn suggestion_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r#"unknown command ["']([^"']+)["'][\s,-]*maybe you meant ["']([^"']+)["']"#)
+            .expect("valid regex")
+    })
+}
+
+/// Gets the misspelled command and pip's suggestion from its error output
+#[allow(clippy::type_complexity)]
+fn broken_and_fixed(result: &CommandResult) -> Option<(String, String)> {
+    suggestion_regex()
+        .captures(&result.output())
+        .map(|captures| (captures[1].to_string(), captures[2].to_string()))
+}
+
+impl Rule for PipUnknownCommand {
+    fn name(&self) -> &'static str {
+        "pip_unknown_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Applies pip's own suggestion for a misspelled command, e.g. `pip instatl` -> `pip install`"
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn programs(&self) -> &[&'static str] {
+        &["pip"]
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        broken_and_fixed(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        broken_and_fixed(result)
+            .map(|(broken, fixed)| command.replace_argument(&broken, &fixed))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{for_app, sudo_support};
+    use crate::types::Shell;
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("pip instatl requests".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "ERROR: unknown command \"instatl\" - maybe you meant \"install\"\n".to_string(),
+        );
+        assert!(PipUnknownCommand.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            1,
+            "ERROR: unknown command \"instatl\", maybe you meant \"install\"\n".to_string(),
+        );
+        assert!(PipUnknownCommand.is_match(&command, &result));
+
+        let command = Command::new("pip3 instatl requests".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "ERROR: unknown command 'instatl', maybe you meant 'install'\n".to_string(),
+        );
+        assert!(PipUnknownCommand.is_match(&command, &result));
+
+        let command = Command::new("pip install requests".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "ERROR: No matching distribution\n".to_string());
+        assert!(!PipUnknownCommand.is_match(&command, &result));
+
+        let command = Command::new("pipx instatl requests".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "ERROR: unknown command \"instatl\", maybe you meant \"install\"\n".to_string(),
+        );
+        assert!(!for_app(&["pip", "pip2", "pip3"], PipUnknownCommand).is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("sudo pip instatl requests".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            "ERROR: unknown command \"instatl\", maybe you meant \"install\"\n".to_string(),
+        );
+        assert_eq!(
+            sudo_support(PipUnknownCommand).get_new_commands(&command, &result),
+            vec!["sudo pip install requests"]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled package.json scripts, e.g. `pnpm run buil` -> `pnpm run build`
+pub struct PnpmMissingScript;
+
+#[allow(clippy::type_complexity)]
+fn missing_script(result: &CommandResult) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"ERR_PNPM_NO_SCRIPT\s+Missing script:? "?([^"\s]+)"?"#).expect("valid regex")
+    });
+    regex
+        .captures(&result.output())
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for PnpmMissingScript {
+    fn name(&self) -> &'static str {
+        "pnpm_missing_script"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled package.json scripts, e.g. `pnpm run buil` -> `pnpm run build`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_script(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match missing_script(result) {
+            Some(script) => utils::replace_command(
+                command.trimmed(),
+                &script,
+                &utils::package_json_scripts(&command.cwd),
+            ),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::for_app;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = " ERR_PNPM_NO_SCRIPT  Missing script: buil\n\n\
+        Command \"buil\" not found. Did you mean \"pnpm run build\"?\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("pnpm run buil".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(PnpmMissingScript.is_match(&command, &result));
+
+        let command = Command::new("npm run buil".to_string(), Shell::Bash);
+        assert!(!for_app(&["pnpm"], PnpmMissingScript).is_match(&command, &result));
+
+        let command = Command::new("pnpm install".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, " ERR_PNPM_FETCH_404  GET failed\n".to_string());
+        assert!(!PnpmMissingScript.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"build": "tsc", "lint": "eslint ."}}"#,
+        )
+        .unwrap();
+
+        let command = Command::new("pnpm run buil --watch".to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string());
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            PnpmMissingScript.get_new_commands(&command, &result),
+            vec!["pnpm run build --watch"]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::rules::Rule;
+use crate::rules::pnpm_wrong_command::{command_not_found, wrong_command};
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Runs package.json scripts pnpm failed to find as commands, e.g. `pnpm typecheck` -> `pnpm run typecheck`
+pub struct PnpmRunScript;
+
+impl Rule for PnpmRunScript {
+    fn name(&self) -> &'static str {
+        "pnpm_run_script"
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs package.json scripts pnpm failed to find as commands, e.g. `pnpm typecheck` -> `pnpm run typecheck`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        command_not_found(&result.output()).is_some()
+            && wrong_command(command).is_some_and(|name| {
+                utils::package_json_scripts(&command.cwd)
+                    .iter()
+                    .any(|script| script == name)
+            })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        match wrong_command(command) {
+            Some(name) => vec![command.insert_before_argument(name, "run")],
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = " ERR_PNPM_RECURSIVE_EXEC_FIRST_FAIL  Command \"typecheck\" not found\n";
+
+    #[allow(clippy::type_complexity)]
+    fn pnpm(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"typecheck": "tsc --noEmit"}}"#,
+        )
+        .unwrap();
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(PnpmRunScript.is_match(&pnpm("pnpm typecheck", &dir), &result));
+        assert!(!PnpmRunScript.is_match(&pnpm("pnpm lint", &dir), &result));
+
+        let result = CommandResult::success(String::new());
+        assert!(!PnpmRunScript.is_match(&pnpm("pnpm typecheck", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            PnpmRunScript.get_new_commands(&pnpm("pnpm typecheck --pretty", &dir), &result),
+            vec!["pnpm run typecheck --pretty"]
+        );
+    }
+}
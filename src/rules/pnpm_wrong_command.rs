@@ -0,0 +1,169 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into pnpm
+#[allow(clippy::type_complexity)]
+const PNPM_COMMANDS: &[&str] = &[
+    "add",
+    "audit",
+    "bin",
+    "config",
+    "create",
+    "dedupe",
+    "deploy",
+    "dlx",
+    "doctor",
+    "env",
+    "exec",
+    "fetch",
+    "import",
+    "init",
+    "install",
+    "install-test",
+    "licenses",
+    "link",
+    "list",
+    "outdated",
+    "pack",
+    "patch",
+    "patch-commit",
+    "prune",
+    "publish",
+    "rebuild",
+    "remove",
+    "root",
+    "run",
+    "setup",
+    "start",
+    "store",
+    "test",
+    "unlink",
+    "update",
+    "why",
+];
+
+/// Fixes misspelled pnpm commands, e.g. `pnpm isntall` -> `pnpm install`
+pub struct PnpmWrongCommand;
+
+/// Gets the first argument that isn't a flag, which pnpm treats as the command
+#[allow(clippy::type_complexity)]
+pub(crate) fn wrong_command(command: &Command) -> Option<&str> {
+    command
+        .arguments()
+        .into_iter()
+        .find(|arg| !arg.starts_with('-'))
+}
+
+/// Gets the command pnpm couldn't find, either as a command or as a script
+#[allow(clippy::type_complexity)]
+pub(crate) fn command_not_found(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r#"Command "([^"]+)" not found|Unknown command '([^']+)'"#).expect("valid regex")
+    });
+    let captures = regex.captures(output)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(2))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Gets the command pnpm suggests, e.g. from `Did you mean "pnpm install"?`
+#[allow(clippy::type_complexity)]
+fn suggested_command(output: &str) -> Option<String> {
+    let suggestion = utils::extract_suggestions(output).into_iter().next()?;
+    let suggestion = suggestion.strip_prefix("pnpm ").unwrap_or(&suggestion);
+    suggestion.split_whitespace().next().map(str::to_string)
+}
+
+impl Rule for PnpmWrongCommand {
+    fn name(&self) -> &'static str {
+        "pnpm_wrong_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled pnpm commands, e.g. `pnpm isntall` -> `pnpm install`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        wrong_command(command).is_some_and(|wrong| {
+            command_not_found(&result.output()).is_some_and(|missing| missing == wrong)
+        })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(wrong) = wrong_command(command) else {
+            return Vec::new();
+        };
+        let script = command.trimmed();
+
+        let mut fixes: Vec<String> = suggested_command(&result.output())
+            .iter()
+            .map(|fixed| command.replace_argument(wrong, fixed))
+            .collect();
+        for fix in utils::replace_command(script, wrong, PNPM_COMMANDS) {
+            if !fixes.contains(&fix) {
+                fixes.push(fix);
+            }
+        }
+        fixes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = " ERR_PNPM_RECURSIVE_EXEC_FIRST_FAIL  Command \"isntall\" not found\n";
+
+    const LEGACY_OUTPUT: &str = " ERROR  Unknown command 'isntall'\n\
+        Did you mean 'install'?\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("pnpm isntall react".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(PnpmWrongCommand.is_match(&command, &result));
+        let result = CommandResult::failure(1, LEGACY_OUTPUT.to_string());
+        assert!(PnpmWrongCommand.is_match(&command, &result));
+
+        let command = Command::new("pnpm exec tsc".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            1,
+            " ERR_PNPM_RECURSIVE_EXEC_FIRST_FAIL  Command \"tsc\" not found\n".to_string(),
+        );
+        assert!(!PnpmWrongCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("pnpm -r isntall react".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            PnpmWrongCommand.get_new_commands(&command, &result)[0],
+            "pnpm -r install react"
+        );
+
+        let command = Command::new("pnpm isntall".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, LEGACY_OUTPUT.to_string());
+        assert_eq!(
+            PnpmWrongCommand.get_new_commands(&command, &result)[0],
+            "pnpm install"
+        );
+    }
+
+    #[test]
+    fn test_parse_output() {
+        assert_eq!(command_not_found(OUTPUT), Some("isntall".to_string()));
+        assert_eq!(
+            suggested_command(LEGACY_OUTPUT),
+            Some("install".to_string())
+        );
+        assert_eq!(suggested_command(OUTPUT), None);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use std::path::Path;
+
+/// Runs the tests in subdirectories too, e.g. `prove t/` -> `prove -r t/`
+pub struct ProveRecursively;
+
+/// Checks if the arguments already ask prove to recurse, including combined flags like `-lr`
+#[allow(clippy::type_complexity)]
+fn is_recursive(arguments: &[&str]) -> bool {
+    arguments.iter().any(|arg| {
+        *arg == "--recurse"
+            || (arg.starts_with('-') && !arg.starts_with("--") && arg[1..].contains('r'))
+    })
+}
+
+impl Rule for ProveRecursively {
+    fn name(&self) -> &'static str {
+        "prove_recursively"
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs the tests in subdirectories too, e.g. `prove t/` -> `prove -r t/`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let arguments = command.arguments();
+        result.output().contains("NOTESTS")
+            && !is_recursive(&arguments)
+            && arguments
+                .iter()
+                .any(|arg| !arg.starts_with('-') && Path::new(&command.cwd).join(arg).is_dir())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        vec![command.replace_command("prove -r")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Files=0, Tests=0,  0 wallclock secs ( 0.00 usr +  0.00 sys =  0.00 CPU)\n\
+        Result: NOTESTS\n";
+
+    #[allow(clippy::type_complexity)]
+    fn prove(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("t")).unwrap();
+        let result = CommandResult::failure(3, OUTPUT.to_string());
+        assert!(ProveRecursively.is_match(&prove("prove -l t", &dir), &result));
+        assert!(!ProveRecursively.is_match(&prove("prove -lr t", &dir), &result));
+        assert!(!ProveRecursively.is_match(&prove("prove --recurse t", &dir), &result));
+        assert!(!ProveRecursively.is_match(&prove("prove missing", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = CommandResult::failure(3, OUTPUT.to_string());
+        assert_eq!(
+            ProveRecursively.get_new_commands(&prove("prove -l t/", &dir), &result),
+            vec!["prove -r -l t/"]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands shipped with pyenv, used when `pyenv commands` is unavailable
+#[allow(clippy::type_complexity)]
+const DEFAULT_PYENV_COMMANDS: &[&str] = &[
+    "commands",
+    "completions",
+    "exec",
+    "global",
+    "help",
+    "hooks",
+    "init",
+    "install",
+    "latest",
+    "local",
+    "prefix",
+    "rehash",
+    "root",
+    "shell",
+    "shims",
+    "uninstall",
+    "version",
+    "version-file",
+    "version-name",
+    "versions",
+    "whence",
+    "which",
+];
+
+/// Commands of pyenv and rbenv that take a version as their first argument
+#[allow(clippy::type_complexity)]
+const VERSION_COMMANDS: &[&str] = &[
+    "global",
+    "install",
+    "latest",
+    "local",
+    "prefix",
+    "shell",
+    "uninstall",
+];
+
+/// Fixes misspelled pyenv commands and version prefixes,
+/// e.g. `pyenv instal 3.12` -> `pyenv install 3.12`
+pub struct PyenvNoSuchCommand;
+
+/// Gets the command a version manager like pyenv or rbenv doesn't know
+#[allow(clippy::type_complexity)]
+pub(crate) fn no_such_command(tool: &str, output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"(\w+): no such command `([^']+)'").expect("valid regex"));
+    regex
+        .captures(output)
+        .filter(|captures| &captures[1] == tool)
+        .map(|captures| captures[2].to_string())
+}
+
+/// Gets the version missing a build definition, e.g. from `python-build: definition not found: 3.12`
+#[allow(clippy::type_complexity)]
+pub(crate) fn definition_not_found(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"definition not found: (\S+)").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the commands of a version manager, reusing what it listed last time
+#[allow(clippy::type_complexity)]
+pub(crate) fn tool_commands(tool: &str, defaults: &[&str]) -> Vec<String> {
+    let mut commands: Vec<String> = defaults.iter().map(|c| c.to_string()).collect();
+    for name in utils::cached_output(tool, &["commands"])
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        if !commands.iter().any(|c| c == name) {
+            commands.push(name.to_string());
+        }
+    }
+    commands
+}
+
+/// Fixes the broken command, preferring commands that take a version when one follows it
+#[allow(clippy::type_complexity)]
+pub(crate) fn fix_command(command: &Command, broken: &str, commands: &[String]) -> Vec<String> {
+    let arguments = command.arguments();
+    let takes_version = arguments
+        .iter()
+        .skip_while(|arg| **arg != broken)
+        .nth(1)
+        .is_some_and(|arg| arg.starts_with(|c: char| c.is_ascii_digit()));
+    if takes_version {
+        #[allow(clippy::type_complexity)]
+        let version_commands: Vec<&String> = commands
+            .iter()
+            .filter(|c| VERSION_COMMANDS.contains(&c.as_str()))
+            .collect();
+        let fixes = utils::replace_command(command.trimmed(), broken, &version_commands);
+        if !fixes.is_empty() {
+            return fixes;
+        }
+    }
+    utils::replace_command(command.trimmed(), broken, commands)
+}
+
+/// Gets the newest stable versions from an install list that start with the prefix
+#[allow(clippy::type_complexity)]
+pub(crate) fn matching_versions(list: &str, prefix: &str) -> Vec<String> {
+    let mut versions: Vec<&str> = list
+        .lines()
+        .map(str::trim)
+        .filter(|version| {
+            version.starts_with(&format!("{prefix}."))
+                && version.chars().all(|c| c.is_ascii_digit() || c == '.')
+        })
+        .collect();
+    versions.sort_by_key(|version| {
+        std::cmp::Reverse(
+            version
+                .split('.')
+                .map(|part| part.parse::<u32>().unwrap_or(0))
+                .collect::<Vec<u32>>(),
+        )
+    });
+    versions
+        .into_iter()
+        .take(utils::DEFAULT_MATCHES)
+        .map(str::to_string)
+        .collect()
+}
+
+impl Rule for PyenvNoSuchCommand {
+    fn name(&self) -> &'static str {
+        "pyenv_no_such_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled pyenv commands and version prefixes, e.g. `pyenv instal 3.12` -> `pyenv install 3.12`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        no_such_command("pyenv", &output).is_some()
+            || (command.argument(0) == Some("install") && definition_not_found(&output).is_some())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        if let Some(broken) = no_such_command("pyenv", &output) {
+            return fix_command(
+                command,
+                &broken,
+                &tool_commands("pyenv", DEFAULT_PYENV_COMMANDS),
+            );
+        }
+        let Some(prefix) = definition_not_found(&output) else {
+            return Vec::new();
+        };
+        let list = utils::get_output("pyenv", &["install", "--list"]).unwrap_or_default();
+        matching_versions(&list, &prefix)
+            .iter()
+            .map(|version| command.replace_argument(&prefix, version))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("pyenv instal 3.12.1".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "pyenv: no such command `instal'\n".to_string());
+        assert!(PyenvNoSuchCommand.is_match(&command, &result));
+
+        let command = Command::new("pyenv install 3.12".to_string(), Shell::Bash);
+        let result = CommandResult::failure(
+            2,
+            "python-build: definition not found: 3.12\n\n\
+             See all available versions with `pyenv install --list'.\n"
+                .to_string(),
+        );
+        assert!(PyenvNoSuchCommand.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "rbenv: no such command `instal'\n".to_string());
+        assert!(!PyenvNoSuchCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("pyenv instal 3.12.1".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "pyenv: no such command `instal'\n".to_string());
+        assert_eq!(
+            PyenvNoSuchCommand.get_new_commands(&command, &result)[0],
+            "pyenv install 3.12.1"
+        );
+    }
+
+    #[test]
+    fn test_fix_command_prefers_version_commands() {
+        #[allow(clippy::type_complexity)]
+        let commands: Vec<String> = ["versions", "version", "local", "uninstall"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let command = Command::new("pyenv lcal 3.11".to_string(), Shell::Bash);
+        assert_eq!(
+            fix_command(&command, "lcal", &commands),
+            vec!["pyenv local 3.11"]
+        );
+
+        let command = Command::new("pyenv versons".to_string(), Shell::Bash);
+        assert_eq!(
+            fix_command(&command, "versons", &commands)[0],
+            "pyenv versions"
+        );
+    }
+
+    #[test]
+    fn test_matching_versions() {
+        let list = "Available versions:\n  3.11.9\n  3.12.0\n  3.12.10\n  3.12.2\n  3.12-dev\n  \
+            3.13.0a1\n  pypy3.12-7.3.19\n";
+        assert_eq!(
+            matching_versions(list, "3.12"),
+            vec!["3.12.10", "3.12.2", "3.12.0"]
+        );
+    }
+}
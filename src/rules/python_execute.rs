@@ -0,0 +1,80 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use std::path::Path;
+
+/// Runs the script file, adding the missing extension, e.g. `python foo` -> `python foo.py`
+pub struct PythonExecute;
+
+/// Gets the script argument when only its `.py` file exists in the directory
+#[allow(clippy::type_complexity)]
+fn script_without_extension(command: &Command) -> Option<&str> {
+    let script = command
+        .arguments()
+        .into_iter()
+        .find(|arg| !arg.starts_with('-'))?;
+    let cwd = Path::new(&command.cwd);
+    (!script.ends_with(".py")
+        && !cwd.join(script).exists()
+        && cwd.join(format!("{script}.py")).is_file())
+    .then_some(script)
+}
+
+impl Rule for PythonExecute {
+    fn name(&self) -> &'static str {
+        "python_execute"
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs the script file, adding the missing extension, e.g. `python foo` -> `python foo.py`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        result.output().contains("can't open file") && script_without_extension(command).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        match script_without_extension(command) {
+            Some(script) => vec![command.replace_argument(script, &format!("{script}.py"))],
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::for_app;
+    use crate::types::Shell;
+
+    const OUTPUT: &str =
+        "python3: can't open file '/home/me/foo': [Errno 2] No such file or directory\n";
+
+    #[allow(clippy::type_complexity)]
+    fn python(script: &str, dir: &tempfile::TempDir) -> Command {
+        Command::new(script.to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("foo.py"), "print('hi')\n").unwrap();
+        let result = CommandResult::failure(2, OUTPUT.to_string());
+        assert!(PythonExecute.is_match(&python("python3 foo", &dir), &result));
+        assert!(!PythonExecute.is_match(&python("python3 bar", &dir), &result));
+        let rule = for_app(&["python"], PythonExecute);
+        assert!(!rule.is_match(&python("ruby foo", &dir), &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("foo.py"), "print('hi')\n").unwrap();
+        let result = CommandResult::failure(2, OUTPUT.to_string());
+        assert_eq!(
+            PythonExecute.get_new_commands(&python("python -u foo --verbose", &dir), &result),
+            vec!["python -u foo.py --verbose"]
+        );
+    }
+}
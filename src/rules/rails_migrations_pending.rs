@@ -0,0 +1,73 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Runs the pending migrations before retrying, e.g. `rspec` -> `bin/rails db:migrate RAILS_ENV=test && rspec`
+pub struct RailsMigrationsPending;
+
+/// Gets the migration command rails asks to run
+#[allow(clippy::type_complexity)]
+fn migrate_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"To resolve this issue, run:\s+(\S[^\n]*?)\s*(?:\n|$)").expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for RailsMigrationsPending {
+    fn name(&self) -> &'static str {
+        "rails_migrations_pending"
+    }
+
+    fn description(&self) -> &'static str {
+        "Runs the pending migrations before retrying, e.g. `rspec` -> `bin/rails db:migrate RAILS_ENV=test && rspec`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        output.contains("Migrations are pending") && migrate_command(&output).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match migrate_command(&result.output()) {
+            Some(migrate) => vec![format!("{migrate} && {}", command.trimmed())],
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "ActiveRecord::PendingMigrationError:\n\n\
+        Migrations are pending. To resolve this issue, run:\n\n        \
+        bin/rails db:migrate RAILS_ENV=test\n\n\
+        You have 1 pending migration:\n\n20240101000000_create_users.rb\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("bundle exec rspec".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(RailsMigrationsPending.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "1 example, 1 failure\n".to_string());
+        assert!(!RailsMigrationsPending.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("bundle exec rspec".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            RailsMigrationsPending.get_new_commands(&command, &result),
+            vec!["bin/rails db:migrate RAILS_ENV=test && bundle exec rspec"]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::rules::Rule;
+use crate::rules::pyenv_no_such_command::{
+    definition_not_found, fix_command, matching_versions, no_such_command, tool_commands,
+};
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Commands shipped with rbenv, used when `rbenv commands` is unavailable
+#[allow(clippy::type_complexity)]
+const DEFAULT_RBENV_COMMANDS: &[&str] = &[
+    "commands",
+    "completions",
+    "exec",
+    "global",
+    "help",
+    "hooks",
+    "init",
+    "install",
+    "local",
+    "prefix",
+    "rehash",
+    "root",
+    "shell",
+    "shims",
+    "uninstall",
+    "version",
+    "version-file",
+    "version-name",
+    "versions",
+    "whence",
+    "which",
+];
+
+/// Fixes misspelled rbenv commands and version prefixes,
+/// e.g. `rbenv instal 3.3.0` -> `rbenv install 3.3.0`
+pub struct RbenvNoSuchCommand;
+
+impl Rule for RbenvNoSuchCommand {
+    fn name(&self) -> &'static str {
+        "rbenv_no_such_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled rbenv commands and version prefixes, e.g. `rbenv instal 3.3.0` -> `rbenv install 3.3.0`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        no_such_command("rbenv", &output).is_some()
+            || (command.argument(0) == Some("install") && definition_not_found(&output).is_some())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        if let Some(broken) = no_such_command("rbenv", &output) {
+            return fix_command(
+                command,
+                &broken,
+                &tool_commands("rbenv", DEFAULT_RBENV_COMMANDS),
+            );
+        }
+        let Some(prefix) = definition_not_found(&output) else {
+            return Vec::new();
+        };
+        let list = utils::get_output("rbenv", &["install", "--list-all"]).unwrap_or_default();
+        matching_versions(&list, &prefix)
+            .iter()
+            .map(|version| command.replace_argument(&prefix, version))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("rbenv lcal 3.3.0".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "rbenv: no such command `lcal'\n".to_string());
+        assert!(RbenvNoSuchCommand.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "pyenv: no such command `lcal'\n".to_string());
+        assert!(!RbenvNoSuchCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("rbenv lcal 3.3.0".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "rbenv: no such command `lcal'\n".to_string());
+        assert_eq!(
+            RbenvNoSuchCommand.get_new_commands(&command, &result)[0],
+            "rbenv local 3.3.0"
+        );
+    }
+}
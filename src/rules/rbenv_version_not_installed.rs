@@ -0,0 +1,69 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Installs the Ruby version a project asks for, e.g. `bundle` -> `rbenv install 3.3.0 && bundle`
+pub struct RbenvVersionNotInstalled;
+
+#[allow(clippy::type_complexity)]
+fn missing_version(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"rbenv: version `([^']+)' is not installed").expect("valid regex")
+    });
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for RbenvVersionNotInstalled {
+    fn name(&self) -> &'static str {
+        "rbenv_version_not_installed"
+    }
+
+    fn description(&self) -> &'static str {
+        "Installs the Ruby version a project asks for, e.g. `bundle` -> `rbenv install 3.3.0 && bundle`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_version(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match missing_version(&result.output()) {
+            Some(version) => vec![format!("rbenv install {version} && {}", command.trimmed())],
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str =
+        "rbenv: version `3.3.0' is not installed (set by /home/me/app/.ruby-version)\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("bundle install".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(RbenvVersionNotInstalled.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "Could not locate Gemfile\n".to_string());
+        assert!(!RbenvVersionNotInstalled.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("bundle install".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            RbenvVersionNotInstalled.get_new_commands(&command, &result),
+            vec!["rbenv install 3.3.0 && bundle install"]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Terminates sed substitutions, e.g. `sed -e s/foo/bar file` -> `sed -e s/foo/bar/ file`
+pub struct SedUnterminatedS;
+
+/// Gets the closing delimiter a `s` expression lacks
+#[allow(clippy::type_complexity)]
+fn missing_delimiter(expression: &str) -> Option<char> {
+    let substitution = expression.strip_prefix("-e").unwrap_or(expression);
+    let mut chars = substitution.chars();
+    if chars.next() != Some('s') {
+        return None;
+    }
+    let delimiter = chars
+        .next()
+        .filter(|c| !c.is_alphanumeric() && *c != '\\')?;
+
+    let mut delimiters = 1;
+    let mut escaped = false;
+    for c in chars {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            delimiters += 1;
+        }
+    }
+    (delimiters == 2).then_some(delimiter)
+}
+
+/// Adds a delimiter to a word as written, inside its closing quote if it has one
+#[allow(clippy::type_complexity)]
+fn terminate(raw: &str, delimiter: char) -> String {
+    match raw.char_indices().last() {
+        Some((index, quote @ ('\'' | '"'))) if !raw[..index].ends_with('\\') => {
+            format!("{}{delimiter}{quote}", &raw[..index])
+        }
+        _ => format!("{raw}{delimiter}"),
+    }
+}
+
+impl Rule for SedUnterminatedS {
+    fn name(&self) -> &'static str {
+        "sed_unterminated_s"
+    }
+
+    fn description(&self) -> &'static str {
+        "Terminates sed substitutions, e.g. `sed -e s/foo/bar file` -> `sed -e s/foo/bar/ file`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        result.output().contains("unterminated `s' command")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        let words = utils::script_words(script);
+        #[allow(clippy::type_complexity)]
+        let unterminated: Vec<&utils::Word> = words
+            .iter()
+            .skip(1)
+            .filter(|word| missing_delimiter(&word.value).is_some())
+            .collect();
+        if unterminated.is_empty() {
+            return Vec::new();
+        }
+        vec![utils::rewrite_words(script, &unterminated, |word| {
+            let raw = &script[word.start..word.end];
+            missing_delimiter(&word.value).map_or_else(|| raw.to_string(), |d| terminate(raw, d))
+        })]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "sed: -e expression #1, char 9: unterminated `s' command\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("sed -e s/foo/bar file".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(SedUnterminatedS.is_match(&command, &result));
+
+        let result = CommandResult::failure(2, "sed: can't read file: No such file\n".to_string());
+        assert!(!SedUnterminatedS.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        for (script, expected) in [
+            ("sed -e s/foo/bar file", "sed -e s/foo/bar/ file"),
+            ("sed -es/foo/bar file", "sed -es/foo/bar/ file"),
+            ("sed 's/a b/c d' file", "sed 's/a b/c d/' file"),
+            (
+                "sed -e 's|/usr|/opt' -e s/x/y/ f",
+                "sed -e 's|/usr|/opt|' -e s/x/y/ f",
+            ),
+            ("sed \"s/$HOME/x\" f", "sed \"s/$HOME/x/\" f"),
+            ("sed -e's/a/b' f", "sed -e's/a/b/' f"),
+        ] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            assert_eq!(
+                SedUnterminatedS.get_new_commands(&command, &result),
+                vec![expected]
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_delimiter() {
+        assert_eq!(missing_delimiter(r"s/a\/b/c"), Some('/'));
+        assert_eq!(missing_delimiter("-es|a|b"), Some('|'));
+        assert_eq!(missing_delimiter("s/a/b/"), None);
+        assert_eq!(missing_delimiter("file"), None);
+        assert_eq!(missing_delimiter("-n"), None);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Installs the snap that provides a missing command,
+/// e.g. `htop` -> `sudo snap install htop && htop`
+pub struct SnapInstall;
+
+/// Gets the snaps to install, keeping flags like `--classic` the hint asks for
+#[allow(clippy::type_complexity)]
+fn suggested_snaps(result: &CommandResult) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX
+        .get_or_init(|| Regex::new(r"(?m)(?:sudo )?snap install ([^#\n]+)").expect("valid regex"));
+    let mut snaps: Vec<String> = Vec::new();
+    for captures in regex.captures_iter(&result.output()) {
+        let snap = captures[1].trim().trim_end_matches(',').trim().to_string();
+        if !snap.is_empty() && !snaps.contains(&snap) {
+            snaps.push(snap);
+        }
+    }
+    snaps
+}
+
+impl Rule for SnapInstall {
+    fn name(&self) -> &'static str {
+        "snap_install"
+    }
+
+    fn description(&self) -> &'static str {
+        "Installs the snap that provides a missing command, e.g. `htop` -> `sudo snap install htop && htop`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        !command.starts_with("snap")
+            && result.output().contains("not found")
+            && !suggested_snaps(result).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        suggested_snaps(result)
+            .iter()
+            .map(|snap| format!("sudo snap install {snap} && {}", command.trimmed()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Command 'htop' not found, but can be installed with:\n\n\
+        sudo snap install htop  # version 3.2.2, or\nsudo apt  install htop  # version 3.0.5-7\n\n\
+        See 'snap info htop' for additional versions.\n";
+
+    const TRY_OUTPUT: &str = "Command 'code' not found, did you mean:\n  \
+        try: sudo snap install code --classic\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("htop -d 10".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, OUTPUT.to_string());
+        assert!(SnapInstall.is_match(&command, &result));
+
+        let result = CommandResult::failure(127, "htop: command not found\n".to_string());
+        assert!(!SnapInstall.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("htop -d 10".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, OUTPUT.to_string());
+        assert_eq!(
+            SnapInstall.get_new_commands(&command, &result),
+            vec!["sudo snap install htop && htop -d 10"]
+        );
+
+        let command = Command::new("code .".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, TRY_OUTPUT.to_string());
+        assert_eq!(
+            SnapInstall.get_new_commands(&command, &result),
+            vec!["sudo snap install code --classic && code ."]
+        );
+    }
+}
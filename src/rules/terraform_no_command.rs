@@ -0,0 +1,79 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled terraform commands, e.g. `terraform pla` -> `terraform plan`
+pub struct TerraformNoCommand;
+
+#[allow(clippy::type_complexity)]
+fn broken_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r#"has no command named "([^"]+)""#).expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for TerraformNoCommand {
+    fn name(&self) -> &'static str {
+        "terraform_no_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled terraform commands, e.g. `terraform pla` -> `terraform plan`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        broken_command(&output).is_some() && !utils::extract_suggestions(&output).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let Some(broken) = broken_command(&output) else {
+            return Vec::new();
+        };
+        utils::extract_suggestions(&output)
+            .iter()
+            .map(|fixed| command.replace_argument(&broken, fixed))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Terraform has no command named \"pla\". Did you mean \"plan\"?\n\n\
+        To see all of Terraform's top-level commands, run:\n  terraform -help\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("terraform pla -out plan.tfplan".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(TerraformNoCommand.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            1,
+            "Terraform has no command named \"zzz\".\n\n\
+             To see all of Terraform's top-level commands, run:\n  terraform -help\n"
+                .to_string(),
+        );
+        assert!(!TerraformNoCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("terraform pla -out plan.tfplan".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            TerraformNoCommand.get_new_commands(&command, &result),
+            vec!["terraform plan -out plan.tfplan"]
+        );
+    }
+}
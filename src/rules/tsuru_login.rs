@@ -0,0 +1,53 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+
+/// Logs in again when the session expired, e.g. `tsuru app-list` -> `tsuru login && tsuru app-list`
+pub struct TsuruLogin;
+
+impl Rule for TsuruLogin {
+    fn name(&self) -> &'static str {
+        "tsuru_login"
+    }
+
+    fn description(&self) -> &'static str {
+        "Logs in again when the session expired, e.g. `tsuru app-list` -> `tsuru login && tsuru app-list`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        output.contains("not authenticated") && output.contains("session has expired")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        vec![format!("tsuru login && {}", command.trimmed())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Error: you're not authenticated or your session has expired.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("tsuru app-shell".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(TsuruLogin.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "Error: app not found\n".to_string());
+        assert!(!TsuruLogin.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("tsuru app-log -f".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            TsuruLogin.get_new_commands(&command, &result),
+            vec!["tsuru login && tsuru app-log -f"]
+        );
+    }
+}
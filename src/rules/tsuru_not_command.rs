@@ -0,0 +1,78 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled tsuru commands, e.g. `tsuru app-lst` -> `tsuru app-list`
+pub struct TsuruNotCommand;
+
+#[allow(clippy::type_complexity)]
+fn broken_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX
+        .get_or_init(|| Regex::new(r#""([^"]+)" is not a tsuru command"#).expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for TsuruNotCommand {
+    fn name(&self) -> &'static str {
+        "tsuru_not_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled tsuru commands, e.g. `tsuru app-lst` -> `tsuru app-list`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        let output = result.output();
+        broken_command(&output).is_some() && !utils::extract_suggestions(&output).is_empty()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        match broken_command(&output) {
+            Some(broken) => utils::replace_command(
+                command.trimmed(),
+                &broken,
+                &utils::extract_suggestions(&output),
+            ),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "tsuru: \"app-lst\" is not a tsuru command. See \"tsuru help\".\n\n\
+        Did you mean?\n\tapp-list\n\tapp-log\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("tsuru app-lst".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(TsuruNotCommand.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            1,
+            "tsuru: \"zzz\" is not a tsuru command. See \"tsuru help\".\n".to_string(),
+        );
+        assert!(!TsuruNotCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("tsuru app-lst -n web".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            TsuruNotCommand.get_new_commands(&command, &result)[0],
+            "tsuru app-list -n web"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Starts the VM before retrying, e.g. `vagrant ssh` -> `vagrant up && vagrant ssh`
+pub struct VagrantUp;
+
+/// Gets the machine the error is about, e.g. from `==> web: VM not created`
+#[allow(clippy::type_complexity)]
+fn machine_name(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"(?m)^==> ([^:\s]+):|machine (?:with the name )?'([^']+)'")
+            .expect("valid regex")
+    });
+    let captures = regex.captures(output)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(2))
+        .map(|m| m.as_str().to_string())
+}
+
+impl Rule for VagrantUp {
+    fn name(&self) -> &'static str {
+        "vagrant_up"
+    }
+
+    fn description(&self) -> &'static str {
+        "Starts the VM before retrying, e.g. `vagrant ssh` -> `vagrant up && vagrant ssh`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        result.output().to_lowercase().contains("run `vagrant up`")
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        let mut fixes: Vec<String> = Vec::new();
+        if let Some(machine) = machine_name(&result.output()) {
+            fixes.push(format!("vagrant up {machine} && {script}"));
+        }
+        fixes.push(format!("vagrant up && {script}"));
+        fixes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "VM must be running to open SSH connection. Run `vagrant up`\n\
+        to start the virtual machine.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("vagrant ssh".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(VagrantUp.is_match(&command, &result));
+
+        let result = CommandResult::failure(
+            1,
+            "A Vagrant environment or target machine is required\n".to_string(),
+        );
+        assert!(!VagrantUp.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("vagrant ssh".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            VagrantUp.get_new_commands(&command, &result),
+            vec!["vagrant up && vagrant ssh"]
+        );
+
+        let command = Command::new("vagrant ssh web".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, format!("==> web: {OUTPUT}"));
+        assert_eq!(
+            VagrantUp.get_new_commands(&command, &result),
+            vec![
+                "vagrant up web && vagrant ssh web",
+                "vagrant up && vagrant ssh web"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_machine_name() {
+        assert_eq!(
+            machine_name("The machine with the name 'db' was not found\n"),
+            Some("db".to_string())
+        );
+        assert_eq!(machine_name(OUTPUT), None);
+    }
+}
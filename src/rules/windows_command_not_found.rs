@@ -0,0 +1,141 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Common commands mapped to their winget package id and chocolatey package name
+#[allow(clippy::type_complexity)]
+const PACKAGES: &[(&str, &str, &str)] = &[
+    ("7z", "7zip.7zip", "7zip"),
+    ("code", "Microsoft.VisualStudioCode", "vscode"),
+    ("curl", "cURL.cURL", "curl"),
+    ("docker", "Docker.DockerDesktop", "docker-desktop"),
+    ("gh", "GitHub.cli", "gh"),
+    ("git", "Git.Git", "git"),
+    ("go", "GoLang.Go", "golang"),
+    ("jq", "jqlang.jq", "jq"),
+    ("kubectl", "Kubernetes.kubectl", "kubernetes-cli"),
+    ("make", "GnuWin32.Make", "make"),
+    ("node", "OpenJS.NodeJS", "nodejs"),
+    ("npm", "OpenJS.NodeJS", "nodejs"),
+    ("nvim", "Neovim.Neovim", "neovim"),
+    ("python", "Python.Python.3.12", "python"),
+    ("rustup", "Rustlang.Rustup", "rustup.install"),
+    ("terraform", "Hashicorp.Terraform", "terraform"),
+    ("vim", "vim.vim", "vim"),
+    ("wget", "JernejSimoncic.Wget", "wget"),
+];
+
+/// Installs well-known missing programs on Windows with winget or chocolatey,
+/// e.g. `git status` -> `winget install Git.Git && git status`
+pub struct WindowsCommandNotFound;
+
+/// Gets the program PowerShell or cmd reported as not recognized
+#[allow(clippy::type_complexity)]
+fn missing_program(result: &CommandResult) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"(?:The term )?'([^']+)' is not recognized as").expect("valid regex")
+    });
+    regex
+        .captures(&result.output())
+        .map(|captures| captures[1].trim_end_matches(".exe").to_lowercase())
+}
+
+/// Builds the install commands for the available package managers
+#[allow(clippy::type_complexity)]
+fn install_commands(script: &str, program: &str, winget: bool, choco: bool) -> Vec<String> {
+    let Some((_, winget_id, choco_name)) = PACKAGES.iter().find(|(name, _, _)| *name == program)
+    else {
+        return Vec::new();
+    };
+
+    let mut fixes: Vec<String> = Vec::new();
+    if winget {
+        fixes.push(format!("winget install {winget_id} && {script}"));
+    }
+    if choco {
+        fixes.push(format!("choco install {choco_name} && {script}"));
+    }
+    fixes
+}
+
+impl Rule for WindowsCommandNotFound {
+    fn name(&self) -> &'static str {
+        "windows_command_not_found"
+    }
+
+    fn description(&self) -> &'static str {
+        "Installs well-known missing programs on Windows with winget or chocolatey, e.g. `git status` -> `winget install Git.Git && git status`"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        cfg!(windows)
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        missing_program(result)
+            .is_some_and(|program| PACKAGES.iter().any(|(name, _, _)| *name == program))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(program) = missing_program(result) else {
+            return Vec::new();
+        };
+        let winget = utils::is_available("winget", command);
+        let choco = utils::is_available("choco", command);
+        // Suggest both when neither package manager can be found on PATH
+        let (winget, choco) = if winget || choco {
+            (winget, choco)
+        } else {
+            (true, true)
+        };
+        install_commands(command.trimmed(), &program, winget, choco)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const POWERSHELL_OUTPUT: &str = "git : The term 'git' is not recognized as the name of a \
+        cmdlet, function, script file, or operable program.\n";
+
+    const CMD_OUTPUT: &str = "'git' is not recognized as an internal or external command,\n\
+        operable program or batch file.\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("git status".to_string(), Shell::PowerShell);
+        for output in [POWERSHELL_OUTPUT, CMD_OUTPUT] {
+            let result = CommandResult::failure(1, output.to_string());
+            assert!(WindowsCommandNotFound.is_match(&command, &result));
+        }
+
+        let command = Command::new("frobnicate".to_string(), Shell::Cmd);
+        let result = CommandResult::failure(
+            1,
+            "'frobnicate' is not recognized as an internal or external command,\n".to_string(),
+        );
+        assert!(!WindowsCommandNotFound.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_install_commands() {
+        assert_eq!(
+            install_commands("git status", "git", true, true),
+            vec![
+                "winget install Git.Git && git status",
+                "choco install git && git status"
+            ]
+        );
+        assert_eq!(
+            install_commands("node app.js", "node", false, true),
+            vec!["choco install nodejs && node app.js"]
+        );
+        assert!(install_commands("frobnicate", "frobnicate", true, true).is_empty());
+    }
+}
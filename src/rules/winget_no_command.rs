@@ -0,0 +1,95 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Fixes misspelled winget commands using the command list winget prints,
+/// e.g. `winget isntall Git.Git` -> `winget install Git.Git`
+pub struct WingetNoCommand;
+
+#[allow(clippy::type_complexity)]
+fn unrecognized_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"Unrecognized command: '([^']+)'").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the commands listed after "The following commands are available:", without their help
+#[allow(clippy::type_complexity)]
+fn available_commands(output: &str) -> Vec<String> {
+    utils::extract_suggestions(output)
+        .iter()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+impl Rule for WingetNoCommand {
+    fn name(&self) -> &'static str {
+        "winget_no_command"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled winget commands using the command list winget prints, e.g. `winget isntall Git.Git` -> `winget install Git.Git`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        unrecognized_command(&result.output()).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        match unrecognized_command(&output) {
+            Some(broken) => {
+                utils::replace_command(command.trimmed(), &broken, &available_commands(&output))
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "Unrecognized command: 'isntall'\n\
+        Windows Package Manager v1.6.2771\nCopyright (c) Microsoft Corporation.\n\n\
+        The following commands are available:\n  install    Installs the given package\n  \
+        show       Shows information about a package\n  source     Manage sources of packages\n  \
+        search     Find and show basic info of packages\n  uninstall  Uninstalls the given package\n\n\
+        For more details on a specific command, pass it the help argument. [-?]\n";
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("winget isntall Git.Git".to_string(), Shell::PowerShell);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert!(WingetNoCommand.is_match(&command, &result));
+
+        let result = CommandResult::failure(1, "No package found\n".to_string());
+        assert!(!WingetNoCommand.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("winget isntall Git.Git".to_string(), Shell::PowerShell);
+        let result = CommandResult::failure(1, OUTPUT.to_string());
+        assert_eq!(
+            WingetNoCommand.get_new_commands(&command, &result)[0],
+            "winget install Git.Git"
+        );
+    }
+
+    #[test]
+    fn test_available_commands() {
+        assert_eq!(
+            available_commands(OUTPUT),
+            vec!["install", "show", "source", "search", "uninstall"]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Subcommands of well-known programs, checked before splitting their hyphenated form
+#[allow(clippy::type_complexity)]
+const KNOWN_SUBCOMMANDS: &[(&str, &[&str])] = &[
+    (
+        "apt",
+        &[
+            "autoremove",
+            "install",
+            "list",
+            "purge",
+            "reinstall",
+            "remove",
+            "search",
+            "show",
+            "update",
+            "upgrade",
+        ],
+    ),
+    (
+        "cargo",
+        &[
+            "add", "bench", "build", "check", "clean", "doc", "fmt", "init", "install", "new",
+            "publish", "remove", "run", "test", "update",
+        ],
+    ),
+    (
+        "git",
+        &[
+            "add",
+            "branch",
+            "checkout",
+            "cherry-pick",
+            "clone",
+            "commit",
+            "diff",
+            "fetch",
+            "init",
+            "log",
+            "merge",
+            "pull",
+            "push",
+            "rebase",
+            "reset",
+            "restore",
+            "stash",
+            "status",
+            "switch",
+            "tag",
+        ],
+    ),
+    (
+        "kubectl",
+        &[
+            "apply", "create", "delete", "describe", "edit", "exec", "explain", "expose", "get",
+            "logs", "patch", "rollout", "run", "scale",
+        ],
+    ),
+    (
+        "npm",
+        &[
+            "audit",
+            "ci",
+            "init",
+            "install",
+            "link",
+            "ls",
+            "publish",
+            "run",
+            "start",
+            "test",
+            "uninstall",
+            "update",
+        ],
+    ),
+];
+
+/// Replaces the hyphen between a program and its subcommand, e.g. `git-commit` -> `git commit`
+pub struct WrongHyphenBeforeSubcommand;
+
+/// Splits `git-commit` into `git` and `commit` when only the prefix is an executable and the
+/// rest is one of its known subcommands, so `ssh-keygen` never becomes `ssh keygen`
+#[allow(clippy::type_complexity)]
+fn split_program(program: &str, is_executable: impl Fn(&str) -> bool) -> Option<(&str, &str)> {
+    let (prefix, subcommand) = program.split_once('-')?;
+    if prefix.is_empty() || subcommand.is_empty() || is_executable(program) {
+        return None;
+    }
+    if !is_executable(prefix) {
+        return None;
+    }
+    let takes_subcommand = KNOWN_SUBCOMMANDS
+        .iter()
+        .find(|(name, _)| *name == prefix)
+        .is_some_and(|(_, subcommands)| subcommands.contains(&subcommand));
+    takes_subcommand.then_some((prefix, subcommand))
+}
+
+impl Rule for WrongHyphenBeforeSubcommand {
+    fn name(&self) -> &'static str {
+        "wrong_hyphen_before_subcommand"
+    }
+
+    fn description(&self) -> &'static str {
+        "Replaces the hyphen between a program and its subcommand, e.g. `git-commit` -> `git commit`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        result.output().contains("not found")
+            && command.program().is_some_and(|program| {
+                split_program(program, |name| utils::is_available(name, command)).is_some()
+            })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, _result: &CommandResult) -> Vec<String> {
+        let script = command.trimmed();
+        match command
+            .program()
+            .and_then(|program| split_program(program, |name| utils::is_available(name, command)))
+        {
+            Some((prefix, subcommand)) => {
+                let rest = &script[prefix.len() + 1 + subcommand.len()..];
+                vec![format!("{prefix} {subcommand}{rest}")]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    fn installed(name: &str) -> bool {
+        matches!(name, "git" | "apt" | "apt-get" | "kubectl" | "ssh")
+    }
+
+    #[test]
+    fn test_split_program() {
+        assert_eq!(
+            split_program("git-commit", installed),
+            Some(("git", "commit"))
+        );
+        assert_eq!(
+            split_program("apt-install", installed),
+            Some(("apt", "install"))
+        );
+        assert_eq!(
+            split_program("kubectl-get", installed),
+            Some(("kubectl", "get"))
+        );
+        assert_eq!(split_program("apt-get", installed), None);
+        assert_eq!(split_program("git-frobnicate", installed), None);
+        assert_eq!(split_program("npm-install", installed), None);
+        assert_eq!(split_program("git", installed), None);
+        assert_eq!(split_program("ssh-keygen", installed), None);
+    }
+
+    #[test]
+    fn test_no_match_for_hyphenated_tool() {
+        let command = Command::new("ssh-keygen -t ed25519".to_string(), Shell::Bash);
+        let result =
+            CommandResult::failure(127, "bash: ssh-keygen: command not found\n".to_string());
+        assert!(
+            WrongHyphenBeforeSubcommand
+                .get_new_commands(&command, &result)
+                .iter()
+                .all(|fix| fix != "ssh keygen -t ed25519")
+        );
+    }
+
+    #[test]
+    fn test_no_match_for_unknown_prefix() {
+        let command = Command::new("zzzz-install foo".to_string(), Shell::Bash);
+        let result =
+            CommandResult::failure(127, "bash: zzzz-install: command not found\n".to_string());
+        assert!(!WrongHyphenBeforeSubcommand.is_match(&command, &result));
+    }
+}
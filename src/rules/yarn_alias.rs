@@ -0,0 +1,76 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+
+/// Replaces the command yarn doesn't know with the one it suggests,
+/// e.g. `yarn rm lodash` -> `yarn remove lodash`
+pub struct YarnAlias;
+
+#[allow(clippy::type_complexity)]
+fn suggestion(result: &CommandResult) -> Option<String> {
+    let suggestion = utils::extract_suggestions(&result.output())
+        .into_iter()
+        .next()?;
+    Some(
+        suggestion
+            .strip_prefix("yarn ")
+            .unwrap_or(&suggestion)
+            .to_string(),
+    )
+}
+
+impl Rule for YarnAlias {
+    fn name(&self) -> &'static str {
+        "yarn_alias"
+    }
+
+    fn description(&self) -> &'static str {
+        "Replaces the command yarn doesn't know with the one it suggests, e.g. `yarn rm lodash` -> `yarn remove lodash`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        command.has_arguments() && suggestion(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        match (command.argument(0), suggestion(result)) {
+            (Some(broken), Some(fixed)) => {
+                vec![command.replace_argument(broken, &fixed)]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("yarn rm lodash".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, "error Did you mean `yarn remove`?\n".to_string());
+        assert!(YarnAlias.is_match(&command, &result));
+
+        let command = Command::new("yarn remove lodash".to_string(), Shell::Bash);
+        let result = CommandResult::success("success Uninstalled packages.\n".to_string());
+        assert!(!YarnAlias.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        for (script, fixed, expected) in [
+            ("yarn rm lodash", "remove", "yarn remove lodash"),
+            ("yarn ls", "list", "yarn list"),
+        ] {
+            let command = Command::new(script.to_string(), Shell::Bash);
+            let result = CommandResult::failure(1, format!("error Did you mean `yarn {fixed}`?\n"));
+            assert_eq!(
+                YarnAlias.get_new_commands(&command, &result),
+                vec![expected]
+            );
+        }
+    }
+}
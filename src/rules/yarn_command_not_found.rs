@@ -0,0 +1,163 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into yarn
+#[allow(clippy::type_complexity)]
+const YARN_COMMANDS: &[&str] = &[
+    "access",
+    "add",
+    "audit",
+    "autoclean",
+    "bin",
+    "cache",
+    "check",
+    "config",
+    "create",
+    "exec",
+    "generate-lock-entry",
+    "global",
+    "help",
+    "import",
+    "info",
+    "init",
+    "install",
+    "licenses",
+    "link",
+    "list",
+    "login",
+    "logout",
+    "node",
+    "outdated",
+    "owner",
+    "pack",
+    "policies",
+    "publish",
+    "remove",
+    "run",
+    "tag",
+    "team",
+    "unlink",
+    "unplug",
+    "upgrade",
+    "upgrade-interactive",
+    "version",
+    "versions",
+    "why",
+    "workspace",
+    "workspaces",
+];
+
+/// npm commands that have a differently named yarn equivalent
+#[allow(clippy::type_complexity)]
+const NPM_EQUIVALENTS: &[(&str, &str)] = &[("require", "add"), ("uninstall", "remove")];
+
+/// Fixes misspelled yarn commands and package.json scripts,
+/// e.g. `yarn buidl` -> `yarn build`
+pub struct YarnCommandNotFound;
+
+#[allow(clippy::type_complexity)]
+fn misspelled_command(result: &CommandResult) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r#"error Command "(.*)" not found"#).expect("valid regex"));
+    regex
+        .captures(&result.output())
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for YarnCommandNotFound {
+    fn name(&self) -> &'static str {
+        "yarn_command_not_found"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled yarn commands and package.json scripts, e.g. `yarn buidl` -> `yarn build`"
+    }
+
+    fn is_match(&self, _command: &Command, result: &CommandResult) -> bool {
+        misspelled_command(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let Some(misspelled) = misspelled_command(result) else {
+            return Vec::new();
+        };
+        if let Some((_, yarn_command)) = NPM_EQUIVALENTS.iter().find(|(npm, _)| *npm == misspelled)
+        {
+            return vec![command.replace_argument(&misspelled, yarn_command)];
+        }
+
+        let mut fixes: Vec<String> = Vec::new();
+        let scripts = utils::package_json_scripts(&command.cwd);
+        for candidates in [
+            YARN_COMMANDS.iter().map(|c| c.to_string()).collect(),
+            scripts,
+        ] {
+            if let Some(fixed) = utils::get_closest(&misspelled, &candidates) {
+                let fix = command.replace_argument(&misspelled, &fixed);
+                if !fixes.contains(&fix) {
+                    fixes.push(fix);
+                }
+            }
+        }
+        fixes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    fn not_found(command: &str) -> CommandResult {
+        CommandResult::failure(
+            1,
+            format!("yarn run v1.22.19\nerror Command \"{command}\" not found.\n"),
+        )
+    }
+
+    #[test]
+    fn test_match() {
+        let command = Command::new("yarn whyy webpack".to_string(), Shell::Bash);
+        assert!(YarnCommandNotFound.is_match(&command, &not_found("whyy")));
+
+        let command = Command::new("yarn why webpack".to_string(), Shell::Bash);
+        let result = CommandResult::success("=> Found \"webpack@5.0.0\"\n".to_string());
+        assert!(!YarnCommandNotFound.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("yarn whyy webpack".to_string(), Shell::Bash);
+        assert_eq!(
+            YarnCommandNotFound.get_new_commands(&command, &not_found("whyy")),
+            vec!["yarn why webpack"]
+        );
+
+        let command = Command::new("yarn require lodash".to_string(), Shell::Bash);
+        assert_eq!(
+            YarnCommandNotFound.get_new_commands(&command, &not_found("require")),
+            vec!["yarn add lodash"]
+        );
+    }
+
+    #[test]
+    fn test_get_new_commands_with_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"lint": "eslint .", "links": "lychee ."}}"#,
+        )
+        .unwrap();
+        let command = Command::new("yarn linkk".to_string(), Shell::Bash)
+            .with_cwd(dir.path().to_string_lossy().to_string());
+        assert_eq!(
+            YarnCommandNotFound.get_new_commands(&command, &not_found("linkk")),
+            vec!["yarn link", "yarn links"]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Opens the documentation yarn points to from `yarn help <command>`
+pub struct YarnHelp;
+
+#[allow(clippy::type_complexity)]
+fn documentation_url(result: &CommandResult) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"Visit (\S+) for documentation about this command").expect("valid regex")
+    });
+    regex
+        .captures(&result.output())
+        .map(|captures| captures[1].to_string())
+}
+
+impl Rule for YarnHelp {
+    fn name(&self) -> &'static str {
+        "yarn_help"
+    }
+
+    fn description(&self) -> &'static str {
+        "Opens the documentation yarn points to from `yarn help <command>`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        command.argument(0) == Some("help") && documentation_url(result).is_some()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, _command: &Command, result: &CommandResult) -> Vec<String> {
+        documentation_url(result)
+            .map(|url| utils::open_command(&url))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const OUTPUT: &str = "\n  Usage: yarn [command] [flags]\n\n  Options:\n\n    -h, --help  output usage information\n\
+        \n  Visit https://yarnpkg.com/en/docs/cli/clean for documentation about this command.\n";
+
+    #[test]
+    fn test_match() {
+        let result = CommandResult::success(OUTPUT.to_string());
+        let command = Command::new("yarn help clean".to_string(), Shell::Bash);
+        assert!(YarnHelp.is_match(&command, &result));
+
+        let command = Command::new("yarn clean".to_string(), Shell::Bash);
+        assert!(!YarnHelp.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let result = CommandResult::success(OUTPUT.to_string());
+        let command = Command::new("yarn help clean".to_string(), Shell::Bash);
+        assert_eq!(
+            YarnHelp.get_new_commands(&command, &result),
+            vec![utils::open_command("https://yarnpkg.com/en/docs/cli/clean")]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use crate::rules::Rule;
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Commands built into zypper
+#[allow(clippy::type_complexity)]
+const ZYPPER_COMMANDS: &[&str] = &[
+    "addlock",
+    "addrepo",
+    "addservice",
+    "clean",
+    "cleanlocks",
+    "dist-upgrade",
+    "download",
+    "info",
+    "install",
+    "install-new-recommends",
+    "licenses",
+    "list-patches",
+    "list-updates",
+    "locks",
+    "modifyrepo",
+    "modifyservice",
+    "needs-rebooting",
+    "packages",
+    "patch",
+    "patch-check",
+    "patches",
+    "patterns",
+    "products",
+    "ps",
+    "purge-kernels",
+    "refresh",
+    "refresh-services",
+    "remove",
+    "removelock",
+    "removerepo",
+    "removeservice",
+    "renamerepo",
+    "repos",
+    "search",
+    "search-packages",
+    "services",
+    "source-download",
+    "source-install",
+    "update",
+    "verify",
+    "what-provides",
+];
+
+/// Fixes misspelled zypper commands and installs missing programs on openSUSE,
+/// e.g. `zypper isntall vim` -> `zypper install vim`, `htop` -> `sudo zypper install htop && htop`
+pub struct Zypper;
+
+#[allow(clippy::type_complexity)]
+fn unknown_command(output: &str) -> Option<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"Unknown command '([^']+)'").expect("valid regex"));
+    regex
+        .captures(output)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Gets the packages the command-not-found handler says provide the missing program
+#[allow(clippy::type_complexity)]
+fn providing_packages(output: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"(?m)^\s*(?:\* (\S+) \[ path:|(?:sudo )?zypper (?:install|in) (\S+))")
+            .expect("valid regex")
+    });
+    let mut packages: Vec<String> = Vec::new();
+    for captures in regex.captures_iter(output) {
+        let package = captures
+            .get(1)
+            .or_else(|| captures.get(2))
+            .map(|m| m.as_str());
+        if let Some(package) = package.filter(|p| !packages.iter().any(|known| known == p)) {
+            packages.push(package.to_string());
+        }
+    }
+    packages
+}
+
+impl Rule for Zypper {
+    fn name(&self) -> &'static str {
+        "zypper"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixes misspelled zypper commands and installs missing programs on openSUSE, e.g. `zypper isntall vim` -> `zypper install vim`, `htop` -> `sudo zypper install htop && htop`"
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        // Missing programs are any command, so only systems with zypper are considered
+        if !utils::is_available("zypper", command) {
+            return false;
+        }
+        let output = result.output();
+        if command.program() == Some("zypper") {
+            unknown_command(&output).is_some()
+        } else {
+            !providing_packages(&output).is_empty()
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let output = result.output();
+        let script = command.trimmed();
+        if let Some(broken) = unknown_command(&output) {
+            return utils::replace_command(script, &broken, ZYPPER_COMMANDS);
+        }
+        providing_packages(&output)
+            .iter()
+            .map(|package| format!("sudo zypper install {package} && {script}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::sudo_support;
+    use crate::types::Shell;
+    use crate::utils::resolve::FUNCTIONS_ENV;
+    use std::collections::HashMap;
+
+    /// Gets a command run where the shell has a `zypper`
+    #[allow(clippy::type_complexity)]
+    fn on_opensuse(script: &str) -> Command {
+        Command::new(script.to_string(), Shell::Bash).with_env(HashMap::from([(
+            FUNCTIONS_ENV.to_string(),
+            "zypper".to_string(),
+        )]))
+    }
+
+    const UNKNOWN_OUTPUT: &str = "Unknown command 'isntall'\n\n  Usage:\n\tzypper [--global-options] \
+        <command> [--command-options] [arguments]\n";
+
+    const NOT_FOUND_OUTPUT: &str = "\nThe program 'htop' can be found in the following package:\n  \
+        * htop [ path: /usr/bin/htop, repository: zypp (repo-oss) ]\n\n\
+        Try installing with:\n    sudo zypper install htop\n\nhtop: command not found\n";
+
+    #[test]
+    fn test_match() {
+        let command = on_opensuse("sudo zypper isntall vim");
+        let result = CommandResult::failure(1, UNKNOWN_OUTPUT.to_string());
+        assert!(sudo_support(Zypper).is_match(&command, &result));
+
+        let command = on_opensuse("htop");
+        let result = CommandResult::failure(127, NOT_FOUND_OUTPUT.to_string());
+        assert!(Zypper.is_match(&command, &result));
+
+        let empty = tempfile::tempdir().unwrap();
+        let elsewhere = Command::new("htop".to_string(), Shell::Bash).with_env(HashMap::from([(
+            "PATH".to_string(),
+            empty.path().to_string_lossy().to_string(),
+        )]));
+        assert!(!Zypper.is_match(&elsewhere, &result));
+
+        let result = CommandResult::failure(127, "htop: command not found\n".to_string());
+        assert!(!Zypper.is_match(&command, &result));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let command = Command::new("sudo zypper isntall vim".to_string(), Shell::Bash);
+        let result = CommandResult::failure(1, UNKNOWN_OUTPUT.to_string());
+        assert_eq!(
+            sudo_support(Zypper).get_new_commands(&command, &result)[0],
+            "sudo zypper install vim"
+        );
+
+        let command = Command::new("htop -d 10".to_string(), Shell::Bash);
+        let result = CommandResult::failure(127, NOT_FOUND_OUTPUT.to_string());
+        assert_eq!(
+            Zypper.get_new_commands(&command, &result),
+            vec!["sudo zypper install htop && htop -d 10"]
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! User rules written in [Rhai](https://rhai.rs).
+//!
+//! Each `*.rhai` file in the rules directory is loaded as one rule named after the file stem.
+//! A rule script defines:
+//!
+//! - `is_match(command, output)`: whether the rule applies to the failed command (`match` is a
+//!   reserved word in Rhai, so it can't be used as the function name)
+//! - `get_new_command(command)` or `get_new_command(command, output)`: the corrected command,
+//!   or an array of them, most likely first
+//! - `priority()` (optional): the priority of the corrections
+//!
+//! Scripts can call `replace_argument(script, old, new)`, `replace_all_arguments(script, old,
+//! new)`, `replace_command(script, broken, possibilities)`, `get_close_matches(word,
+//! possibilities)` and `get_closest(word, possibilities)`. They have no access to the file system or processes, and their operations,
+//! call depth and data sizes are bounded.
+
+use crate::rules::{DEFAULT_PRIORITY, Rule};
+use crate::types::{Command, CommandResult};
+use crate::utils;
+use crate::{TheFuckError, TheFuckResult};
+use rhai::{AST, Array, Dynamic, Engine, Scope};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Extension of rule scripts in the rules directory
+pub const SCRIPT_EXTENSION: &str = "rhai";
+
+/// Maximum number of operations of a single call into a script
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Converts a script array to strings, dropping anything that isn't a string
+#[allow(clippy::type_complexity)]
+fn strings(array: Array) -> Vec<String> {
+    array
+        .into_iter()
+        .filter_map(|value| value.into_string().ok())
+        .collect()
+}
+
+/// Converts strings to a script array
+#[allow(clippy::type_complexity)]
+fn array(strings: Vec<String>) -> Array {
+    strings.into_iter().map(Dynamic::from).collect()
+}
+
+/// Creates a sandboxed engine with the rule helpers registered
+fn script_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(1 << 20)
+        .set_max_array_size(10_000)
+        .set_max_map_size(10_000);
+    // Whatever is printed to stdout is evaluated by the shell alias
+    engine.on_print(|text| tracing::info!("{text}"));
+    engine.on_debug(|text, _, _| tracing::debug!("{text}"));
+
+    // Scripts pass replacements as written, often several words, like the original thefuck
+    engine.register_fn("replace_argument", |script: &str, old: &str, new: &str| {
+        utils::replace_argument_words(script, old, new)
+    });
+    engine.register_fn(
+        "replace_all_arguments",
+        |script: &str, old: &str, new: &str| utils::replace_all_argument_words(script, old, new),
+    );
+    engine.register_fn(
+        "replace_command",
+        |script: &str, broken: &str, possibilities: Array| {
+            array(utils::replace_command(
+                script,
+                broken,
+                &strings(possibilities),
+            ))
+        },
+    );
+    engine.register_fn("get_close_matches", |word: &str, possibilities: Array| {
+        array(utils::get_close_matches(
+            word,
+            &strings(possibilities),
+            utils::DEFAULT_MATCHES,
+            utils::DEFAULT_CUTOFF,
+        ))
+    });
+    engine.register_fn("get_closest", |word: &str, possibilities: Array| {
+        utils::get_closest(word, &strings(possibilities)).map_or(Dynamic::UNIT, Dynamic::from)
+    });
+    engine
+}
+
+/// Compiles rule scripts with a shared, sandboxed engine
+pub struct ScriptLoader {
+    #[allow(clippy::type_complexity)]
+    engine: Arc<Engine>,
+}
+
+impl Default for ScriptLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptLoader {
+    /// Creates a loader
+    pub fn new() -> Self {
+        Self {
+            engine: Arc::new(script_engine()),
+        }
+    }
+
+    /// Loads a rule from the source of a script
+    #[allow(clippy::type_complexity)]
+    pub fn load(&self, name: &str, source: &str) -> TheFuckResult<ScriptRule> {
+        let ast = self
+            .engine
+            .compile(source)
+            .map_err(|e| TheFuckError::plugin_error(format!("{name}: {e}")))?;
+        let arity = |function: &str| {
+            ast.iter_functions()
+                .find(|metadata| metadata.name == function)
+                .map(|metadata| metadata.params.len())
+        };
+        if arity("is_match") != Some(2) {
+            return Err(TheFuckError::plugin_error(format!(
+                "{name}: missing `is_match(command, output)`"
+            )));
+        }
+        let new_command_takes_output = match arity("get_new_command") {
+            Some(1) => false,
+            Some(2) => true,
+            _ => {
+                return Err(TheFuckError::plugin_error(format!(
+                    "{name}: missing `get_new_command(command)`"
+                )));
+            }
+        };
+        let has_priority = arity("priority") == Some(0);
+
+        let mut rule = ScriptRule {
+            // Rule names are static, and scripts are loaded once per process
+            name: Box::leak(name.to_string().into_boxed_str()),
+            priority: DEFAULT_PRIORITY,
+            new_command_takes_output,
+            engine: Arc::clone(&self.engine),
+            ast,
+        };
+        if has_priority {
+            let priority = rule
+                .engine
+                .call_fn::<rhai::INT>(&mut Scope::new(), &rule.ast, "priority", ())
+                .map_err(|e| TheFuckError::plugin_error(format!("{name}: {e}")))?;
+            rule.priority = priority.clamp(0, u32::MAX as rhai::INT) as u32;
+        }
+        Ok(rule)
+    }
+
+    /// Loads a rule from a script file, naming it after the file stem
+    #[allow(clippy::type_complexity)]
+    pub fn load_file(&self, path: &Path) -> TheFuckResult<ScriptRule> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .ok_or_else(|| TheFuckError::path_error(path.display().to_string()))?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| TheFuckError::FileReadError(format!("{}: {e}", path.display())))?;
+        self.load(&name, &source)
+    }
+
+    /// Loads every rule script in a directory, sorted by file name and skipping invalid ones
+    #[allow(clippy::type_complexity)]
+    pub fn load_dir(&self, dir: &Path) -> Vec<ScriptRule> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        #[allow(clippy::type_complexity)]
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension == SCRIPT_EXTENSION)
+            })
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .filter_map(|path| match self.load_file(path) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    tracing::warn!("skipping rule script {}: {e}", path.display());
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// A rule implemented by a Rhai script
+pub struct ScriptRule {
+    name: &'static str,
+    priority: u32,
+    new_command_takes_output: bool,
+    #[allow(clippy::type_complexity)]
+    engine: Arc<Engine>,
+    ast: AST,
+}
+
+impl Rule for ScriptRule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    fn is_match(&self, command: &Command, result: &CommandResult) -> bool {
+        let args = (command.text.clone(), result.output());
+        match self
+            .engine
+            .call_fn::<bool>(&mut Scope::new(), &self.ast, "is_match", args)
+        {
+            Ok(matched) => matched,
+            Err(e) => {
+                tracing::warn!("rule script {} failed to match: {e}", self.name);
+                false
+            }
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn get_new_commands(&self, command: &Command, result: &CommandResult) -> Vec<String> {
+        let mut scope = Scope::new();
+        let new_command = if self.new_command_takes_output {
+            let args = (command.text.clone(), result.output());
+            self.engine
+                .call_fn::<Dynamic>(&mut scope, &self.ast, "get_new_command", args)
+        } else {
+            let args = (command.text.clone(),);
+            self.engine
+                .call_fn::<Dynamic>(&mut scope, &self.ast, "get_new_command", args)
+        };
+        let new_command = match new_command {
+            Ok(new_command) => new_command,
+            Err(e) => {
+                tracing::warn!("rule script {} failed to correct: {e}", self.name);
+                return Vec::new();
+            }
+        };
+
+        #[allow(clippy::type_complexity)]
+        let commands = if new_command.is_array() {
+            new_command.into_array().map(strings).unwrap_or_default()
+        } else {
+            new_command.into_string().into_iter().collect()
+        };
+        commands
+            .into_iter()
+            .filter(|command| !command.trim().is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    const GIT_PUSH: &str = r#"
+        fn is_match(command, output) {
+            command.starts_with("git push") && output.contains("git push --set-upstream")
+        }
+
+        fn get_new_command(command, output) {
+            let index = output.index_of("git push --set-upstream");
+            let line = output.sub_string(index).split("\n")[0];
+            replace_argument(command, "push", line.sub_string(4))
+        }
+
+        fn priority() { 1200 }
+    "#;
+
+    const GIT_TYPO: &str = r#"
+        fn is_match(command, output) { output.contains("is not a git command") }
+
+        fn get_new_command(command) {
+            let broken = command.split(" ")[1];
+            replace_command(command, broken, ["push", "pull", "status"])
+        }
+    "#;
+
+    const PUSH_OUTPUT: &str = "fatal: The current branch feature has no upstream branch.\n\
+        To push the current branch and set the remote as upstream, use\n\n    \
+        git push --set-upstream origin feature\n";
+
+    #[allow(clippy::type_complexity)]
+    fn git(text: &str) -> Command {
+        Command::new(text.to_string(), Shell::Bash)
+    }
+
+    #[test]
+    fn test_load() {
+        let loader = ScriptLoader::new();
+        let rule = loader.load("git_push", GIT_PUSH).unwrap();
+        assert_eq!(rule.name(), "git_push");
+        assert_eq!(rule.priority(), 1200);
+        assert_eq!(
+            loader.load("git_typo", GIT_TYPO).unwrap().priority(),
+            DEFAULT_PRIORITY
+        );
+
+        assert!(matches!(
+            loader.load("missing", "fn is_match(command, output) { true }"),
+            Err(TheFuckError::PluginError(_))
+        ));
+        assert!(loader.load("invalid", "fn is_match(").is_err());
+    }
+
+    #[test]
+    fn test_match() {
+        let rule = ScriptLoader::new().load("git_push", GIT_PUSH).unwrap();
+        let result = CommandResult::failure(128, PUSH_OUTPUT.to_string());
+        assert!(rule.is_match(&git("git push"), &result));
+        assert!(!rule.is_match(&git("git pull"), &result));
+        assert!(!rule.is_match(&git("git push"), &CommandResult::failure(1, String::new())));
+    }
+
+    #[test]
+    fn test_get_new_commands() {
+        let loader = ScriptLoader::new();
+        let rule = loader.load("git_push", GIT_PUSH).unwrap();
+        let result = CommandResult::failure(128, PUSH_OUTPUT.to_string());
+        assert_eq!(
+            rule.get_new_commands(&git("git push"), &result),
+            vec!["git push --set-upstream origin feature"]
+        );
+
+        let rule = loader.load("git_typo", GIT_TYPO).unwrap();
+        let result = CommandResult::failure(1, "'psuh' is not a git command".to_string());
+        assert_eq!(
+            rule.get_new_commands(&git("git psuh"), &result)[0],
+            "git push"
+        );
+    }
+
+    #[test]
+    fn test_runaway_script() {
+        let script = "fn is_match(command, output) { loop {} }\n\
+            fn get_new_command(command) { command }";
+        let rule = ScriptLoader::new().load("spin", script).unwrap();
+        let result = CommandResult::failure(1, String::new());
+        assert!(!rule.is_match(&git("git psuh"), &result));
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("git_push.rhai"), GIT_PUSH).unwrap();
+        std::fs::write(dir.path().join("broken.rhai"), "fn is_match(").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), GIT_PUSH).unwrap();
+
+        let loader = ScriptLoader::new();
+        #[allow(clippy::type_complexity)]
+        let names: Vec<&str> = loader
+            .load_dir(dir.path())
+            .iter()
+            .map(|rule| rule.name())
+            .collect();
+        assert_eq!(names, vec!["git_push"]);
+        assert!(loader.load_dir(&dir.path().join("missing")).is_empty());
+    }
+}
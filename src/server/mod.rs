@@ -0,0 +1,323 @@
+//! JSON-RPC server for editor and IDE integrations.
+//!
+//! `thefuck-rs serve --stdio` reads JSON-RPC 2.0 requests from stdin, one per line, and writes
+//! each response on a line of stdout, so terminal plugins of VS Code or Neovim can show the
+//! corrections of a failed command inline. Its methods are:
+//!
+//! - `correct`: takes a correction request, e.g. `{"command": "git psuh", "stderr": "..."}`,
+//!   and returns its corrections, most likely first
+//! - `explain`: same as `correct`, with why each correction was suggested
+//! - `listRules`: returns every rule, whether it's enabled, its priority and its programs
+//!
+//! Requests without an `id` are notifications and get no response.
+
+use crate::config::Settings;
+use crate::core::CorrectionRequest;
+use crate::engine::CorrectionEngine;
+use crate::guard::{Guard, Verdict};
+use crate::rules::RuleInfo;
+use crate::types::{CorrectedCommand, Explanation};
+use crate::{TheFuckError, TheFuckResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+/// The request couldn't be parsed as JSON
+pub const PARSE_ERROR: i64 = -32700;
+
+/// The request isn't a JSON-RPC request
+pub const INVALID_REQUEST: i64 = -32600;
+
+/// The requested method doesn't exist
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The parameters don't fit the method
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// The server failed to answer
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// A JSON-RPC request or notification
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// Identifier the response is sent with, none for notifications
+    #[serde(default)]
+    #[allow(clippy::type_complexity)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Why a request failed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// A correction, as sent to the editor
+#[derive(Debug, Serialize)]
+struct Correction<'a> {
+    text: &'a str,
+    rule: &'a str,
+    priority: u32,
+    #[allow(clippy::type_complexity)]
+    side_effects: &'a [String],
+    /// Why the command should be confirmed before it runs, if it's dangerous
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::type_complexity)]
+    danger: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::type_complexity)]
+    explanation: Option<&'a Explanation>,
+}
+
+/// Serializes the result of a method
+#[allow(clippy::type_complexity)]
+fn to_result(value: impl Serialize) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))
+}
+
+/// Answers the requests of an editor with a correction engine
+pub struct Server {
+    engine: CorrectionEngine,
+    settings: Settings,
+    guard: Guard,
+}
+
+impl Server {
+    /// Creates a server, failing if the settings' guard patterns are invalid
+    #[allow(clippy::type_complexity)]
+    pub fn new(engine: CorrectionEngine, settings: Settings) -> TheFuckResult<Self> {
+        let guard = Guard::new(&settings.guard)?;
+        Ok(Self {
+            engine,
+            settings,
+            guard,
+        })
+    }
+
+    /// Creates a server with the user's settings and rules, ranking by their previous picks
+    #[allow(clippy::type_complexity)]
+    pub fn load() -> TheFuckResult<Self> {
+        let settings = Settings::load()?;
+        let builder = || {
+            CorrectionEngine::builder()
+                .settings(settings.clone())
+                .user_rules(true)
+        };
+        let engine = builder().learning(true).build().or_else(|e| {
+            tracing::warn!("failed to open the learning store: {e}");
+            builder().build()
+        })?;
+        Self::new(engine, settings)
+    }
+
+    /// Answers requests, one per line, until the input ends
+    #[allow(clippy::type_complexity)]
+    pub fn serve(&self, input: impl BufRead, mut output: impl Write) -> TheFuckResult<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle(&line) {
+                writeln!(output, "{response}")?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Answers one request, or nothing if it's a notification
+    #[allow(clippy::type_complexity)]
+    pub fn handle(&self, line: &str) -> Option<String> {
+        let (id, outcome) = match serde_json::from_str::<Value>(line) {
+            Err(e) => (Value::Null, Err(RpcError::new(PARSE_ERROR, e.to_string()))),
+            Ok(message) => match serde_json::from_value::<Request>(message) {
+                Err(e) => (
+                    Value::Null,
+                    Err(RpcError::new(INVALID_REQUEST, e.to_string())),
+                ),
+                Ok(request) => {
+                    let outcome = self.call(&request.method, request.params);
+                    (request.id?, outcome)
+                }
+            },
+        };
+        let response = match outcome {
+            Ok(result) => serde_json::json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(error) => serde_json::json!({"jsonrpc": "2.0", "id": id, "error": error}),
+        };
+        Some(response.to_string())
+    }
+
+    /// Runs a method
+    #[allow(clippy::type_complexity)]
+    fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "correct" => {
+                let corrections = self.correct(params)?;
+                to_result(self.describe(&corrections, false))
+            }
+            "explain" => {
+                let corrections = self.correct(params)?;
+                to_result(self.describe(&corrections, true))
+            }
+            "listRules" => to_result(self.rules()),
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method: {method}"),
+            )),
+        }
+    }
+
+    /// Gets the corrections of the failed command in a request's parameters
+    #[allow(clippy::type_complexity)]
+    fn correct(&self, params: Value) -> Result<Vec<CorrectedCommand>, RpcError> {
+        let request: CorrectionRequest = serde_json::from_value(params)
+            .map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?;
+        if request.command.trim().is_empty() {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                TheFuckError::parse_error("No command provided to fix").to_string(),
+            ));
+        }
+        Ok(self.guard.filter(self.engine.correct_request(&request)))
+    }
+
+    /// Describes corrections to the editor, with why they were suggested if asked
+    #[allow(clippy::type_complexity)]
+    fn describe<'a>(
+        &self,
+        corrections: &'a [CorrectedCommand],
+        explain: bool,
+    ) -> Vec<Correction<'a>> {
+        corrections
+            .iter()
+            .map(|correction| Correction {
+                text: &correction.text,
+                rule: &correction.rule,
+                priority: correction.priority,
+                side_effects: &correction.side_effects,
+                danger: match self.guard.verdict(&correction.text) {
+                    Verdict::Confirm(danger) => Some(danger.reason),
+                    _ => None,
+                },
+                explanation: if explain {
+                    correction.explanation.as_ref()
+                } else {
+                    None
+                },
+            })
+            .collect()
+    }
+
+    /// Describes every rule of the engine, sorted by name
+    #[allow(clippy::type_complexity)]
+    fn rules(&self) -> Vec<RuleInfo> {
+        let mut rules: Vec<RuleInfo> = self
+            .engine
+            .registry()
+            .rules()
+            .iter()
+            .map(|rule| RuleInfo::new(rule.as_ref(), &self.settings))
+            .collect();
+        rules.sort_by(|a, b| a.name.cmp(&b.name));
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Shell;
+
+    fn server() -> Server {
+        let engine = CorrectionEngine::builder()
+            .rules(["terraform_no_command", "az_cli"])
+            .shell(Shell::Bash)
+            .build()
+            .unwrap();
+        Server::new(engine, Settings::default()).unwrap()
+    }
+
+    fn call(server: &Server, request: &str) -> Value {
+        serde_json::from_str(&server.handle(request).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_correct() {
+        let server = server();
+        let params = serde_json::json!({
+            "command": "terraform pla",
+            "stderr": "Terraform has no command named \"pla\". Did you mean \"plan\"?",
+        });
+        let request = |id: Value, method: &str| {
+            serde_json::json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})
+                .to_string()
+        };
+
+        let response = call(&server, &request(1.into(), "correct"));
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"][0]["text"], "terraform plan");
+        assert_eq!(response["result"][0]["rule"], "terraform_no_command");
+        assert!(response["result"][0].get("explanation").is_none());
+
+        let response = call(&server, &request("a".into(), "explain"));
+        assert_eq!(response["id"], "a");
+        assert_eq!(
+            response["result"][0]["explanation"]["rule"],
+            "terraform_no_command"
+        );
+    }
+
+    #[test]
+    fn test_list_rules() {
+        let response = call(
+            &server(),
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "listRules"}"#,
+        );
+        assert_eq!(response["result"][0]["name"], "az_cli");
+        assert_eq!(response["result"][1]["name"], "terraform_no_command");
+    }
+
+    #[test]
+    fn test_errors() {
+        let server = server();
+        assert_eq!(call(&server, "{")["error"]["code"], PARSE_ERROR);
+        assert_eq!(call(&server, "[]")["error"]["code"], INVALID_REQUEST);
+        assert_eq!(
+            call(&server, r#"{"id": 3, "method": "fix"}"#)["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            call(
+                &server,
+                r#"{"id": 4, "method": "correct", "params": {"command": " "}}"#
+            )["error"]["code"],
+            INVALID_PARAMS
+        );
+        assert_eq!(server.handle(r#"{"method": "listRules"}"#), None);
+    }
+
+    #[test]
+    fn test_serve() {
+        let input = "{\"id\": 1, \"method\": \"listRules\"}\n\n{\"method\": \"listRules\"}\n";
+        let mut output = Vec::new();
+        server().serve(input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains("\"az_cli\""));
+    }
+}
@@ -0,0 +1,36 @@
+# Captures the exit status and output of each command, so thefuck-rs needn't run it again.
+# Install with: eval "$(thefuck-rs hook bash)"
+
+# mktemp picks an unpredictable name and creates the directory private to the user
+export TF_CAPTURE_DIR="$(mktemp -d "${XDG_RUNTIME_DIR:-${TMPDIR:-/tmp}}/thefuck-rs.XXXXXX")"
+[ -n "$TF_CAPTURE_DIR" ] && [ -O "$TF_CAPTURE_DIR" ] || unset TF_CAPTURE_DIR
+# Interactive programs need a terminal, so their output isn't captured
+: "${TF_CAPTURE_EXCLUDE:=vi vim nvim nano emacs less more man ssh top htop tmux screen}"
+
+__thefuck_preexec() {
+    [ -n "$__thefuck_ready" ] && [ -n "$TF_CAPTURE_DIR" ] || return 0
+    unset __thefuck_ready
+    case "$BASH_COMMAND" in *thefuck-rs*)
+        # Lets thefuck-rs tell aliases and functions apart from missing programs
+        export TF_SHELL_ALIASES="$(alias)" TF_SHELL_FUNCTIONS="$(compgen -A function)"
+        return 0 ;;
+    esac
+    rm -f "$TF_CAPTURE_DIR/command" "$TF_CAPTURE_DIR/exit_code" "$TF_CAPTURE_DIR/output"
+    case " $TF_CAPTURE_EXCLUDE " in *" ${BASH_COMMAND%% *} "*) return 0 ;; esac
+    exec {__thefuck_stdout}>&1 {__thefuck_stderr}>&2 > >(tee "$TF_CAPTURE_DIR/output") 2>&1
+    __thefuck_capturing=1
+}
+
+__thefuck_precmd() {
+    local exit_code=$?
+    if [ -n "$__thefuck_capturing" ]; then
+        exec 1>&$__thefuck_stdout 2>&$__thefuck_stderr {__thefuck_stdout}>&- {__thefuck_stderr}>&-
+        unset __thefuck_capturing
+        fc -ln -1 > "$TF_CAPTURE_DIR/command"
+        printf '%s' "$exit_code" > "$TF_CAPTURE_DIR/exit_code"
+    fi
+    return $exit_code
+}
+
+trap '__thefuck_preexec' DEBUG
+PROMPT_COMMAND="__thefuck_precmd${PROMPT_COMMAND:+; $PROMPT_COMMAND}; __thefuck_ready=1"
@@ -0,0 +1,25 @@
+# Records the exit status of each command for thefuck-rs. Fish can't redirect its own output,
+# so the output is still captured by running the command again.
+# Install with: thefuck-rs hook fish | source
+
+# mktemp picks an unpredictable name and creates the directory private to the user
+set -l __thefuck_tmp /tmp
+set -q TMPDIR; and set __thefuck_tmp $TMPDIR
+set -q XDG_RUNTIME_DIR; and set __thefuck_tmp $XDG_RUNTIME_DIR
+set -gx TF_CAPTURE_DIR (mktemp -d $__thefuck_tmp/thefuck-rs.XXXXXX)
+test -n "$TF_CAPTURE_DIR" -a -O "$TF_CAPTURE_DIR"; or set -e TF_CAPTURE_DIR
+
+# Lets thefuck-rs tell functions, which fish aliases are, apart from missing programs
+function __thefuck_preexec --on-event fish_preexec
+    string match -q '*thefuck-rs*' -- $argv[1]; or return
+    set -gx TF_SHELL_FUNCTIONS (string join \n (functions -n))
+end
+
+function __thefuck_postexec --on-event fish_postexec
+    set -l exit_code $status
+    string match -q '*thefuck-rs*' -- $argv[1]; and return
+    set -q TF_CAPTURE_DIR; or return
+    rm -f $TF_CAPTURE_DIR/command $TF_CAPTURE_DIR/exit_code $TF_CAPTURE_DIR/output
+    printf '%s' $argv[1] > $TF_CAPTURE_DIR/command
+    printf '%s' $exit_code > $TF_CAPTURE_DIR/exit_code
+end
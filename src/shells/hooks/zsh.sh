@@ -0,0 +1,34 @@
+# Captures the exit status and output of each command, so thefuck-rs needn't run it again.
+# Install with: eval "$(thefuck-rs hook zsh)"
+
+# mktemp picks an unpredictable name and creates the directory private to the user
+export TF_CAPTURE_DIR="$(mktemp -d "${XDG_RUNTIME_DIR:-${TMPDIR:-/tmp}}/thefuck-rs.XXXXXX")"
+[[ -n "$TF_CAPTURE_DIR" && -O "$TF_CAPTURE_DIR" ]] || unset TF_CAPTURE_DIR
+# Interactive programs need a terminal, so their output isn't captured
+: "${TF_CAPTURE_EXCLUDE:=vi vim nvim nano emacs less more man ssh top htop tmux screen}"
+
+__thefuck_preexec() {
+    if [[ "$3" == *thefuck-rs* ]]; then
+        # Lets thefuck-rs tell aliases and functions apart from missing programs
+        export TF_SHELL_ALIASES="$(alias)" TF_SHELL_FUNCTIONS="${(F)${(k)functions}}"
+        return 0
+    fi
+    [[ -n "$TF_CAPTURE_DIR" ]] || return 0
+    rm -f "$TF_CAPTURE_DIR/command" "$TF_CAPTURE_DIR/exit_code" "$TF_CAPTURE_DIR/output"
+    [[ " $TF_CAPTURE_EXCLUDE " == *" ${1%% *} "* ]] && return 0
+    print -rn -- "$1" >| "$TF_CAPTURE_DIR/command"
+    exec {__thefuck_stdout}>&1 {__thefuck_stderr}>&2 > >(tee "$TF_CAPTURE_DIR/output") 2>&1
+    __thefuck_capturing=1
+}
+
+__thefuck_precmd() {
+    local exit_code=$?
+    [[ -n "$__thefuck_capturing" ]] || return 0
+    exec >&$__thefuck_stdout 2>&$__thefuck_stderr {__thefuck_stdout}>&- {__thefuck_stderr}>&-
+    unset __thefuck_capturing
+    print -rn -- "$exit_code" >| "$TF_CAPTURE_DIR/exit_code"
+}
+
+autoload -Uz add-zsh-hook
+add-zsh-hook preexec __thefuck_preexec
+add-zsh-hook precmd __thefuck_precmd
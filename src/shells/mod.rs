@@ -1 +1,95 @@
+use crate::types::Shell;
+use std::path::Path;
 
+/// Environment variable that overrides the detected shell
+pub const SHELL_ENV: &str = "TF_SHELL";
+
+/// Environment variable naming the directory the shell hook captures the last command to
+pub const CAPTURE_DIR_ENV: &str = "TF_CAPTURE_DIR";
+
+/// Gets the shell of a path to its executable, e.g. `/usr/bin/zsh`
+pub fn shell_from_path(path: &str) -> Shell {
+    let name = Path::new(path.trim())
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Shell::from_string(&name)
+}
+
+/// Detects the user's shell from `TF_SHELL`, then `SHELL`
+pub fn detect_shell() -> Shell {
+    match std::env::var(SHELL_ENV).or_else(|_| std::env::var("SHELL")) {
+        Ok(shell) if !shell.trim().is_empty() => shell_from_path(&shell),
+        _ if cfg!(windows) && std::env::var_os("PSModulePath").is_some() => Shell::PowerShell,
+        _ if cfg!(windows) => Shell::Cmd,
+        _ => Shell::Unknown("sh".to_string()),
+    }
+}
+
+/// Gets the program and flag that run a command line in a shell, e.g. `bash -c`
+#[allow(clippy::type_complexity)]
+pub fn run_args(shell: &Shell) -> (&'static str, &'static str) {
+    match shell {
+        Shell::Bash => ("bash", "-c"),
+        Shell::Zsh => ("zsh", "-c"),
+        Shell::Fish => ("fish", "-c"),
+        Shell::PowerShell => ("powershell", "-Command"),
+        Shell::Cmd => ("cmd", "/C"),
+        Shell::Unknown(_) if cfg!(windows) => ("cmd", "/C"),
+        Shell::Unknown(_) => ("sh", "-c"),
+    }
+}
+
+/// Gets the script that hooks into a shell to capture each command's exit status and output
+///
+/// Fish can't redirect its own output, so its hook only captures the exit status.
+#[allow(clippy::type_complexity)]
+pub fn hook_script(shell: &Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(include_str!("hooks/bash.sh")),
+        Shell::Zsh => Some(include_str!("hooks/zsh.sh")),
+        Shell::Fish => Some(include_str!("hooks/fish.fish")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_from_path() {
+        assert_eq!(shell_from_path("/usr/bin/zsh"), Shell::Zsh);
+        assert_eq!(shell_from_path("bash"), Shell::Bash);
+        assert_eq!(
+            shell_from_path("/bin/dash"),
+            Shell::Unknown("dash".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hook_script() {
+        assert!(
+            hook_script(&Shell::Bash)
+                .unwrap()
+                .contains("trap '__thefuck_preexec' DEBUG")
+        );
+        assert!(
+            hook_script(&Shell::Zsh)
+                .unwrap()
+                .contains("add-zsh-hook precmd")
+        );
+        assert!(hook_script(&Shell::Fish).unwrap().contains("fish_postexec"));
+        assert_eq!(hook_script(&Shell::Cmd), None);
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = hook_script(&shell).unwrap();
+            assert!(script.contains(crate::utils::resolve::FUNCTIONS_ENV));
+        }
+    }
+
+    #[test]
+    fn test_run_args() {
+        assert_eq!(run_args(&Shell::Fish), ("fish", "-c"));
+        assert_eq!(run_args(&Shell::Cmd), ("cmd", "/C"));
+    }
+}
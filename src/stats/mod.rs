@@ -0,0 +1,299 @@
+//! Local usage statistics: how often each rule fires and is accepted, and how long corrections
+//! take.
+//!
+//! Every correction run is recorded in a database in the data directory with its latency and the
+//! rules that suggested something; the correction the user goes with counts as accepted for its
+//! rule. Nothing leaves the machine.
+
+use crate::config::Settings;
+use crate::types::CorrectedCommand;
+use crate::{TheFuckError, TheFuckResult};
+use rusqlite::{Connection, params};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::time::Duration;
+
+/// Name of the statistics database inside the data directory
+pub const STATS_DB: &str = "stats.db";
+
+/// How often a rule fired and was accepted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleStats {
+    /// Name of the rule
+    pub rule: String,
+    /// Number of runs the rule suggested a correction in
+    pub fired: u64,
+    /// Number of times a correction of the rule was accepted
+    pub accepted: u64,
+}
+
+/// Summary of the recorded correction runs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+    /// Number of runs that found corrections
+    pub runs: u64,
+    /// Number of runs whose correction was accepted
+    pub accepted: u64,
+    /// Average time it took to find the corrections
+    pub average_latency: Duration,
+    /// Rules that fired, most often first
+    #[allow(clippy::type_complexity)]
+    pub rules: Vec<RuleStats>,
+}
+
+/// Gets an accepted-out-of-total ratio as a percentage
+fn rate(accepted: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        accepted as f64 * 100.0 / total as f64
+    }
+}
+
+impl Stats {
+    /// Describes the statistics with the `top` rules that fired most often
+    pub fn report(&self, top: usize) -> String {
+        let mut report = format!(
+            "Corrections: {} ({} accepted, {:.1}%)\nAverage latency: {:.1} ms\n",
+            self.runs,
+            self.accepted,
+            rate(self.accepted, self.runs),
+            self.average_latency.as_secs_f64() * 1000.0
+        );
+        #[allow(clippy::type_complexity)]
+        let rules: Vec<&RuleStats> = self.rules.iter().take(top).collect();
+        if rules.is_empty() {
+            return report;
+        }
+        let width = rules
+            .iter()
+            .map(|rule| rule.rule.len())
+            .max()
+            .unwrap_or(0)
+            .max("RULE".len());
+        report.push_str(&format!("\n{:width$}  FIRED  ACCEPTED    RATE\n", "RULE"));
+        for rule in rules {
+            report.push_str(&format!(
+                "{:width$}  {:5}  {:8}  {:5.1}%\n",
+                rule.rule,
+                rule.fired,
+                rule.accepted,
+                rate(rule.accepted, rule.fired)
+            ));
+        }
+        report
+    }
+}
+
+/// Local store of the correction runs
+pub struct StatsStore {
+    connection: Connection,
+}
+
+impl StatsStore {
+    /// Opens the store in a database file, creating it if needed
+    #[allow(clippy::type_complexity)]
+    pub fn open(path: &Path) -> TheFuckResult<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Opens the store in the data directory
+    #[allow(clippy::type_complexity)]
+    pub fn open_default() -> TheFuckResult<Self> {
+        let dir = Settings::data_dir()
+            .ok_or_else(|| TheFuckError::config_error("No data directory found"))?;
+        Self::open(&dir.join(STATS_DB))
+    }
+
+    /// Opens a store that only lives in memory
+    #[allow(clippy::type_complexity)]
+    pub fn in_memory() -> TheFuckResult<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn init(connection: Connection) -> TheFuckResult<Self> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS runs (
+                timestamp INTEGER NOT NULL,
+                latency_us INTEGER NOT NULL,
+                accepted INTEGER NOT NULL DEFAULT 0
+            );
+            CREATE TABLE IF NOT EXISTS rules (
+                rule TEXT PRIMARY KEY,
+                fired INTEGER NOT NULL DEFAULT 0,
+                accepted INTEGER NOT NULL DEFAULT 0
+            );",
+        )?;
+        Ok(Self { connection })
+    }
+
+    /// Records a run that found corrections, and how long it took to find them
+    #[allow(clippy::type_complexity)]
+    pub fn record_run(
+        &self,
+        corrections: &[CorrectedCommand],
+        latency: Duration,
+    ) -> TheFuckResult<()> {
+        if corrections.is_empty() {
+            return Ok(());
+        }
+        self.connection.execute(
+            "INSERT INTO runs (timestamp, latency_us) VALUES (?1, ?2)",
+            params![
+                chrono::Utc::now().timestamp(),
+                latency.as_micros().min(i64::MAX as u128) as i64
+            ],
+        )?;
+        #[allow(clippy::type_complexity)]
+        let rules: BTreeSet<&str> = corrections
+            .iter()
+            .map(|correction| correction.rule.as_str())
+            .filter(|rule| !rule.is_empty())
+            .collect();
+        for rule in rules {
+            self.connection.execute(
+                "INSERT INTO rules (rule, fired) VALUES (?1, 1)
+                 ON CONFLICT (rule) DO UPDATE SET fired = fired + 1",
+                params![rule],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Records that the correction of the last run was accepted
+    #[allow(clippy::type_complexity)]
+    pub fn record_accepted(&self, correction: &CorrectedCommand) -> TheFuckResult<()> {
+        self.connection.execute(
+            "UPDATE runs SET accepted = 1 WHERE rowid = (SELECT MAX(rowid) FROM runs)",
+            [],
+        )?;
+        if !correction.rule.is_empty() {
+            self.connection.execute(
+                "UPDATE rules SET accepted = accepted + 1 WHERE rule = ?1",
+                params![correction.rule],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Summarizes the recorded runs
+    #[allow(clippy::type_complexity)]
+    pub fn stats(&self) -> TheFuckResult<Stats> {
+        let (runs, accepted, average_latency) = self.connection.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(accepted), 0), COALESCE(AVG(latency_us), 0) FROM runs",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, f64>(2)?,
+                ))
+            },
+        )?;
+        let mut statement = self.connection.prepare(
+            "SELECT rule, fired, accepted FROM rules ORDER BY fired DESC, accepted DESC, rule",
+        )?;
+        #[allow(clippy::type_complexity)]
+        let rules = statement
+            .query_map([], |row| {
+                Ok(RuleStats {
+                    rule: row.get(0)?,
+                    fired: row.get::<_, i64>(1)?.max(0) as u64,
+                    accepted: row.get::<_, i64>(2)?.max(0) as u64,
+                })
+            })?
+            .collect::<Result<Vec<RuleStats>, rusqlite::Error>>()?;
+        Ok(Stats {
+            runs: runs.max(0) as u64,
+            accepted: accepted.max(0) as u64,
+            average_latency: Duration::from_micros(average_latency.max(0.0) as u64),
+            rules,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Command, Shell};
+
+    #[allow(clippy::type_complexity)]
+    fn correction(text: &str, rule: &str) -> CorrectedCommand {
+        let command = Command::new("git psuh".to_string(), Shell::Bash);
+        CorrectedCommand::new(text.to_string(), command, 1000).with_rule(rule)
+    }
+
+    #[test]
+    fn test_stats() {
+        let store = StatsStore::in_memory().unwrap();
+        let push = correction("git push", "git_not_command");
+        let corrections = vec![
+            push.clone(),
+            correction("git pull", "git_not_command"),
+            correction("git push --force", "git_push_force"),
+        ];
+        store
+            .record_run(&corrections, Duration::from_millis(10))
+            .unwrap();
+        store.record_accepted(&push).unwrap();
+        store
+            .record_run(&corrections[2..], Duration::from_millis(30))
+            .unwrap();
+        store.record_run(&[], Duration::from_secs(1)).unwrap();
+
+        let stats = store.stats().unwrap();
+        assert_eq!(stats.runs, 2);
+        assert_eq!(stats.accepted, 1);
+        assert_eq!(stats.average_latency, Duration::from_millis(20));
+        assert_eq!(
+            stats.rules,
+            vec![
+                RuleStats {
+                    rule: "git_push_force".to_string(),
+                    fired: 2,
+                    accepted: 0,
+                },
+                RuleStats {
+                    rule: "git_not_command".to_string(),
+                    fired: 1,
+                    accepted: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let stats = Stats {
+            runs: 4,
+            accepted: 3,
+            average_latency: Duration::from_micros(12_345),
+            rules: vec![
+                RuleStats {
+                    rule: "git_not_command".to_string(),
+                    fired: 4,
+                    accepted: 3,
+                },
+                RuleStats {
+                    rule: "java".to_string(),
+                    fired: 1,
+                    accepted: 0,
+                },
+            ],
+        };
+        assert_eq!(
+            stats.report(1),
+            "Corrections: 4 (3 accepted, 75.0%)\nAverage latency: 12.3 ms\n\n\
+             RULE             FIRED  ACCEPTED    RATE\n\
+             git_not_command      4         3   75.0%\n"
+        );
+        assert_eq!(
+            Stats::default().report(10),
+            "Corrections: 0 (0 accepted, 0.0%)\nAverage latency: 0.0 ms\n"
+        );
+    }
+}
@@ -109,6 +109,41 @@ impl Command {
         self.arguments().iter().any(|a| a.eq_ignore_ascii_case(arg))
     }
 
+    /// Gets the script with its first argument equal to `old` replaced by `new`
+    ///
+    /// Arguments match as written or as the shell reads them, so `'psh'` matches `psh`, and a
+    /// quoted argument stays quoted the same way.
+    pub fn replace_argument(&self, old: &str, new: &str) -> String {
+        crate::utils::replace_argument(self.trimmed(), old, new)
+    }
+
+    /// Gets the script with every argument equal to `old` replaced by `new`
+    pub fn replace_all_arguments(&self, old: &str, new: &str) -> String {
+        crate::utils::replace_all_arguments(self.trimmed(), old, new)
+    }
+
+    /// Gets the script with words inserted before its first argument equal to `arg`
+    ///
+    /// The words are inserted as written, e.g. `run` in `pnpm 'build'` -> `pnpm run 'build'`.
+    pub fn insert_before_argument(&self, arg: &str, words: &str) -> String {
+        crate::utils::insert_before_argument(self.trimmed(), arg, words)
+    }
+
+    /// Gets the script with words inserted after its first argument equal to `arg`
+    pub fn insert_after_argument(&self, arg: &str, words: &str) -> String {
+        crate::utils::insert_after_argument(self.trimmed(), arg, words)
+    }
+
+    /// Gets the script without any argument equal to `arg`
+    pub fn remove_argument(&self, arg: &str) -> String {
+        crate::utils::remove_all_arguments(self.trimmed(), arg)
+    }
+
+    /// Gets the script run by another program, e.g. `gradle build` -> `./gradlew build`
+    pub fn replace_command(&self, program: &str) -> String {
+        crate::utils::replace_program(self.trimmed(), program)
+    }
+
     /// Parses the command and returns structured data
     #[allow(clippy::type_complexity)]
     pub fn parse(&self) -> TheFuckResult<ParsedCommand> {
@@ -243,6 +278,27 @@ pub struct CorrectedCommand {
     /// Side effects of this correction
     #[allow(clippy::type_complexity)]
     pub side_effects: Vec<String>,
+    /// Name of the rule that suggested this correction, empty if unknown
+    #[serde(default)]
+    pub rule: String,
+    /// Why this correction was suggested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[allow(clippy::type_complexity)]
+    pub explanation: Option<Explanation>,
+}
+
+/// Why a correction was suggested
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Explanation {
+    /// Name of the rule that suggested the correction
+    pub rule: String,
+    /// The part of the output that made the rule match, if the rule knows it
+    #[allow(clippy::type_complexity)]
+    pub pattern: Option<String>,
+    /// External lookups the rule performed, e.g. programs it ran
+    #[allow(clippy::type_complexity)]
+    pub lookups: Vec<String>,
 }
 
 impl CorrectedCommand {
@@ -254,9 +310,23 @@ impl CorrectedCommand {
             priority,
             requires_confirmation: true,
             side_effects: Vec::new(),
+            rule: String::new(),
+            explanation: None,
         }
     }
 
+    /// Sets the name of the rule that suggested this correction
+    pub fn with_rule(mut self, rule: &str) -> Self {
+        self.rule = rule.to_string();
+        self
+    }
+
+    /// Sets why this correction was suggested
+    pub fn with_explanation(mut self, explanation: Explanation) -> Self {
+        self.explanation = Some(explanation);
+        self
+    }
+
     /// Sets whether confirmation is required
     pub fn with_confirmation(mut self, requires_confirmation: bool) -> Self {
         self.requires_confirmation = requires_confirmation;
@@ -360,6 +430,11 @@ impl CommandResult {
     pub fn failure(exit_code: i32, stderr: String) -> Self {
         Self::new(false, exit_code, String::new(), stderr, 0)
     }
+
+    /// Gets the combined standard output and standard error
+    pub fn output(&self) -> String {
+        format!("{}{}", self.stdout, self.stderr)
+    }
 }
 
 #[cfg(test)]
@@ -434,6 +509,27 @@ mod tests {
         assert!(!cmd.contains_argument("pull"));
     }
 
+    #[test]
+    fn test_command_rewriting_methods() {
+        let cmd = Command::new("  git psh origin 'psh' ".to_string(), Shell::Bash);
+
+        assert_eq!(cmd.replace_argument("psh", "push"), "git push origin 'psh'");
+        assert_eq!(
+            cmd.replace_all_arguments("psh", "push"),
+            "git push origin 'push'"
+        );
+        assert_eq!(cmd.replace_command("hub"), "hub psh origin 'psh'");
+        assert_eq!(cmd.replace_argument("pull", "push"), cmd.trimmed());
+        assert_eq!(
+            cmd.insert_before_argument("psh", "-C repo"),
+            "git -C repo psh origin 'psh'"
+        );
+        assert_eq!(
+            cmd.insert_after_argument("origin", "--force"),
+            "git psh origin --force 'psh'"
+        );
+    }
+
     #[test]
     fn test_command_parse() {
         let cmd = Command::new("git status --porcelain".to_string(), Shell::Bash);
@@ -519,4 +615,10 @@ mod tests {
         assert_eq!(corrected.priority, 100);
         assert!(corrected.requires_confirmation);
     }
+
+    #[test]
+    fn test_command_result_output() {
+        let result = CommandResult::new(false, 1, "out\n".to_string(), "err\n".to_string(), 0);
+        assert_eq!(result.output(), "out\nerr\n");
+    }
 }
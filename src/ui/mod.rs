@@ -0,0 +1,260 @@
+//! Presentation of corrections to the user.
+
+pub mod picker;
+pub mod selector;
+
+use crate::types::CorrectedCommand;
+use crate::{TheFuckError, TheFuckResult};
+use colored::Colorize;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use std::io::IsTerminal;
+
+/// How the user picks among several corrections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Picker {
+    /// One correction at a time on the prompt line
+    Inline,
+    /// A full-screen list with a preview of the selected correction
+    FullScreen,
+}
+
+/// What a key press does in a selector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Action {
+    Previous,
+    Next,
+    Pick,
+    Cancel,
+    Ignore,
+}
+
+/// Gets what a key press does in a selector
+pub(crate) fn key_action(key: &KeyEvent) -> Action {
+    if key.kind == KeyEventKind::Release {
+        return Action::Ignore;
+    }
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => Action::Previous,
+        KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => Action::Next,
+        KeyCode::Enter => Action::Pick,
+        KeyCode::Esc | KeyCode::Char('q') => Action::Cancel,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Cancel,
+        _ => Action::Ignore,
+    }
+}
+
+/// Checks if the terminal can't move the cursor, e.g. `TERM=dumb`
+fn is_dumb_terminal() -> bool {
+    std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+/// Lets the user pick a correction on the terminal, or `None` if they cancelled
+///
+/// Dumb terminals get a numbered list instead of the picker, and the first correction is
+/// picked when there is no terminal to ask on.
+#[allow(clippy::type_complexity)]
+pub fn select_correction<'a>(
+    original: &str,
+    corrections: &'a [CorrectedCommand],
+    picker: Picker,
+    colors: bool,
+) -> TheFuckResult<Option<&'a CorrectedCommand>> {
+    if corrections.is_empty() || !std::io::stderr().is_terminal() {
+        return Ok(corrections.first());
+    }
+    let picked = if is_dumb_terminal() {
+        if !std::io::stdin().is_terminal() {
+            return Ok(corrections.first());
+        }
+        selector::select_numbered(
+            corrections,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stderr(),
+        )?
+    } else {
+        match picker {
+            Picker::Inline => selector::select(original, corrections, colors)?,
+            Picker::FullScreen => picker::pick(original, corrections)?,
+        }
+    };
+    Ok(picked.map(|index| &corrections[index]))
+}
+
+/// A token of a corrected command compared to the failed one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change<'a> {
+    /// The token is in both commands
+    Kept(&'a str),
+    /// The token is only in the failed command
+    Removed(&'a str),
+    /// The token is only in the corrected command
+    Added(&'a str),
+}
+
+/// Diffs two commands token by token, keeping their longest common subsequence of tokens
+#[allow(clippy::type_complexity)]
+pub fn diff_tokens<'a>(original: &'a str, corrected: &'a str) -> Vec<Change<'a>> {
+    #[allow(clippy::type_complexity)]
+    let old: Vec<&str> = original.split_whitespace().collect();
+    #[allow(clippy::type_complexity)]
+    let new: Vec<&str> = corrected.split_whitespace().collect();
+
+    // lengths[i][j] is the length of the common subsequence of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            changes.push(Change::Kept(new[j]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            changes.push(Change::Removed(old[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Added(new[j]));
+            j += 1;
+        }
+    }
+    changes.extend(old[i..].iter().map(|token| Change::Removed(token)));
+    changes.extend(new[j..].iter().map(|token| Change::Added(token)));
+    changes
+}
+
+/// Formats a correction with the removed tokens struck through in red and the added ones in
+/// green, or as plain text without colors
+pub fn highlight_correction(original: &str, corrected: &str, colors: bool) -> String {
+    if !colors {
+        return corrected.to_string();
+    }
+    diff_tokens(original, corrected)
+        .into_iter()
+        .map(|change| match change {
+            Change::Kept(token) => token.bold().to_string(),
+            Change::Removed(token) => token.red().strikethrough().to_string(),
+            Change::Added(token) => token.green().bold().to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Styles a correction like [`highlight_correction`] for the full-screen picker
+#[allow(clippy::type_complexity)]
+pub(crate) fn diff_spans(original: &str, corrected: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (index, change) in diff_tokens(original, corrected).into_iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(match change {
+            Change::Kept(token) => Span::raw(token.to_string()),
+            Change::Removed(token) => Span::styled(
+                token.to_string(),
+                Style::new()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::CROSSED_OUT),
+            ),
+            Change::Added(token) => Span::styled(
+                token.to_string(),
+                Style::new().fg(Color::Green).add_modifier(Modifier::BOLD),
+            ),
+        });
+    }
+    spans
+}
+
+/// Places a command on the system clipboard instead of handing it to the shell
+#[allow(clippy::type_complexity)]
+pub fn copy_to_clipboard(text: &str) -> TheFuckResult<()> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| TheFuckError::process_error(format!("No clipboard available: {e}")))?;
+    clipboard
+        .set_text(text)
+        .map_err(|e| TheFuckError::process_error(format!("Failed to copy to the clipboard: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_tokens() {
+        assert_eq!(
+            diff_tokens("git psh origin", "git push origin"),
+            vec![
+                Change::Kept("git"),
+                Change::Removed("psh"),
+                Change::Added("push"),
+                Change::Kept("origin"),
+            ]
+        );
+        assert_eq!(
+            diff_tokens("apt install vim", "sudo apt install vim"),
+            vec![
+                Change::Added("sudo"),
+                Change::Kept("apt"),
+                Change::Kept("install"),
+                Change::Kept("vim"),
+            ]
+        );
+        assert_eq!(
+            diff_tokens("ls -l  ", "ls"),
+            vec![Change::Kept("ls"), Change::Removed("-l")]
+        );
+    }
+
+    #[test]
+    fn test_key_action() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(key_action(&key(KeyCode::Up)), Action::Previous);
+        assert_eq!(key_action(&key(KeyCode::Char('j'))), Action::Next);
+        assert_eq!(key_action(&key(KeyCode::Enter)), Action::Pick);
+        assert_eq!(
+            key_action(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Action::Cancel
+        );
+        assert_eq!(key_action(&key(KeyCode::Char('c'))), Action::Ignore);
+    }
+
+    #[test]
+    fn test_highlight_correction() {
+        assert_eq!(
+            highlight_correction("git psh", "git push", false),
+            "git push"
+        );
+    }
+
+    #[test]
+    fn test_diff_spans() {
+        assert_eq!(
+            diff_spans("git psh", "git push"),
+            vec![
+                Span::raw("git"),
+                Span::raw(" "),
+                Span::styled(
+                    "psh",
+                    Style::new()
+                        .fg(Color::Red)
+                        .add_modifier(Modifier::CROSSED_OUT)
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    "push",
+                    Style::new().fg(Color::Green).add_modifier(Modifier::BOLD)
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Full-screen picker with the corrections on the left and a preview of the selected one on the
+//! right.
+
+use super::{Action, diff_spans, key_action};
+use crate::types::CorrectedCommand;
+use crate::{TheFuckResult, i18n, undo};
+use crossterm::event::{self, Event};
+use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+
+/// Restores the terminal when the picker exits, even on errors
+struct Screen;
+
+impl Screen {
+    #[allow(clippy::type_complexity)]
+    fn enter() -> TheFuckResult<Self> {
+        enable_raw_mode()?;
+        let screen = Self;
+        execute!(std::io::stderr(), EnterAlternateScreen)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(std::io::stderr(), LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Gets the preview of a correction: what runs where, why it was suggested, and how to undo it
+#[allow(clippy::type_complexity)]
+pub(crate) fn preview(correction: &CorrectedCommand) -> Vec<String> {
+    let cwd = &correction.original.cwd;
+    let cwd_name = if cwd.is_empty() {
+        i18n::tr("preview-current-directory")
+    } else {
+        cwd.clone()
+    };
+    let mut lines = vec![
+        i18n::tr_with(
+            "preview-runs",
+            &[("command", correction.text.as_str().into())],
+        ),
+        i18n::tr_with("preview-in", &[("cwd", cwd_name.into())]),
+        String::new(),
+        i18n::tr_with(
+            "preview-rule",
+            &[
+                ("rule", correction.rule.as_str().into()),
+                ("priority", correction.priority.to_string().into()),
+            ],
+        ),
+    ];
+    if let Some(explanation) = &correction.explanation {
+        if let Some(pattern) = &explanation.pattern {
+            lines.push(i18n::tr_with(
+                "preview-matched",
+                &[("pattern", pattern.as_str().into())],
+            ));
+        }
+        lines.extend(
+            explanation.lookups.iter().map(|lookup| {
+                i18n::tr_with("preview-lookup", &[("lookup", lookup.as_str().into())])
+            }),
+        );
+    }
+    lines.extend(correction.side_effects.iter().map(|side_effect| {
+        i18n::tr_with(
+            "preview-side-effect",
+            &[("side-effect", side_effect.as_str().into())],
+        )
+    }));
+    lines.push(String::new());
+    lines.push(match undo::inverse(&correction.text, cwd) {
+        Some(inverse) => i18n::tr_with("preview-undo", &[("command", inverse.command.into())]),
+        None => i18n::tr("preview-no-undo"),
+    });
+    lines
+}
+
+/// Draws the picker
+#[allow(clippy::type_complexity)]
+fn draw(
+    frame: &mut Frame,
+    original: &str,
+    corrections: &[CorrectedCommand],
+    previews: &[Vec<String>],
+    state: &mut ListState,
+) {
+    let [list_area, preview_area] =
+        Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .areas(frame.area());
+
+    #[allow(clippy::type_complexity)]
+    let items: Vec<ListItem> = corrections
+        .iter()
+        .map(|correction| ListItem::new(Line::from(diff_spans(original, &correction.text))))
+        .collect();
+    let list = List::new(items)
+        .block(Block::bordered().title(format!(" {} ", i18n::tr("picker-corrections"))))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, list_area, state);
+
+    let selected = state.selected().unwrap_or(0);
+    let text = Text::from(
+        previews[selected]
+            .iter()
+            .map(|line| Line::from(line.as_str()))
+            .collect::<Vec<Line>>(),
+    );
+    let preview = Paragraph::new(text)
+        .block(Block::bordered().title(format!(" {} ", i18n::tr("picker-preview"))))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(preview, preview_area);
+}
+
+/// Runs the picker on a terminal until the user picks a correction or cancels
+#[allow(clippy::type_complexity)]
+fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
+    original: &str,
+    corrections: &[CorrectedCommand],
+    mut next_event: impl FnMut() -> std::io::Result<Event>,
+) -> TheFuckResult<Option<usize>>
+where
+    B::Error: Send + Sync + 'static,
+{
+    #[allow(clippy::type_complexity)]
+    let previews: Vec<Vec<String>> = corrections.iter().map(preview).collect();
+    let mut state = ListState::default().with_selected(Some(0));
+    loop {
+        terminal
+            .draw(|frame| draw(frame, original, corrections, &previews, &mut state))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        let Event::Key(key) = next_event()? else {
+            continue;
+        };
+        let selected = state.selected().unwrap_or(0);
+        match key_action(&key) {
+            Action::Previous => state.select(Some(
+                selected.checked_sub(1).unwrap_or(corrections.len() - 1),
+            )),
+            Action::Next => state.select(Some((selected + 1) % corrections.len())),
+            Action::Pick => return Ok(Some(selected)),
+            Action::Cancel => return Ok(None),
+            Action::Ignore => {}
+        }
+    }
+}
+
+/// Lets the user pick a correction in a full-screen picker drawn on stderr
+#[allow(clippy::type_complexity)]
+pub fn pick(original: &str, corrections: &[CorrectedCommand]) -> TheFuckResult<Option<usize>> {
+    let _screen = Screen::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stderr()))?;
+    run(&mut terminal, original, corrections, event::read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Command, Shell};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::backend::TestBackend;
+
+    fn correction(text: &str, priority: u32) -> CorrectedCommand {
+        let original = Command::new("git psh".to_string(), Shell::Bash).with_cwd("/repo".into());
+        CorrectedCommand::new(text.to_string(), original, priority).with_rule("git_push")
+    }
+
+    #[test]
+    fn test_preview() {
+        let preview = preview(&correction("git push", 1000));
+        assert_eq!(preview[0], "Runs: git push");
+        assert_eq!(preview[1], "In: /repo");
+        assert_eq!(preview[3], "Rule: git_push (priority 1000)");
+        assert_eq!(preview.last().unwrap(), "Undo: no known inverse");
+    }
+
+    #[test]
+    fn test_run() {
+        let corrections = vec![correction("git push", 1000), correction("git pull", 999)];
+        let mut terminal = Terminal::new(TestBackend::new(80, 12)).unwrap();
+        let mut keys = vec![KeyCode::Enter, KeyCode::Down, KeyCode::Down, KeyCode::Up];
+        let picked = run(&mut terminal, "git psh", &corrections, || {
+            Ok(Event::Key(KeyEvent::new(
+                keys.pop().unwrap(),
+                KeyModifiers::NONE,
+            )))
+        })
+        .unwrap();
+        assert_eq!(picked, Some(1));
+
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("Runs: git pull"));
+        assert!(screen.contains("> git psh pull"));
+
+        let picked = run(&mut terminal, "git psh", &corrections, || {
+            Ok(Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)))
+        })
+        .unwrap();
+        assert_eq!(picked, None);
+    }
+}
@@ -0,0 +1,141 @@
+//! Inline selectors that show one correction at a time on the prompt line.
+
+use super::{Action, highlight_correction, key_action};
+use crate::types::CorrectedCommand;
+use crate::{TheFuckResult, i18n};
+use crossterm::event::{self, Event};
+use crossterm::terminal::{self, disable_raw_mode, enable_raw_mode};
+use crossterm::{cursor, queue};
+use std::io::{BufRead, Write};
+
+/// Keeps the terminal in raw mode until dropped
+struct RawMode;
+
+impl RawMode {
+    #[allow(clippy::type_complexity)]
+    fn enable() -> TheFuckResult<Self> {
+        enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Lets the user cycle through the corrections with the arrow keys on stderr
+#[allow(clippy::type_complexity)]
+pub fn select(
+    original: &str,
+    corrections: &[CorrectedCommand],
+    colors: bool,
+) -> TheFuckResult<Option<usize>> {
+    let _raw_mode = RawMode::enable()?;
+    let mut stderr = std::io::stderr();
+    let mut selected = 0;
+    loop {
+        queue!(
+            stderr,
+            cursor::MoveToColumn(0),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        )?;
+        write!(
+            stderr,
+            "{} [{}]",
+            highlight_correction(original, &corrections[selected].text, colors),
+            i18n::tr("selector-keys")
+        )?;
+        stderr.flush()?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        match key_action(&key) {
+            Action::Previous => selected = selected.checked_sub(1).unwrap_or(corrections.len() - 1),
+            Action::Next => selected = (selected + 1) % corrections.len(),
+            Action::Pick => {
+                write!(stderr, "\r\n")?;
+                return Ok(Some(selected));
+            }
+            Action::Cancel => {
+                write!(stderr, "\r\n")?;
+                return Ok(None);
+            }
+            Action::Ignore => {}
+        }
+    }
+}
+
+/// Lets the user pick a correction by number, for terminals that can't move the cursor
+#[allow(clippy::type_complexity)]
+pub fn select_numbered(
+    corrections: &[CorrectedCommand],
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> TheFuckResult<Option<usize>> {
+    for (index, correction) in corrections.iter().enumerate() {
+        writeln!(output, "{}. {}", index + 1, correction.text)?;
+    }
+    loop {
+        write!(
+            output,
+            "{} ",
+            i18n::tr_with(
+                "selector-numbered-prompt",
+                &[("count", corrections.len().into())]
+            )
+        )?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        match line.trim() {
+            "" => return Ok(Some(0)),
+            "q" => return Ok(None),
+            answer => match answer.parse::<usize>() {
+                Ok(number) if (1..=corrections.len()).contains(&number) => {
+                    return Ok(Some(number - 1));
+                }
+                _ => writeln!(
+                    output,
+                    "{}",
+                    i18n::tr_with("selector-not-a-correction", &[("answer", answer.into())])
+                )?,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Command, Shell};
+
+    #[test]
+    fn test_select_numbered() {
+        let original = Command::new("git psh".to_string(), Shell::Bash);
+        let corrections = vec![
+            CorrectedCommand::new("git push".to_string(), original.clone(), 1000),
+            CorrectedCommand::new("git pull".to_string(), original, 999),
+        ];
+        let mut output = Vec::new();
+        let picked = select_numbered(&corrections, &mut "3\n2\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(picked, Some(1));
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("1. git push\n2. git pull\n"));
+        assert!(output.contains("Not a correction: 3"));
+
+        let mut output = Vec::new();
+        assert_eq!(
+            select_numbered(&corrections, &mut "\n".as_bytes(), &mut output).unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            select_numbered(&corrections, &mut "".as_bytes(), &mut output).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! Journal of the applied corrections, so they can be undone.
+//!
+//! Every correction handed to the shell is appended to a journal in the data directory, along
+//! with the command that reverts it when one is known. What the inverse needs (e.g. the commit
+//! of a branch about to be deleted) is captured when the correction is recorded, before it runs.
+//! Since the correction may then fail or change nothing, inverses avoid assuming it did: each
+//! records a [`Precondition`] checked before undoing, e.g. an undone commit is reset to the commit
+//! recorded before it only if it's still the parent of `HEAD`, and packages that were already
+//! installed aren't uninstalled.
+
+use crate::config::Settings;
+use crate::types::CorrectedCommand;
+use crate::{TheFuckError, TheFuckResult, utils};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command as ProcessCommand;
+use std::time::Duration;
+
+/// Name of the journal inside the data directory
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// Maximum number of corrections kept in the journal
+const MAX_ENTRIES: usize = 100;
+
+/// Time a package manager gets to tell if a package is installed, before it's assumed to be
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// What must hold for an undo command to revert the correction it was recorded for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Precondition {
+    /// The parent of `HEAD` is this commit, the one `HEAD` was at before a `git commit`
+    Parent(String),
+    /// This branch, created by `git switch -c`, is checked out
+    Branch(String),
+    /// The latest stash is new, and the one before it is this one, the latest before
+    /// `git stash`, or none
+    #[allow(clippy::type_complexity)]
+    Stash(Option<String>),
+}
+
+impl Precondition {
+    /// Checks the precondition in a directory
+    #[allow(clippy::type_complexity)]
+    fn check(&self, cwd: &str) -> Result<(), String> {
+        match self {
+            Self::Parent(parent) if git_commit("HEAD~1", cwd).as_ref() != Some(parent) => {
+                Err("it didn't create the last commit".to_string())
+            }
+            Self::Branch(branch) if git_branch(cwd).as_ref() != Some(branch) => {
+                Err(format!("`{branch}` isn't checked out"))
+            }
+            Self::Stash(previous)
+                if git_commit("stash@{0}", cwd) == *previous
+                    || git_commit("stash@{1}", cwd) != *previous =>
+            {
+                Err("it didn't create the latest stash".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A correction that was applied
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalEntry {
+    /// The failed command
+    pub original: String,
+    /// The correction that was applied
+    pub corrected: String,
+    /// Name of the rule that suggested the correction
+    pub rule: String,
+    /// Side effects of the correction
+    #[allow(clippy::type_complexity)]
+    pub side_effects: Vec<String>,
+    /// Directory the correction ran in
+    pub cwd: String,
+    /// When the correction was applied, in seconds since the Unix epoch
+    pub timestamp: i64,
+    /// Command that reverts the correction, if one is known
+    #[allow(clippy::type_complexity)]
+    pub undo: Option<String>,
+    /// What must hold for the undo command to apply
+    #[allow(clippy::type_complexity)]
+    pub undo_precondition: Option<Precondition>,
+}
+
+impl JournalEntry {
+    /// Creates an entry for a correction about to be applied
+    pub fn new(correction: &CorrectedCommand) -> Self {
+        let cwd = correction.original.cwd.clone();
+        let inverse = inverse(&correction.text, &cwd);
+        Self {
+            original: correction.original.text.clone(),
+            corrected: correction.text.clone(),
+            rule: correction.rule.clone(),
+            side_effects: correction.side_effects.clone(),
+            undo_precondition: inverse
+                .as_ref()
+                .and_then(|inverse| inverse.precondition.clone()),
+            undo: inverse.map(|inverse| inverse.command),
+            cwd,
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// Checks that the undo command still applies, e.g. that the commit to undo is the last one
+    #[allow(clippy::type_complexity)]
+    pub fn check_undo(&self) -> TheFuckResult<()> {
+        let Some(precondition) = &self.undo_precondition else {
+            return Ok(());
+        };
+        precondition.check(&self.cwd).map_err(|reason| {
+            TheFuckError::validation_error(format!(
+                "`{}` can't be undone: {reason}",
+                self.corrected
+            ))
+        })
+    }
+}
+
+/// The command that reverts another one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inverse {
+    /// The reverting command
+    pub command: String,
+    /// What must hold for the command to apply, checked before it runs
+    #[allow(clippy::type_complexity)]
+    pub precondition: Option<Precondition>,
+}
+
+/// Gets the command that reverts another one, run in a directory
+///
+/// Only single commands with a well-known inverse are handled, e.g. `git add`, `git commit`,
+/// `git branch -d` or `mkdir`; anything chained or redirected has none.
+#[allow(clippy::type_complexity)]
+pub fn inverse(command: &str, cwd: &str) -> Option<Inverse> {
+    inverse_with(command, cwd, &is_installed)
+}
+
+/// Gets the command that reverts another one, checking which packages are installed with
+/// `installed(manager, package)`
+#[allow(clippy::type_complexity)]
+fn inverse_with(
+    command: &str,
+    cwd: &str,
+    installed: &dyn Fn(&str, &str) -> bool,
+) -> Option<Inverse> {
+    if command.contains(['&', '|', ';', '>', '<', '`', '$']) {
+        return None;
+    }
+    let mut words = shlex::split(command)?;
+    let sudo = words.first().is_some_and(|word| word == "sudo");
+    if sudo {
+        words.remove(0);
+    }
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let (flags, args): (Vec<&str>, Vec<&str>) =
+        words.iter().skip(2).partition(|word| word.starts_with('-'));
+    #[allow(clippy::type_complexity)]
+    let new_packages = |manager: &str| -> Option<Vec<&str>> {
+        let new: Vec<&str> = args
+            .iter()
+            .copied()
+            .filter(|package| !installed(manager, package))
+            .collect();
+        (!new.is_empty()).then_some(new)
+    };
+    let mut precondition = None;
+
+    // Literal words are kept as is, the ones from the command are quoted again
+    #[allow(clippy::type_complexity)]
+    let inverse: Vec<String> = match words.as_slice() {
+        ["git", "add", ..] if !args.is_empty() => {
+            join(&["git", "restore", "--staged", "--"], &args)?
+        }
+        ["git", "commit", ..] if !flags.iter().any(|flag| flag.starts_with("--amend")) => {
+            let sha = git_commit("HEAD", cwd)?;
+            let inverse = join(&["git", "reset", "--soft"], &[&sha])?;
+            precondition = Some(Precondition::Parent(sha));
+            inverse
+        }
+        ["git", "branch", ..]
+            if args.len() == 1
+                && flags
+                    .iter()
+                    .any(|flag| matches!(*flag, "-d" | "-D" | "--delete")) =>
+        {
+            let sha = git_commit(args[0], cwd)?;
+            join(&["git", "branch"], &[args[0], &sha])?
+        }
+        // A branch that already exists isn't created, and mustn't be deleted
+        ["git", "checkout", "-b", branch] | ["git", "switch", "-c", branch]
+            if git_commit(&format!("refs/heads/{branch}"), cwd).is_none() =>
+        {
+            precondition = Some(Precondition::Branch(branch.to_string()));
+            join(
+                &["git", "switch", "-", "&&", "git", "branch", "-d"],
+                &[branch],
+            )?
+        }
+        ["git", "stash"] | ["git", "stash", "push"] => {
+            precondition = Some(Precondition::Stash(git_commit("stash@{0}", cwd)));
+            join(&["git", "stash", "pop"], &[])?
+        }
+        ["mkdir", dirs @ ..] if !dirs.iter().any(|dir| dir.starts_with('-')) => {
+            join(&["rmdir"], dirs)?
+        }
+        ["mv", from, to] if !from.starts_with('-') && !to.starts_with('-') => {
+            if Path::new(cwd).join(to).is_dir() {
+                return None;
+            }
+            join(&["mv"], &[to, from])?
+        }
+        ["brew", "install", ..] if flags.is_empty() && !args.is_empty() => {
+            join(&["brew", "uninstall"], &new_packages("brew")?)?
+        }
+        ["cargo", "install", name] if !name.starts_with('-') && !installed("cargo", name) => {
+            join(&["cargo", "uninstall"], &[name])?
+        }
+        [pip, "install", ..]
+            if matches!(*pip, "pip" | "pip3") && flags.is_empty() && !args.is_empty() =>
+        {
+            join(&[pip, "uninstall", "-y"], &new_packages(pip)?)?
+        }
+        _ => return None,
+    };
+
+    let inverse = inverse.join(" ");
+    Some(Inverse {
+        command: if sudo {
+            format!("sudo {inverse}")
+        } else {
+            inverse
+        },
+        precondition,
+    })
+}
+
+/// Checks if a package manager (`brew`, `cargo`, `pip` or `pip3`) has a package installed
+///
+/// This runs before every applied install, so a slow package manager is given up on and the
+/// package assumed installed, which leaves it alone when undoing.
+fn is_installed(manager: &str, package: &str) -> bool {
+    // Version requirements like `requests==2.31` aren't part of the name
+    let name = package
+        .split(['=', '<', '>', '!', '~', '[', ';', '@'])
+        .next()
+        .unwrap_or(package);
+    let mut command = ProcessCommand::new(manager);
+    match manager {
+        "brew" => command.args(["list", "--versions", name]),
+        "cargo" => command.args(["install", "--list"]),
+        _ => command.args(["show", "-q", name]),
+    };
+    let output = match utils::run_with_timeout(&mut command, "", LOOKUP_TIMEOUT) {
+        Ok(output) if output.timed_out => {
+            tracing::debug!("gave up on checking if {manager} has {name} installed");
+            return true;
+        }
+        Ok(output) => output,
+        Err(_) => return false,
+    };
+    if manager == "cargo" {
+        let prefix = format!("{name} v");
+        return output.stdout.lines().any(|line| line.starts_with(&prefix));
+    }
+    output.status.success() && !output.stdout.is_empty()
+}
+
+/// Joins literal words with words that need quoting
+#[allow(clippy::type_complexity)]
+fn join(literal: &[&str], quoted: &[&str]) -> Option<Vec<String>> {
+    let mut words: Vec<String> = literal.iter().map(|word| word.to_string()).collect();
+    for word in quoted {
+        words.push(shlex::try_quote(word).ok()?.to_string());
+    }
+    Some(words)
+}
+
+/// Gets the commit a git branch points to
+#[allow(clippy::type_complexity)]
+fn git_commit(branch: &str, cwd: &str) -> Option<String> {
+    let mut command = ProcessCommand::new("git");
+    command.args([
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{branch}^{{commit}}"),
+    ]);
+    if !cwd.is_empty() {
+        command.current_dir(cwd);
+    }
+    let output = command.output().ok()?;
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !sha.is_empty()).then_some(sha)
+}
+
+/// Gets the branch checked out in a directory, if any
+#[allow(clippy::type_complexity)]
+fn git_branch(cwd: &str) -> Option<String> {
+    let mut command = ProcessCommand::new("git");
+    command.args(["symbolic-ref", "--quiet", "--short", "HEAD"]);
+    if !cwd.is_empty() {
+        command.current_dir(cwd);
+    }
+    let output = command.output().ok()?;
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !branch.is_empty()).then_some(branch)
+}
+
+/// Journal of the applied corrections, oldest first
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    /// Opens the journal in a file, which is created on the first record
+    pub fn open(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Opens the journal in the data directory
+    #[allow(clippy::type_complexity)]
+    pub fn open_default() -> TheFuckResult<Self> {
+        let dir = Settings::data_dir()
+            .ok_or_else(|| TheFuckError::config_error("No data directory found"))?;
+        Ok(Self::open(&dir.join(JOURNAL_FILE)))
+    }
+
+    /// Gets the applied corrections, oldest first
+    #[allow(clippy::type_complexity)]
+    pub fn entries(&self) -> TheFuckResult<Vec<JournalEntry>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    tracing::warn!("skipping invalid journal entry: {e}");
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Records an applied correction, forgetting the oldest ones past the limit
+    #[allow(clippy::type_complexity)]
+    pub fn record(&self, entry: &JournalEntry) -> TheFuckResult<()> {
+        let mut entries = self.entries()?;
+        if entries.len() < MAX_ENTRIES {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            writeln!(file, "{}", serde_json::to_string(entry)?)?;
+            return Ok(());
+        }
+        entries.push(entry.clone());
+        let excess = entries.len() - MAX_ENTRIES;
+        self.write(&entries[excess..])
+    }
+
+    /// Gets the last applied correction
+    #[allow(clippy::type_complexity)]
+    pub fn last(&self) -> TheFuckResult<Option<JournalEntry>> {
+        Ok(self.entries()?.pop())
+    }
+
+    /// Removes the last applied correction, once it's undone
+    #[allow(clippy::type_complexity)]
+    pub fn pop(&self) -> TheFuckResult<Option<JournalEntry>> {
+        let mut entries = self.entries()?;
+        let last = entries.pop();
+        if last.is_some() {
+            self.write(&entries)?;
+        }
+        Ok(last)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn write(&self, entries: &[JournalEntry]) -> TheFuckResult<()> {
+        let mut content = String::new();
+        for entry in entries {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::type_complexity)]
+    fn undo(command: &str, cwd: &str) -> Option<String> {
+        inverse_with(command, cwd, &|_, _| false).map(|inverse| inverse.command)
+    }
+
+    fn entry(corrected: &str) -> JournalEntry {
+        JournalEntry {
+            corrected: corrected.to_string(),
+            ..JournalEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_undo() {
+        assert_eq!(
+            undo("git add src/main.rs", "").as_deref(),
+            Some("git restore --staged -- src/main.rs")
+        );
+        assert_eq!(undo("git commit --amend", ""), None);
+        assert_eq!(
+            undo("git checkout -b thefuck-rs-undo-test", "").as_deref(),
+            Some("git switch - && git branch -d thefuck-rs-undo-test")
+        );
+        assert_eq!(undo("mkdir -p a/b", ""), None);
+        assert_eq!(
+            undo("sudo mkdir 'my dir'", "").as_deref(),
+            Some("sudo rmdir 'my dir'")
+        );
+        assert_eq!(
+            undo("pip install requests", "").as_deref(),
+            Some("pip uninstall -y requests")
+        );
+        assert_eq!(undo("git push", ""), None);
+        assert_eq!(undo("git stash && git pull", ""), None);
+    }
+
+    #[test]
+    fn test_inverse_mv() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path().to_string_lossy();
+        std::fs::create_dir(dir.path().join("dir")).unwrap();
+        assert_eq!(undo("mv a b", &cwd).as_deref(), Some("mv b a"));
+        assert_eq!(undo("mv a dir", &cwd), None);
+    }
+
+    #[test]
+    fn test_inverse_git_branch_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path().to_string_lossy();
+        #[allow(clippy::type_complexity)]
+        let git = |args: &[&str]| {
+            ProcessCommand::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if !git(&["init", "-q"]) || !git(&["commit", "-q", "--allow-empty", "-m", "init"]) {
+            return;
+        }
+        assert!(git(&["branch", "feature"]));
+        let sha = git_commit("HEAD", &cwd).unwrap();
+
+        assert_eq!(
+            undo("git branch -D feature", &cwd),
+            Some(format!("git branch feature {sha}"))
+        );
+        assert_eq!(undo("git branch -d missing", &cwd), None);
+    }
+
+    #[test]
+    fn test_inverse_git_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path().to_string_lossy().to_string();
+        #[allow(clippy::type_complexity)]
+        let git = |args: &[&str]| {
+            ProcessCommand::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if !git(&["init", "-q"]) || !git(&["commit", "-q", "--allow-empty", "-m", "init"]) {
+            return;
+        }
+        let sha = git_commit("HEAD", &cwd).unwrap();
+        let inverse = inverse("git commit -m 'fix typo'", &cwd).unwrap();
+        assert_eq!(inverse.command, format!("git reset --soft {sha}"));
+        assert_eq!(inverse.precondition, Some(Precondition::Parent(sha)));
+        let entry = JournalEntry {
+            corrected: "git commit -m 'fix typo'".to_string(),
+            cwd: cwd.clone(),
+            undo: Some(inverse.command),
+            undo_precondition: inverse.precondition,
+            ..JournalEntry::default()
+        };
+
+        // The commit failed, e.g. with nothing to commit
+        assert!(entry.check_undo().is_err());
+        assert!(git(&["commit", "-q", "--allow-empty", "-m", "fix typo"]));
+        assert!(entry.check_undo().is_ok());
+        assert!(git(&["commit", "-q", "--allow-empty", "-m", "later"]));
+        assert!(entry.check_undo().is_err());
+    }
+
+    #[test]
+    fn test_inverse_git_branch_and_stash() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path().to_string_lossy().to_string();
+        #[allow(clippy::type_complexity)]
+        let git = |args: &[&str]| {
+            ProcessCommand::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if !git(&["init", "-q"]) || !git(&["commit", "-q", "--allow-empty", "-m", "init"]) {
+            return;
+        }
+        #[allow(clippy::type_complexity)]
+        let entry = |command: &str| {
+            let inverse = inverse(command, &cwd).unwrap();
+            JournalEntry {
+                corrected: command.to_string(),
+                cwd: cwd.clone(),
+                undo: Some(inverse.command),
+                undo_precondition: inverse.precondition,
+                ..JournalEntry::default()
+            }
+        };
+
+        // The user's existing branch must survive undoing a failed `git switch -c`
+        assert!(git(&["branch", "existing"]));
+        assert_eq!(undo("git switch -c existing", &cwd), None);
+        let created = entry("git switch -c feature");
+        assert!(created.check_undo().is_err());
+        assert!(git(&["switch", "-q", "-c", "feature"]));
+        assert!(created.check_undo().is_ok());
+
+        // With nothing to stash, undoing mustn't pop an older stash
+        std::fs::write(dir.path().join("a"), "a").unwrap();
+        assert!(git(&["add", "a"]));
+        assert!(git(&["stash", "-q"]));
+        let stashed = entry("git stash");
+        assert!(stashed.check_undo().is_err());
+        std::fs::write(dir.path().join("b"), "b").unwrap();
+        assert!(git(&["add", "b"]));
+        assert!(git(&["stash", "-q"]));
+        assert!(stashed.check_undo().is_ok());
+    }
+
+    #[test]
+    fn test_inverse_skips_installed_packages() {
+        let installed = |_: &str, package: &str| package == "requests";
+        #[allow(clippy::type_complexity)]
+        let undo = |command| inverse_with(command, "", &installed).map(|inverse| inverse.command);
+        assert_eq!(undo("pip install requests"), None);
+        assert_eq!(
+            undo("pip install requests flask").as_deref(),
+            Some("pip uninstall -y flask")
+        );
+        assert_eq!(undo("brew install requests"), None);
+        assert_eq!(undo("cargo install requests"), None);
+        assert_eq!(
+            undo("cargo install ripgrep").as_deref(),
+            Some("cargo uninstall ripgrep")
+        );
+    }
+
+    #[test]
+    fn test_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::open(&dir.path().join("data").join(JOURNAL_FILE));
+        assert_eq!(journal.last().unwrap(), None);
+
+        journal.record(&entry("git push")).unwrap();
+        journal.record(&entry("git add .")).unwrap();
+        assert_eq!(journal.entries().unwrap().len(), 2);
+        assert_eq!(journal.pop().unwrap(), Some(entry("git add .")));
+        assert_eq!(journal.last().unwrap(), Some(entry("git push")));
+    }
+
+    #[test]
+    fn test_journal_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::open(&dir.path().join(JOURNAL_FILE));
+        for i in 0..=MAX_ENTRIES {
+            journal.record(&entry(&i.to_string())).unwrap();
+        }
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0], entry("1"));
+    }
+}
@@ -0,0 +1,303 @@
+//! Memoizes expensive lookups, such as asking a program for its commands, across runs.
+//!
+//! A value is cached under a [`CacheKey`] made of a description, its arguments and the
+//! modification times of the files it depends on, so touching a build file or reinstalling a
+//! program changes the key instead of serving stale data. Values are kept in memory for the
+//! process and in a JSON file in the cache directory for later runs, until their TTL runs out.
+
+use super::{get_output, get_output_in, join_script, lookups};
+use crate::config::Settings;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Name of the lookup cache inside the cache directory
+pub const LOOKUP_CACHE: &str = "lookups.json";
+
+/// How long cached lookups are kept by default
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What a cached value depends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    name: String,
+    key: String,
+}
+
+/// Gets the modification time of a file as seconds since the epoch, if it exists
+#[allow(clippy::type_complexity)]
+fn modified(path: &Path) -> Option<u128> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+impl CacheKey {
+    /// Creates a key described by a name, e.g. "`go help`"
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            key: name.clone(),
+            name,
+        }
+    }
+
+    /// Adds an argument the value depends on
+    pub fn arg(mut self, arg: impl AsRef<str>) -> Self {
+        self.key.push('\0');
+        self.key.push_str(arg.as_ref());
+        self
+    }
+
+    /// Adds a file the value depends on, whose modification invalidates it
+    pub fn file(mut self, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let modified = modified(path).map_or_else(|| "missing".to_string(), |m| m.to_string());
+        self.key
+            .push_str(&format!("\0{}@{modified}", path.display()));
+        self
+    }
+
+    /// Adds a program the value depends on, whose reinstallation invalidates it
+    pub fn program(self, program: &str) -> Self {
+        match super::which(program) {
+            Some(path) => self.file(path),
+            None => self.arg(format!("{program}@missing")),
+        }
+    }
+
+    /// Gets what the key stands for
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A cached value and when it expires, in seconds since the epoch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    expires: u64,
+    value: serde_json::Value,
+}
+
+/// Cached values stored in a file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiskCache {
+    #[allow(clippy::type_complexity)]
+    entries: HashMap<String, Entry>,
+}
+
+/// Gets a time as seconds since the epoch
+fn seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+impl DiskCache {
+    /// Loads the values cached in a file, or none if it can't be read
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the values that haven't expired to a file
+    #[allow(clippy::type_complexity)]
+    pub fn save(&mut self, path: &Path, now: SystemTime) -> crate::TheFuckResult<()> {
+        let now = seconds(now);
+        self.entries.retain(|_, entry| entry.expires > now);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Gets a value that hasn't expired
+    #[allow(clippy::type_complexity)]
+    pub fn get(&self, key: &CacheKey, now: SystemTime) -> Option<&serde_json::Value> {
+        self.entries
+            .get(&key.key)
+            .filter(|entry| entry.expires > seconds(now))
+            .map(|entry| &entry.value)
+    }
+
+    /// Caches a value until it expires
+    pub fn insert(&mut self, key: &CacheKey, value: serde_json::Value, expires: SystemTime) {
+        let expires = seconds(expires);
+        self.entries
+            .insert(key.key.clone(), Entry { expires, value });
+    }
+}
+
+/// Values cached in memory, and the on-disk cache backing them
+struct Layers {
+    memory: DiskCache,
+    #[allow(clippy::type_complexity)]
+    disk: Option<(PathBuf, DiskCache)>,
+}
+
+/// Gets the cache layers, loading the on-disk cache on first use
+#[allow(clippy::type_complexity)]
+fn layers() -> &'static Mutex<Layers> {
+    #[allow(clippy::type_complexity)]
+    static LAYERS: OnceLock<Mutex<Layers>> = OnceLock::new();
+    LAYERS.get_or_init(|| {
+        // Tests must not depend on, or leave behind, what the developer's runs cached
+        let path = if cfg!(test) {
+            None
+        } else {
+            Settings::cache_dir().map(|dir| dir.join(LOOKUP_CACHE))
+        };
+        Mutex::new(Layers {
+            memory: DiskCache::default(),
+            disk: path.map(|path| {
+                let cache = DiskCache::load(&path);
+                (path, cache)
+            }),
+        })
+    })
+}
+
+/// Gets a value cached under a key, computing and caching it for `ttl` if there is none
+///
+/// The value is computed without holding the cache, so a lookup may run twice when two threads
+/// miss at once.
+#[allow(clippy::type_complexity)]
+pub fn cached<T: Serialize + DeserializeOwned>(
+    key: &CacheKey,
+    ttl: Duration,
+    compute: impl FnOnce() -> T,
+) -> T {
+    let now = SystemTime::now();
+    {
+        let layers = layers()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let hit = layers.memory.get(key, now).or_else(|| {
+            let (_, disk) = layers.disk.as_ref()?;
+            disk.get(key, now)
+        });
+        if let Some(value) = hit.and_then(|json| serde_json::from_value(json.clone()).ok()) {
+            lookups::record(|| format!("reused the cached {}", key.name));
+            return value;
+        }
+    }
+
+    let value = compute();
+    let Ok(json) = serde_json::to_value(&value) else {
+        return value;
+    };
+    let mut layers = layers()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    layers.memory.insert(key, json.clone(), now + ttl);
+    if let Some((path, disk)) = layers.disk.as_mut() {
+        disk.insert(key, json, now + ttl);
+        if let Err(e) = disk.save(path, now) {
+            tracing::warn!("failed to save the lookup cache: {e}");
+        }
+    }
+    value
+}
+
+/// Runs a program and gets its standard output, reusing the output of a previous run
+#[allow(clippy::type_complexity)]
+pub(crate) fn cached_output(program: &str, args: &[&str]) -> Option<String> {
+    let command = join_script(&[&[program], args].concat());
+    let key = args
+        .iter()
+        .fold(
+            CacheKey::new(format!("output of `{command}`")),
+            |key, arg| key.arg(arg),
+        )
+        .program(program);
+    cached(&key, DEFAULT_TTL, || get_output(program, args))
+}
+
+/// Runs a program in a directory and gets its standard output, reusing the output of a previous
+/// run as long as none of the files it depends on changed
+#[allow(clippy::type_complexity)]
+pub(crate) fn cached_output_in(
+    dir: &str,
+    program: &str,
+    args: &[&str],
+    files: &[&str],
+) -> Option<String> {
+    let command = join_script(&[&[program], args].concat());
+    let key = args
+        .iter()
+        .fold(
+            CacheKey::new(format!("output of `{command}` in {dir}")).arg(dir),
+            |key, arg| key.arg(arg),
+        )
+        .program(program);
+    let key = files
+        .iter()
+        .fold(key, |key, file| key.file(Path::new(dir).join(file)));
+    cached(&key, DEFAULT_TTL, || get_output_in(dir, program, args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_cache_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("build.gradle");
+        let missing = CacheKey::new("tasks").file(&file);
+        std::fs::write(&file, "").unwrap();
+        let written = CacheKey::new("tasks").file(&file);
+        assert_ne!(missing, written);
+        assert_eq!(written, CacheKey::new("tasks").file(&file));
+        assert_ne!(
+            CacheKey::new("tasks").arg("a b"),
+            CacheKey::new("tasks").arg("a").arg("b")
+        );
+        assert_eq!(written.name(), "tasks");
+    }
+
+    #[test]
+    fn test_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOOKUP_CACHE);
+        let now = SystemTime::now();
+        let fresh = CacheKey::new("fresh");
+        let stale = CacheKey::new("stale");
+
+        let mut cache = DiskCache::load(&path);
+        cache.insert(&fresh, serde_json::json!(["build"]), now + DEFAULT_TTL);
+        cache.insert(&stale, serde_json::json!(1), now - Duration::from_secs(1));
+        assert_eq!(cache.get(&stale, now), None);
+        cache.save(&path, now).unwrap();
+
+        let cache = DiskCache::load(&path);
+        assert_eq!(cache.get(&fresh, now), Some(&serde_json::json!(["build"])));
+        assert_eq!(cache.get(&fresh, now + DEFAULT_TTL), None);
+        assert_eq!(cache.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_cached() {
+        let calls = Cell::new(0);
+        let compute = || {
+            calls.set(calls.get() + 1);
+            vec!["build".to_string()]
+        };
+        let key = CacheKey::new("test_cached").arg("go");
+        assert_eq!(cached(&key, DEFAULT_TTL, compute), vec!["build"]);
+        let (value, lookups) = lookups::track(|| cached(&key, DEFAULT_TTL, compute));
+        assert_eq!(value, vec!["build"]);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(lookups, vec!["reused the cached test_cached"]);
+
+        let expired = CacheKey::new("test_cached").arg("expired");
+        cached(&expired, Duration::ZERO, compute);
+        cached(&expired, Duration::ZERO, compute);
+        assert_eq!(calls.get(), 3);
+    }
+}
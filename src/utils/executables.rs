@@ -0,0 +1,311 @@
+//! Index of the executables on `PATH`.
+//!
+//! The `PATH` directories are scanned once per process, and the result is cached on disk so the
+//! next run only rescans directories whose modification time changed.
+
+use crate::config::Settings;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+/// Name of the executable index inside the cache directory
+pub const EXECUTABLES_CACHE: &str = "executables.json";
+
+/// Executables found in one `PATH` directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexedDir {
+    path: PathBuf,
+    #[allow(clippy::type_complexity)]
+    modified: Option<SystemTime>,
+    #[allow(clippy::type_complexity)]
+    executables: Vec<String>,
+}
+
+impl IndexedDir {
+    #[allow(clippy::type_complexity)]
+    fn scan(path: &Path, modified: Option<SystemTime>) -> Self {
+        #[allow(clippy::type_complexity)]
+        let mut executables: Vec<String> = std::fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| is_executable(path))
+            .filter_map(|path| executable_name(&path))
+            .collect();
+        executables.sort();
+        executables.dedup();
+        Self {
+            path: path.to_path_buf(),
+            modified,
+            executables,
+        }
+    }
+}
+
+/// Index of the executables in a list of directories, in lookup order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutableIndex {
+    #[allow(clippy::type_complexity)]
+    dirs: Vec<IndexedDir>,
+}
+
+impl ExecutableIndex {
+    /// Indexes the directories, reusing the entries of `previous` that are still up to date
+    #[allow(clippy::type_complexity)]
+    pub fn scan(dirs: &[PathBuf], previous: Option<&ExecutableIndex>) -> Self {
+        let dirs = dirs
+            .iter()
+            .map(|dir| {
+                let modified = std::fs::metadata(dir)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                previous
+                    .and_then(|previous| previous.dirs.iter().find(|cached| cached.path == *dir))
+                    .filter(|cached| modified.is_some() && cached.modified == modified)
+                    .cloned()
+                    .unwrap_or_else(|| IndexedDir::scan(dir, modified))
+            })
+            .collect();
+        Self { dirs }
+    }
+
+    /// Loads an index cached in a file
+    #[allow(clippy::type_complexity)]
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Caches the index in a file
+    #[allow(clippy::type_complexity)]
+    pub fn save(&self, path: &Path) -> crate::TheFuckResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Gets the names of all indexed executables, sorted and without duplicates
+    #[allow(clippy::type_complexity)]
+    pub fn executables(&self) -> Vec<String> {
+        #[allow(clippy::type_complexity)]
+        let mut names: Vec<String> = self
+            .dirs
+            .iter()
+            .flat_map(|dir| dir.executables.iter().cloned())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Gets the path of the first indexed executable with a name
+    #[allow(clippy::type_complexity)]
+    pub fn which(&self, name: &str) -> Option<PathBuf> {
+        self.dirs
+            .iter()
+            .find(|dir| {
+                dir.executables
+                    .binary_search_by(|e| e.as_str().cmp(name))
+                    .is_ok()
+            })
+            .map(|dir| resolve(&dir.path, name))
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file() && executable_name(path).is_some()
+}
+
+/// Gets the name an executable is run by, e.g. `git` for `git.exe` on Windows
+#[cfg(not(windows))]
+#[allow(clippy::type_complexity)]
+fn executable_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// Gets the name an executable is run by, e.g. `git` for `git.exe` on Windows
+#[cfg(windows)]
+#[allow(clippy::type_complexity)]
+fn executable_name(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+    pathext
+        .split(';')
+        .any(|known| {
+            known
+                .trim_start_matches('.')
+                .eq_ignore_ascii_case(&extension)
+        })
+        .then(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })?
+}
+
+/// Gets the path of an indexed executable in a directory
+#[cfg(not(windows))]
+fn resolve(dir: &Path, name: &str) -> PathBuf {
+    dir.join(name)
+}
+
+/// Gets the path of an indexed executable in a directory
+#[cfg(windows)]
+fn resolve(dir: &Path, name: &str) -> PathBuf {
+    std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .find(|path| executable_name(path).as_deref() == Some(name))
+        .unwrap_or_else(|| dir.join(name))
+}
+
+/// Gets the index of the `PATH` directories, scanning them at most once per process
+#[allow(clippy::type_complexity)]
+fn index() -> &'static ExecutableIndex {
+    static INDEX: OnceLock<ExecutableIndex> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        #[allow(clippy::type_complexity)]
+        let dirs: Vec<PathBuf> = std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).collect())
+            .unwrap_or_default();
+        let cache = Settings::cache_dir().map(|dir| dir.join(EXECUTABLES_CACHE));
+        let previous = cache.as_deref().and_then(ExecutableIndex::load);
+        let index = ExecutableIndex::scan(&dirs, previous.as_ref());
+        if let Some(cache) = cache {
+            if previous.as_ref() != Some(&index) {
+                let _ = index.save(&cache);
+            }
+        }
+        index
+    })
+}
+
+/// Gets the names of all executables on `PATH`, sorted and without duplicates
+#[allow(clippy::type_complexity)]
+pub fn all_executables() -> &'static [String] {
+    super::lookups::record(|| "listed the executables on PATH".to_string());
+    super::system().executables()
+}
+
+/// Gets the names of the executables in the `PATH` directories, scanning them once per process
+#[allow(clippy::type_complexity)]
+pub(crate) fn path_executables() -> &'static [String] {
+    static EXECUTABLES: OnceLock<Vec<String>> = OnceLock::new();
+    EXECUTABLES.get_or_init(|| index().executables())
+}
+
+/// Finds an executable on `PATH`, or checks a path to one
+#[allow(clippy::type_complexity)]
+pub fn which(program: &str) -> Option<PathBuf> {
+    super::lookups::record(|| format!("looked up `{program}` on PATH"));
+    super::system().which(program)
+}
+
+/// Finds an executable in the `PATH` directories, or checks a path to one
+#[allow(clippy::type_complexity)]
+pub(crate) fn find_executable(program: &str) -> Option<PathBuf> {
+    if program.contains(std::path::MAIN_SEPARATOR) || program.contains('/') {
+        let path = PathBuf::from(program);
+        return is_executable(&path).then_some(path);
+    }
+    index().which(program)
+}
+
+/// Finds an executable in the directories of a `PATH` other than this process' one, without
+/// indexing them
+#[allow(clippy::type_complexity)]
+pub(crate) fn find_in_path(path: &str, program: &str) -> Option<PathBuf> {
+    if program.contains(std::path::MAIN_SEPARATOR) || program.contains('/') {
+        return find_executable(program);
+    }
+    std::env::split_paths(path).find_map(|dir| {
+        let candidate = resolve(&dir, program);
+        is_executable(&candidate).then_some(candidate)
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn executable(dir: &Path, name: &str) {
+        let path = dir.join(name);
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_scan() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        executable(first.path(), "git");
+        executable(second.path(), "git");
+        executable(second.path(), "cargo");
+        std::fs::write(second.path().join("README"), "").unwrap();
+
+        #[allow(clippy::type_complexity)]
+        let dirs = vec![
+            first.path().to_path_buf(),
+            second.path().to_path_buf(),
+            first.path().join("missing"),
+        ];
+        let index = ExecutableIndex::scan(&dirs, None);
+        assert_eq!(index.executables(), vec!["cargo", "git"]);
+        assert_eq!(index.which("git"), Some(first.path().join("git")));
+        assert_eq!(index.which("cargo"), Some(second.path().join("cargo")));
+        assert_eq!(index.which("README"), None);
+    }
+
+    #[test]
+    fn test_scan_reuses_up_to_date_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        executable(dir.path(), "git");
+        #[allow(clippy::type_complexity)]
+        let dirs = vec![dir.path().to_path_buf()];
+        let mut previous = ExecutableIndex::scan(&dirs, None);
+        previous.dirs[0].executables.push("stale".to_string());
+
+        let index = ExecutableIndex::scan(&dirs, Some(&previous));
+        assert_eq!(index.executables(), vec!["git", "stale"]);
+
+        previous.dirs[0].modified = Some(SystemTime::UNIX_EPOCH);
+        let index = ExecutableIndex::scan(&dirs, Some(&previous));
+        assert_eq!(index.executables(), vec!["git"]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        executable(dir.path(), "git");
+        let index = ExecutableIndex::scan(&[dir.path().to_path_buf()], None);
+
+        let cache = dir.path().join("cache").join(EXECUTABLES_CACHE);
+        assert_eq!(ExecutableIndex::load(&cache), None);
+        index.save(&cache).unwrap();
+        assert_eq!(ExecutableIndex::load(&cache), Some(index));
+    }
+
+    #[test]
+    fn test_which_path() {
+        let dir = tempfile::tempdir().unwrap();
+        executable(dir.path(), "script.sh");
+        let path = dir.path().join("script.sh");
+        assert_eq!(which(&path.to_string_lossy()), Some(path));
+        assert_eq!(which(&dir.path().join("missing").to_string_lossy()), None);
+    }
+}
@@ -0,0 +1,287 @@
+//! Fuzzy string matching for suggesting what a mistyped word was meant to be.
+//!
+//! ```
+//! use thefuck_rs::utils::fuzzy::{self, Scorer};
+//!
+//! let commands = ["push", "pull", "status"];
+//! assert_eq!(fuzzy::get_closest("psuh", &commands), Some("push".to_string()));
+//! assert_eq!(
+//!     fuzzy::get_close_matches_with("stauts", &commands, 1, 0.8, Scorer::JaroWinkler),
+//!     vec!["status"]
+//! );
+//! ```
+
+/// Minimum similarity for a possibility to count as a close match
+pub const DEFAULT_CUTOFF: f64 = 0.6;
+
+/// Maximum number of close matches returned when suggesting corrections
+pub const DEFAULT_MATCHES: usize = 3;
+
+/// Computes how similar two strings are, from 0.0 (nothing in common) to 1.0 (identical)
+///
+/// The score is twice the length of the longest common subsequence divided by the
+/// total length of both strings, which mirrors Python's `difflib` ratio closely enough
+/// for matching typos against command names.
+#[allow(clippy::type_complexity)]
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let total = a.len() + b.len();
+    if total == 0 {
+        return 1.0;
+    }
+
+    let mut previous = vec![0usize; b.len() + 1];
+    let mut current = vec![0usize; b.len() + 1];
+    for ca in &a {
+        for (j, cb) in b.iter().enumerate() {
+            current[j + 1] = if ca == cb {
+                previous[j] + 1
+            } else {
+                current[j].max(previous[j + 1])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    (2 * previous[b.len()]) as f64 / total as f64
+}
+
+/// Counts the single-character insertions, deletions and substitutions that turn `a` into `b`
+///
+/// ```
+/// assert_eq!(thefuck_rs::utils::fuzzy::levenshtein("kitten", "sitting"), 3);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0usize; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Computes the Levenshtein distance as a similarity, from 0.0 to 1.0 (identical)
+pub fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+/// Computes the Jaro similarity of two strings, from 0.0 to 1.0 (identical)
+#[allow(clippy::type_complexity)]
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0usize;
+    for (i, ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        for j in start..end {
+            if !b_matched[j] && b[j] == *ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0usize;
+    let mut j = 0;
+    for (i, ca) in a.iter().enumerate() {
+        if !a_matched[i] {
+            continue;
+        }
+        while !b_matched[j] {
+            j += 1;
+        }
+        if *ca != b[j] {
+            transpositions += 1;
+        }
+        j += 1;
+    }
+
+    let matches = matches as f64;
+    (matches / a.len() as f64
+        + matches / b.len() as f64
+        + (matches - (transpositions / 2) as f64) / matches)
+        / 3.0
+}
+
+/// Computes the Jaro-Winkler similarity, which favors strings with a common prefix
+///
+/// ```
+/// use thefuck_rs::utils::fuzzy::jaro_winkler;
+///
+/// assert!(jaro_winkler("checkout", "chekcout") > jaro_winkler("checkout", "tuokcehc"));
+/// ```
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let jaro = jaro(a, b);
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take(4)
+        .take_while(|(ca, cb)| ca == cb)
+        .count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// Scoring function used to rank possibilities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Scorer {
+    /// Longest common subsequence ratio, like Python's `difflib` (see [`similarity`])
+    #[default]
+    Ratio,
+    /// Normalized edit distance (see [`levenshtein_similarity`])
+    Levenshtein,
+    /// Jaro-Winkler similarity (see [`jaro_winkler`])
+    JaroWinkler,
+}
+
+impl Scorer {
+    /// Scores how similar two strings are, from 0.0 to 1.0 (identical)
+    pub fn score(self, a: &str, b: &str) -> f64 {
+        match self {
+            Scorer::Ratio => similarity(a, b),
+            Scorer::Levenshtein => levenshtein_similarity(a, b),
+            Scorer::JaroWinkler => jaro_winkler(a, b),
+        }
+    }
+}
+
+/// Gets up to `n` possibilities at least `cutoff` similar to `word`, best matches first
+#[allow(clippy::type_complexity)]
+pub fn get_close_matches<S: AsRef<str>>(
+    word: &str,
+    possibilities: &[S],
+    n: usize,
+    cutoff: f64,
+) -> Vec<String> {
+    get_close_matches_with(word, possibilities, n, cutoff, Scorer::Ratio)
+}
+
+/// Gets up to `n` possibilities at least `cutoff` similar to `word` by a scorer, best first
+#[allow(clippy::type_complexity)]
+pub fn get_close_matches_with<S: AsRef<str>>(
+    word: &str,
+    possibilities: &[S],
+    n: usize,
+    cutoff: f64,
+    scorer: Scorer,
+) -> Vec<String> {
+    let mut scored: Vec<(f64, &str)> = possibilities
+        .iter()
+        .map(|p| (scorer.score(word, p.as_ref()), p.as_ref()))
+        .filter(|(score, _)| *score >= cutoff)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut matches: Vec<String> = Vec::new();
+    for (_, possibility) in scored {
+        if matches.len() == n {
+            break;
+        }
+        if !matches.iter().any(|m| m == possibility) {
+            matches.push(possibility.to_string());
+        }
+    }
+    matches
+}
+
+/// Gets the possibility closest to `word`, if any is similar enough
+#[allow(clippy::type_complexity)]
+pub fn get_closest<S: AsRef<str>>(word: &str, possibilities: &[S]) -> Option<String> {
+    get_close_matches(word, possibilities, 1, DEFAULT_CUTOFF)
+        .into_iter()
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("push", "push"), 1.0);
+        assert_eq!(similarity("abc", "xyz"), 0.0);
+        assert_eq!(similarity("", ""), 1.0);
+        assert!(similarity("biuld", "build") >= DEFAULT_CUTOFF);
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("push", "push"), 0);
+        assert_eq!(levenshtein_similarity("", ""), 1.0);
+        assert_eq!(levenshtein_similarity("psuh", "push"), 0.5);
+    }
+
+    #[test]
+    fn test_jaro_winkler() {
+        assert!((jaro("martha", "marhta") - 0.944).abs() < 0.001);
+        assert!((jaro_winkler("martha", "marhta") - 0.961).abs() < 0.001);
+        assert!((jaro_winkler("dixon", "dicksonx") - 0.813).abs() < 0.001);
+        assert_eq!(jaro_winkler("", ""), 1.0);
+        assert_eq!(jaro_winkler("abc", ""), 0.0);
+        assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+    }
+
+    #[test]
+    fn test_get_close_matches() {
+        let possibilities = ["install", "uninstall", "info", "list"];
+        let matches = get_close_matches("instal", &possibilities, 3, DEFAULT_CUTOFF);
+        assert_eq!(matches[0], "install");
+        assert!(!matches.contains(&"info".to_string()));
+
+        assert!(get_close_matches("zzz", &possibilities, 3, DEFAULT_CUTOFF).is_empty());
+        assert_eq!(get_close_matches("instal", &possibilities, 1, 0.0).len(), 1);
+    }
+
+    #[test]
+    fn test_get_close_matches_with() {
+        let possibilities = ["checkout", "cherry-pick", "commit"];
+        for scorer in [Scorer::Ratio, Scorer::Levenshtein, Scorer::JaroWinkler] {
+            let matches = get_close_matches_with("chekcout", &possibilities, 3, 0.6, scorer);
+            assert_eq!(matches[0], "checkout");
+        }
+        assert_eq!(
+            get_close_matches_with("chekcout", &possibilities, 3, 0.99, Scorer::JaroWinkler),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_get_closest() {
+        assert_eq!(
+            get_closest("psh", &["pull", "push"]),
+            Some("push".to_string())
+        );
+        assert_eq!(get_closest("zzz", &["pull", "push"]), None);
+    }
+}
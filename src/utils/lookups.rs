@@ -0,0 +1,47 @@
+//! Tracks the external lookups rules perform, such as running programs or reading files.
+//!
+//! Helpers that reach outside the process record what they did, and the registry tracks the
+//! lookups of each rule so they can be shown alongside its corrections.
+
+use std::cell::RefCell;
+
+thread_local! {
+    #[allow(clippy::type_complexity)]
+    static LOOKUPS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Records a lookup if lookups are being tracked on this thread
+pub(crate) fn record(describe: impl FnOnce() -> String) {
+    LOOKUPS.with(|lookups| {
+        if let Some(lookups) = lookups.borrow_mut().as_mut() {
+            lookups.push(describe());
+        }
+    });
+}
+
+/// Runs a function, returning its result and the lookups it recorded
+#[allow(clippy::type_complexity)]
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let outer = LOOKUPS.with(|lookups| lookups.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let tracked = LOOKUPS.with(|lookups| std::mem::replace(&mut *lookups.borrow_mut(), outer));
+    (result, tracked.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track() {
+        record(|| "untracked".to_string());
+        let (value, lookups) = track(|| {
+            record(|| "outer".to_string());
+            let ((), inner) = track(|| record(|| "inner".to_string()));
+            assert_eq!(inner, vec!["inner"]);
+            42
+        });
+        assert_eq!(value, 42);
+        assert_eq!(lookups, vec!["outer"]);
+    }
+}
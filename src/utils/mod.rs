@@ -1 +1,642 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command as ProcessCommand, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::{Duration, Instant};
 
+pub mod cache;
+pub mod executables;
+pub mod fuzzy;
+pub mod lookups;
+pub mod profile;
+pub mod resolve;
+pub mod suggestions;
+pub mod system;
+
+pub use cache::{CacheKey, DEFAULT_TTL, cached};
+pub(crate) use cache::{cached_output, cached_output_in};
+pub use executables::{all_executables, which};
+pub use fuzzy::{
+    DEFAULT_CUTOFF, DEFAULT_MATCHES, Scorer, get_close_matches, get_close_matches_with,
+    get_closest, jaro_winkler, levenshtein, similarity,
+};
+pub use resolve::{Resolution, ShellNames, is_available, resolve};
+pub use suggestions::extract_suggestions;
+pub use system::{NativeSystem, NoSystem, System, set_system, system};
+
+/// A word of a script, as written and as the shell would read it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Word {
+    /// Byte offset of the word in the script
+    pub start: usize,
+    /// Byte offset just past the word in the script
+    pub end: usize,
+    /// The word without its quotes and escapes
+    pub value: String,
+    /// The quote wrapping the whole word, e.g. `'` for `'a b'`
+    #[allow(clippy::type_complexity)]
+    pub quote: Option<char>,
+}
+
+/// Splits a script into words, keeping where each one was written
+///
+/// Unlike [`split_script`] this never fails: an unterminated quote runs to the end of the script.
+#[allow(clippy::type_complexity)]
+pub(crate) fn script_words(script: &str) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut chars = script.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut value = String::new();
+        let mut end = start;
+        let mut quoted: Vec<char> = Vec::new();
+        while let Some(&(index, c)) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            chars.next();
+            end = index + c.len_utf8();
+            match c {
+                '\'' | '"' => {
+                    quoted.push(c);
+                    while let Some((index, inner)) = chars.next() {
+                        end = index + inner.len_utf8();
+                        if inner == c {
+                            break;
+                        }
+                        match chars.peek() {
+                            Some(&(index, escaped)) if inner == '\\' && c == '"' => {
+                                chars.next();
+                                end = index + escaped.len_utf8();
+                                value.push(escaped);
+                            }
+                            _ => value.push(inner),
+                        }
+                    }
+                }
+                '\\' => {
+                    if let Some((index, escaped)) = chars.next() {
+                        end = index + escaped.len_utf8();
+                        value.push(escaped);
+                    }
+                }
+                _ => value.push(c),
+            }
+        }
+        let raw = &script[start..end];
+        let quote = match quoted.as_slice() {
+            [quote] if raw.len() > 1 && raw.starts_with(*quote) && raw.ends_with(*quote) => {
+                Some(*quote)
+            }
+            _ => None,
+        };
+        words.push(Word {
+            start,
+            end,
+            value,
+            quote,
+        });
+    }
+    words
+}
+
+/// Quotes a word the way the word it replaces was quoted, escaping what that quote needs
+///
+/// An unquoted word is single-quoted if the shell would otherwise split or expand it.
+#[allow(clippy::type_complexity)]
+fn quote_like(new: &str, quote: Option<char>) -> String {
+    let single_quoted = || format!("'{}'", new.replace('\'', r"'\''"));
+    match quote {
+        Some('\'') => single_quoted(),
+        Some(quote) => {
+            let mut quoted = String::with_capacity(new.len() + 2);
+            quoted.push(quote);
+            for c in new.chars() {
+                if matches!(c, '"' | '\\' | '$' | '`') {
+                    quoted.push('\\');
+                }
+                quoted.push(c);
+            }
+            quoted.push(quote);
+            quoted
+        }
+        None if new.is_empty()
+            || new
+                .chars()
+                .any(|c| c.is_whitespace() || "'\"\\$`;&|<>()*?[]!#".contains(c)) =>
+        {
+            single_quoted()
+        }
+        None => new.to_string(),
+    }
+}
+
+/// Rewrites words of a script, keeping the rest verbatim
+#[allow(clippy::type_complexity)]
+pub(crate) fn rewrite_words(
+    script: &str,
+    words: &[&Word],
+    rewrite: impl Fn(&Word) -> String,
+) -> String {
+    let mut replaced = String::with_capacity(script.len());
+    let mut last = 0;
+    for word in words {
+        replaced.push_str(&script[last..word.start]);
+        replaced.push_str(&rewrite(word));
+        last = word.end;
+    }
+    replaced.push_str(&script[last..]);
+    replaced
+}
+
+/// Replaces words of a script with one word, quoted like each word it replaces
+#[allow(clippy::type_complexity)]
+fn replace_words(script: &str, words: &[&Word], new: &str) -> String {
+    rewrite_words(script, words, |word| quote_like(new, word.quote))
+}
+
+/// Gets the arguments of a script that are `old`, either as written or as the shell reads them
+#[allow(clippy::type_complexity)]
+fn matching_arguments<'a>(script: &str, words: &'a [Word], old: &str) -> Vec<&'a Word> {
+    words
+        .iter()
+        .skip(1)
+        .filter(|word| word.value == old || &script[word.start..word.end] == old)
+        .collect()
+}
+
+/// Replaces the first argument equal to `old` with `new`
+///
+/// The program name is never replaced, a quoted argument stays quoted the same way and the rest
+/// of the script is kept verbatim.
+pub(crate) fn replace_argument(script: &str, old: &str, new: &str) -> String {
+    let words = script_words(script);
+    let matching = matching_arguments(script, &words, old);
+    replace_words(script, &matching[..matching.len().min(1)], new)
+}
+
+/// Replaces every argument equal to `old` with `new`, like [`replace_argument`]
+pub(crate) fn replace_all_arguments(script: &str, old: &str, new: &str) -> String {
+    let words = script_words(script);
+    replace_words(script, &matching_arguments(script, &words, old), new)
+}
+
+/// Replaces the first argument equal to `old` with `new` as written, which can be several words
+///
+/// Rule scripts rely on this, e.g. `push` -> `push --set-upstream origin main`, like the original
+/// thefuck's `replace_argument`.
+#[cfg(feature = "rhai-rules")]
+pub(crate) fn replace_argument_words(script: &str, old: &str, new: &str) -> String {
+    let words = script_words(script);
+    let matching = matching_arguments(script, &words, old);
+    rewrite_words(script, &matching[..matching.len().min(1)], |_| {
+        new.to_string()
+    })
+}
+
+/// Replaces every argument equal to `old` with `new` as written, like [`replace_argument_words`]
+#[cfg(feature = "rhai-rules")]
+pub(crate) fn replace_all_argument_words(script: &str, old: &str, new: &str) -> String {
+    let words = script_words(script);
+    rewrite_words(script, &matching_arguments(script, &words, old), |_| {
+        new.to_string()
+    })
+}
+
+/// Inserts words, as written, before the first argument equal to `arg`, e.g. `run` in
+/// `pnpm build` -> `pnpm run build`
+pub(crate) fn insert_before_argument(script: &str, arg: &str, words: &str) -> String {
+    let parsed = script_words(script);
+    let matching = matching_arguments(script, &parsed, arg);
+    rewrite_words(script, &matching[..matching.len().min(1)], |word| {
+        format!("{words} {}", &script[word.start..word.end])
+    })
+}
+
+/// Inserts words, as written, after the first argument equal to `arg`, e.g. `--user` in
+/// `pip install x` -> `pip install --user x`
+pub(crate) fn insert_after_argument(script: &str, arg: &str, words: &str) -> String {
+    let parsed = script_words(script);
+    let matching = matching_arguments(script, &parsed, arg);
+    rewrite_words(script, &matching[..matching.len().min(1)], |word| {
+        format!("{} {words}", &script[word.start..word.end])
+    })
+}
+
+/// Removes every argument equal to `arg`, with the whitespace before it
+pub(crate) fn remove_all_arguments(script: &str, arg: &str) -> String {
+    let words = script_words(script);
+    let mut removed = String::with_capacity(script.len());
+    let mut last = 0;
+    for word in matching_arguments(script, &words, arg) {
+        removed.push_str(script[last..word.start].trim_end());
+        last = word.end;
+    }
+    removed.push_str(&script[last..]);
+    removed
+}
+
+/// Replaces the program of a script, e.g. `gradle` with `./gradlew`, keeping its arguments verbatim
+pub(crate) fn replace_program(script: &str, program: &str) -> String {
+    match script_words(script).first() {
+        Some(word) => format!("{}{program}{}", &script[..word.start], &script[word.end..]),
+        None => program.to_string(),
+    }
+}
+
+/// Builds one corrected script per close match of the `broken` argument
+#[allow(clippy::type_complexity)]
+pub(crate) fn replace_command<S: AsRef<str>>(
+    script: &str,
+    broken: &str,
+    possibilities: &[S],
+) -> Vec<String> {
+    get_close_matches(broken, possibilities, DEFAULT_MATCHES, DEFAULT_CUTOFF)
+        .into_iter()
+        .map(|fixed| replace_argument(script, broken, &fixed))
+        .collect()
+}
+
+/// Builds one corrected script per suggestion the program made for the `broken` argument, then
+/// one per close match among the `known` ones it didn't suggest
+#[allow(clippy::type_complexity)]
+pub(crate) fn suggested_fixes<S: AsRef<str>>(
+    script: &str,
+    broken: &str,
+    suggestions: &[String],
+    known: &[S],
+) -> Vec<String> {
+    let mut fixes: Vec<String> = suggestions
+        .iter()
+        .map(|fixed| replace_argument(script, broken, fixed))
+        .collect();
+    for fix in replace_command(script, broken, known) {
+        if !fixes.contains(&fix) {
+            fixes.push(fix);
+        }
+    }
+    fixes
+}
+
+/// Splits a script into words the way a POSIX shell would, honoring quotes and escapes
+#[allow(clippy::type_complexity)]
+pub(crate) fn split_script(script: &str) -> Option<Vec<String>> {
+    shlex::split(script)
+}
+
+/// Joins words into a script, quoting the ones that need it
+#[allow(clippy::type_complexity)]
+pub(crate) fn join_script<S: AsRef<str>>(words: &[S]) -> String {
+    words
+        .iter()
+        .map(|word| {
+            shlex::try_quote(word.as_ref())
+                .map(|quoted| quoted.into_owned())
+                .unwrap_or_else(|_| word.as_ref().to_string())
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Runs a program and gets its standard output, if it ran successfully
+#[allow(clippy::type_complexity)]
+pub(crate) fn get_output(program: &str, args: &[&str]) -> Option<String> {
+    lookups::record(|| format!("ran `{}`", join_script(&[&[program], args].concat())));
+    system().output(program, args, None)
+}
+
+/// Runs a program in a directory and gets its standard output, if it ran successfully
+#[allow(clippy::type_complexity)]
+pub(crate) fn get_output_in(dir: &str, program: &str, args: &[&str]) -> Option<String> {
+    lookups::record(|| {
+        format!(
+            "ran `{}` in {dir}",
+            join_script(&[&[program], args].concat())
+        )
+    });
+    system().output(program, args, Some(dir))
+}
+
+/// Runs a program and gets its standard output, if it ran successfully
+#[allow(clippy::type_complexity)]
+fn run_for_output(command: &mut ProcessCommand) -> Option<String> {
+    let output = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Output of a program run with a time limit
+#[derive(Debug)]
+pub(crate) struct TimedOutput {
+    pub(crate) status: ExitStatus,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+    /// Whether the program was killed for running out of time
+    pub(crate) timed_out: bool,
+}
+
+/// Time the output of a killed program is still read for, in case it was in flight
+const KILLED_OUTPUT_GRACE: Duration = Duration::from_millis(100);
+
+/// Runs a program with `input` on stdin, killing it if it runs longer than `timeout`
+///
+/// On Unix the program runs in its own process group, so what it started is killed with it, and
+/// its output is only read until the time runs out, in case something it started holds on to it.
+#[allow(clippy::type_complexity)]
+pub(crate) fn run_with_timeout(
+    command: &mut ProcessCommand,
+    input: &str,
+    timeout: Duration,
+) -> crate::TheFuckResult<TimedOutput> {
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Neither thread is joined, since a leftover process can keep the pipes open forever
+    let mut stdin = child.stdin.take().expect("piped stdin");
+    let input = input.to_string();
+    std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let read_all = |mut pipe: Box<dyn Read + Send>| {
+        let bytes = Arc::new(Mutex::new(Vec::new()));
+        let (done, finished) = mpsc::channel::<()>();
+        let read = Arc::clone(&bytes);
+        std::thread::spawn(move || {
+            let mut chunk = [0; 8192];
+            loop {
+                match pipe.read(&mut chunk) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => read
+                        .lock()
+                        .unwrap_or_else(|p| p.into_inner())
+                        .extend_from_slice(&chunk[..n]),
+                }
+            }
+            drop(done);
+        });
+        (bytes, finished)
+    };
+    let stdout = read_all(Box::new(child.stdout.take().expect("piped stdout")));
+    let stderr = read_all(Box::new(child.stderr.take().expect("piped stderr")));
+
+    let deadline = Instant::now() + timeout;
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            timed_out = true;
+            kill_group(&mut child);
+            break child.wait()?;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    };
+    let reading_until = if timed_out {
+        Instant::now() + KILLED_OUTPUT_GRACE
+    } else {
+        deadline
+    };
+    #[allow(clippy::type_complexity)]
+    let collect = |(bytes, finished): (Arc<Mutex<Vec<u8>>>, mpsc::Receiver<()>)| {
+        let _ = finished.recv_timeout(reading_until.saturating_duration_since(Instant::now()));
+        let bytes = bytes.lock().unwrap_or_else(|p| p.into_inner());
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+    Ok(TimedOutput {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        timed_out,
+    })
+}
+
+/// Kills a program and everything in its process group
+#[cfg(unix)]
+fn kill_group(child: &mut Child) {
+    // SAFETY: `kill` has no memory safety requirements; a negative pid targets a process group
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+}
+
+/// Kills a program
+#[cfg(not(unix))]
+fn kill_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+/// Builds the command that opens a file or URL with the default application
+pub(crate) fn open_command(target: &str) -> String {
+    if cfg!(target_os = "macos") {
+        format!("open {target}")
+    } else if cfg!(windows) {
+        format!("start {target}")
+    } else {
+        format!("xdg-open {target}")
+    }
+}
+
+/// Gets the names of the scripts defined in the `package.json` of a directory
+#[allow(clippy::type_complexity)]
+pub(crate) fn package_json_scripts(dir: &str) -> Vec<String> {
+    let path = Path::new(dir).join("package.json");
+    lookups::record(|| format!("read {}", path.display()));
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    package
+        .get("scripts")
+        .and_then(|scripts| scripts.as_object())
+        .map(|scripts| scripts.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_argument() {
+        assert_eq!(
+            replace_argument("git psh origin", "psh", "push"),
+            "git push origin"
+        );
+        assert_eq!(replace_argument("git psh", "psh", "push"), "git push");
+        assert_eq!(replace_argument("psh psh", "psh", "push"), "psh push");
+        assert_eq!(replace_argument("git pshx", "psh", "push"), "git pshx");
+        assert_eq!(
+            replace_argument("git commit -m 'psh fix' 'psh'", "psh", "push"),
+            "git commit -m 'psh fix' 'push'"
+        );
+        assert_eq!(
+            replace_argument("rm \"my fle.txt\"", "my fle.txt", "my file.txt"),
+            "rm \"my file.txt\""
+        );
+        assert_eq!(
+            replace_all_arguments("cp src/a.rs  src/a.rs", "src/a.rs", "src/b.rs"),
+            "cp src/b.rs  src/b.rs"
+        );
+    }
+
+    #[test]
+    fn test_script_words() {
+        #[allow(clippy::type_complexity)]
+        let words: Vec<(String, Option<char>)> = script_words(r#"git  "a b"'c' \$x 'unterminated"#)
+            .into_iter()
+            .map(|word| (word.value, word.quote))
+            .collect();
+        assert_eq!(
+            words,
+            vec![
+                ("git".to_string(), None),
+                ("a bc".to_string(), None),
+                ("$x".to_string(), None),
+                ("unterminated".to_string(), None),
+            ]
+        );
+        assert_eq!(script_words("echo 'a b'")[1].quote, Some('\''));
+        assert_eq!(script_words("echo 'a b'")[1].end, 10);
+    }
+
+    #[test]
+    fn test_replace_program() {
+        assert_eq!(
+            replace_program("gradle build 'my task'", "./gradlew"),
+            "./gradlew build 'my task'"
+        );
+        assert_eq!(replace_program("", "ls"), "ls");
+    }
+
+    #[test]
+    fn test_replace_command() {
+        let fixed = replace_command("brew instal wget", "instal", &["install", "uninstall"]);
+        assert_eq!(fixed[0], "brew install wget");
+    }
+
+    #[test]
+    fn test_suggested_fixes() {
+        let known = ["describe", "delete", "get"];
+        assert_eq!(
+            suggested_fixes(
+                "kubectl descrbe pod",
+                "descrbe",
+                &["describe".to_string(), "debug".to_string()],
+                &known,
+            ),
+            vec!["kubectl describe pod", "kubectl debug pod"]
+        );
+        assert_eq!(
+            suggested_fixes("kubectl descrbe pod", "descrbe", &[], &known),
+            vec!["kubectl describe pod"]
+        );
+    }
+
+    #[test]
+    fn test_split_and_join_script() {
+        assert_eq!(
+            split_script("sed -e 's/a b/c/' file"),
+            Some(vec![
+                "sed".to_string(),
+                "-e".to_string(),
+                "s/a b/c/".to_string(),
+                "file".to_string()
+            ])
+        );
+        assert_eq!(split_script("echo 'unterminated"), None);
+        assert_eq!(
+            join_script(&["sed", "-e", "s/a b/c/", "file"]),
+            "sed -e 's/a b/c/' file"
+        );
+    }
+
+    #[test]
+    fn test_replace_argument_quoting() {
+        assert_eq!(
+            replace_argument("echo 'a' x", "a", "it's"),
+            r"echo 'it'\''s' x"
+        );
+        assert_eq!(
+            replace_argument(r#"echo "a""#, "a", r#"say "$hi""#),
+            r#"echo "say \"\$hi\"""#
+        );
+        assert_eq!(replace_argument("cat a", "a", "my file"), "cat 'my file'");
+        assert_eq!(replace_argument("cat a", "a", "b;c"), "cat 'b;c'");
+        assert_eq!(
+            replace_argument("grunt buld:dev", "buld:dev", "build:dev"),
+            "grunt build:dev"
+        );
+    }
+
+    #[cfg(feature = "rhai-rules")]
+    #[test]
+    fn test_replace_argument_words() {
+        assert_eq!(
+            replace_argument_words("git push", "push", "push --set-upstream origin main"),
+            "git push --set-upstream origin main"
+        );
+        assert_eq!(
+            replace_all_argument_words("cp 'a' b a", "a", "c d"),
+            "cp c d b c d"
+        );
+    }
+
+    #[test]
+    fn test_insert_around_argument() {
+        assert_eq!(
+            insert_before_argument("pnpm 'typecheck' --watch", "typecheck", "run"),
+            "pnpm run 'typecheck' --watch"
+        );
+        assert_eq!(
+            insert_before_argument("deno build", "build", "task"),
+            "deno task build"
+        );
+        assert_eq!(insert_before_argument("deno", "build", "task"), "deno");
+        assert_eq!(
+            remove_all_arguments("pip --user install --user 'x y'", "--user"),
+            "pip install 'x y'"
+        );
+        assert_eq!(
+            insert_after_argument(r#"pip install "requests""#, "install", "--user"),
+            r#"pip install --user "requests""#
+        );
+    }
+
+    #[test]
+    fn test_open_command() {
+        let command = open_command("https://yarnpkg.com");
+        assert!(command.ends_with(" https://yarnpkg.com"));
+    }
+
+    #[test]
+    fn test_package_json_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        assert!(package_json_scripts(&path).is_empty());
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "app", "scripts": {"build": "tsc", "test": "jest"}}"#,
+        )
+        .unwrap();
+        let mut scripts = package_json_scripts(&path);
+        scripts.sort();
+        assert_eq!(scripts, vec!["build", "test"]);
+    }
+}
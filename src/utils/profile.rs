@@ -0,0 +1,143 @@
+//! Times the phases of each rule, to find the slow ones (typically those running programs).
+//!
+//! The registry records how long each rule took to match and to build its corrections, and
+//! `--profile` tracks those timings to print them as a table.
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+thread_local! {
+    #[allow(clippy::type_complexity)]
+    static TIMINGS: RefCell<Option<Vec<RuleTiming>>> = const { RefCell::new(None) };
+}
+
+/// How long a rule took to check a failed command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTiming {
+    /// Name of the rule
+    pub rule: String,
+    /// Time spent checking if the rule matched
+    pub matching: Duration,
+    /// Time spent building the corrections, zero if the rule didn't match
+    pub correcting: Duration,
+}
+
+impl RuleTiming {
+    /// Gets the time spent in both phases
+    pub fn total(&self) -> Duration {
+        self.matching + self.correcting
+    }
+}
+
+/// Records the timing of a rule if timings are being tracked on this thread
+pub(crate) fn record(rule: &str, matching: Duration, correcting: Duration) {
+    TIMINGS.with(|timings| {
+        if let Some(timings) = timings.borrow_mut().as_mut() {
+            timings.push(RuleTiming {
+                rule: rule.to_string(),
+                matching,
+                correcting,
+            });
+        }
+    });
+}
+
+/// Runs a function, returning its result and the rule timings it recorded
+#[allow(clippy::type_complexity)]
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Vec<RuleTiming>) {
+    let outer = TIMINGS.with(|timings| timings.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let tracked = TIMINGS.with(|timings| std::mem::replace(&mut *timings.borrow_mut(), outer));
+    (result, tracked.unwrap_or_default())
+}
+
+/// Formats a duration in milliseconds
+fn millis(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Describes rule timings as a table, slowest rule first
+#[allow(clippy::type_complexity)]
+pub fn report(timings: &[RuleTiming]) -> String {
+    let mut timings = timings.to_vec();
+    timings.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.rule.cmp(&b.rule)));
+    let width = timings
+        .iter()
+        .map(|timing| timing.rule.len())
+        .max()
+        .unwrap_or(0)
+        .max("TOTAL".len());
+
+    let mut report = format!(
+        "{:width$}  {:>12}  {:>12}  {:>12}\n",
+        "RULE", "MATCH", "CORRECT", "TOTAL"
+    );
+    for timing in &timings {
+        report.push_str(&format!(
+            "{:width$}  {:>12}  {:>12}  {:>12}\n",
+            timing.rule,
+            millis(timing.matching),
+            millis(timing.correcting),
+            millis(timing.total())
+        ));
+    }
+    let matching: Duration = timings.iter().map(|timing| timing.matching).sum();
+    let correcting: Duration = timings.iter().map(|timing| timing.correcting).sum();
+    report.push_str(&format!(
+        "{:width$}  {:>12}  {:>12}  {:>12}\n",
+        "TOTAL",
+        millis(matching),
+        millis(correcting),
+        millis(matching + correcting)
+    ));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track() {
+        record("untracked", Duration::ZERO, Duration::ZERO);
+        let (value, timings) = track(|| {
+            record("java", Duration::from_millis(1), Duration::ZERO);
+            42
+        });
+        assert_eq!(value, 42);
+        assert_eq!(timings.len(), 1);
+        assert_eq!(timings[0].rule, "java");
+    }
+
+    #[test]
+    fn test_report() {
+        let timings = vec![
+            RuleTiming {
+                rule: "java".to_string(),
+                matching: Duration::from_micros(10),
+                correcting: Duration::ZERO,
+            },
+            RuleTiming {
+                rule: "brew_unknown_command".to_string(),
+                matching: Duration::from_micros(500),
+                correcting: Duration::from_millis(25),
+            },
+        ];
+        let report = report(&timings);
+        #[allow(clippy::type_complexity)]
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(
+            lines[0],
+            "RULE                         MATCH       CORRECT         TOTAL"
+        );
+        assert_eq!(
+            lines[1],
+            "brew_unknown_command      0.500 ms     25.000 ms     25.500 ms"
+        );
+        assert!(lines[2].starts_with("java "));
+        assert_eq!(
+            lines[3],
+            "TOTAL                     0.510 ms     25.000 ms     25.510 ms"
+        );
+    }
+}
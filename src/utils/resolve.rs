@@ -0,0 +1,468 @@
+//! Resolves a command name the way the user's shell would.
+//!
+//! A name runs an alias, a shell function or a builtin before any executable on `PATH`, so
+//! checking `PATH` alone misses `ll`, `nvm` or `cd`. The shell hooks export the user's aliases
+//! and function names when thefuck-rs runs, and names are looked up in those first.
+//!
+//! Names are resolved in the environment of the failed command, not of this process, which is
+//! the daemon's when it answers.
+
+use super::{executables, lookups, which};
+use crate::types::{Command, Shell};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Environment variable the shell hooks export the user's aliases in, as printed by `alias`
+pub const ALIASES_ENV: &str = "TF_SHELL_ALIASES";
+
+/// Environment variable the shell hooks export the user's function names in
+pub const FUNCTIONS_ENV: &str = "TF_SHELL_FUNCTIONS";
+
+/// Commands built into bash
+#[allow(clippy::type_complexity)]
+const BASH_BUILTINS: &[&str] = &[
+    ".",
+    ":",
+    "[",
+    "alias",
+    "bg",
+    "bind",
+    "break",
+    "builtin",
+    "caller",
+    "cd",
+    "command",
+    "compgen",
+    "complete",
+    "compopt",
+    "continue",
+    "declare",
+    "dirs",
+    "disown",
+    "echo",
+    "enable",
+    "eval",
+    "exec",
+    "exit",
+    "export",
+    "false",
+    "fc",
+    "fg",
+    "getopts",
+    "hash",
+    "help",
+    "history",
+    "jobs",
+    "kill",
+    "let",
+    "local",
+    "logout",
+    "mapfile",
+    "popd",
+    "printf",
+    "pushd",
+    "pwd",
+    "read",
+    "readarray",
+    "readonly",
+    "return",
+    "set",
+    "shift",
+    "shopt",
+    "source",
+    "suspend",
+    "test",
+    "times",
+    "trap",
+    "true",
+    "type",
+    "typeset",
+    "ulimit",
+    "umask",
+    "unalias",
+    "unset",
+    "wait",
+];
+
+/// Commands built into zsh
+#[allow(clippy::type_complexity)]
+const ZSH_BUILTINS: &[&str] = &[
+    ".",
+    ":",
+    "[",
+    "alias",
+    "autoload",
+    "bg",
+    "bindkey",
+    "break",
+    "builtin",
+    "bye",
+    "cd",
+    "chdir",
+    "command",
+    "compctl",
+    "continue",
+    "declare",
+    "dirs",
+    "disown",
+    "echo",
+    "emulate",
+    "enable",
+    "eval",
+    "exec",
+    "exit",
+    "export",
+    "false",
+    "fc",
+    "fg",
+    "functions",
+    "getopts",
+    "hash",
+    "history",
+    "integer",
+    "jobs",
+    "kill",
+    "let",
+    "limit",
+    "local",
+    "logout",
+    "noglob",
+    "popd",
+    "print",
+    "printf",
+    "pushd",
+    "pushln",
+    "pwd",
+    "read",
+    "readonly",
+    "rehash",
+    "return",
+    "sched",
+    "set",
+    "setopt",
+    "shift",
+    "source",
+    "suspend",
+    "test",
+    "times",
+    "trap",
+    "true",
+    "type",
+    "typeset",
+    "ulimit",
+    "umask",
+    "unalias",
+    "unfunction",
+    "unhash",
+    "unlimit",
+    "unset",
+    "unsetopt",
+    "vared",
+    "wait",
+    "whence",
+    "where",
+    "which",
+    "zcompile",
+    "zle",
+    "zmodload",
+    "zparseopts",
+    "zstyle",
+];
+
+/// Commands built into fish
+#[allow(clippy::type_complexity)]
+const FISH_BUILTINS: &[&str] = &[
+    "abbr",
+    "and",
+    "argparse",
+    "begin",
+    "bg",
+    "bind",
+    "block",
+    "break",
+    "builtin",
+    "case",
+    "cd",
+    "command",
+    "commandline",
+    "complete",
+    "contains",
+    "continue",
+    "count",
+    "disown",
+    "echo",
+    "else",
+    "emit",
+    "end",
+    "eval",
+    "exec",
+    "exit",
+    "false",
+    "fg",
+    "for",
+    "function",
+    "functions",
+    "history",
+    "if",
+    "jobs",
+    "math",
+    "not",
+    "or",
+    "path",
+    "printf",
+    "pwd",
+    "random",
+    "read",
+    "realpath",
+    "return",
+    "set",
+    "set_color",
+    "source",
+    "status",
+    "string",
+    "switch",
+    "test",
+    "time",
+    "true",
+    "type",
+    "ulimit",
+    "wait",
+    "while",
+];
+
+/// Commands built into cmd
+#[allow(clippy::type_complexity)]
+const CMD_BUILTINS: &[&str] = &[
+    "assoc", "break", "call", "cd", "chdir", "cls", "color", "copy", "date", "del", "dir", "echo",
+    "endlocal", "erase", "exit", "for", "ftype", "goto", "if", "md", "mkdir", "mklink", "move",
+    "path", "pause", "popd", "prompt", "pushd", "rd", "rem", "ren", "rename", "rmdir", "set",
+    "setlocal", "shift", "start", "time", "title", "type", "ver", "verify", "vol",
+];
+
+/// What a command name runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// An alias, with what it expands to
+    Alias(String),
+    /// A shell function
+    Function,
+    /// A command built into the shell
+    Builtin,
+    /// An executable on `PATH`, or the path the name pointed to
+    Executable(PathBuf),
+}
+
+/// Gets the commands built into a shell
+#[allow(clippy::type_complexity)]
+fn builtins(shell: &Shell) -> &'static [&'static str] {
+    match shell {
+        Shell::Bash | Shell::Unknown(_) => BASH_BUILTINS,
+        Shell::Zsh => ZSH_BUILTINS,
+        Shell::Fish => FISH_BUILTINS,
+        Shell::Cmd => CMD_BUILTINS,
+        Shell::PowerShell => &[],
+    }
+}
+
+/// Parses aliases as printed by `alias`, e.g. `alias ll='ls -l'` in bash, `ll='ls -l'` in zsh
+/// or `alias ll 'ls -l'` in fish
+#[allow(clippy::type_complexity)]
+pub fn parse_aliases(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("alias ").unwrap_or(line).trim_start();
+            let (name, value) = match line.split_once('=') {
+                Some((name, value)) if !name.contains(char::is_whitespace) => (name, value),
+                _ => line.split_once(char::is_whitespace)?,
+            };
+            let value = super::split_script(value.trim())
+                .map(|words| words.join(" "))
+                .unwrap_or_else(|| value.trim().to_string());
+            (!name.is_empty()).then(|| (name.to_string(), value))
+        })
+        .collect()
+}
+
+/// Parses function names separated by newlines, spaces or commas
+#[allow(clippy::type_complexity)]
+pub fn parse_functions(text: &str) -> Vec<String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The aliases and functions defined in a shell
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellNames {
+    shell: Shell,
+    #[allow(clippy::type_complexity)]
+    aliases: HashMap<String, String>,
+    #[allow(clippy::type_complexity)]
+    functions: Vec<String>,
+    /// `PATH` of the shell, when it isn't this process' one
+    #[allow(clippy::type_complexity)]
+    path: Option<String>,
+}
+
+impl ShellNames {
+    /// Creates the names of a shell without aliases or functions
+    pub fn new(shell: Shell) -> Self {
+        Self {
+            shell,
+            aliases: HashMap::new(),
+            functions: Vec::new(),
+            path: None,
+        }
+    }
+
+    /// Gets the names of a shell from what its hook exported in an environment
+    #[allow(clippy::type_complexity)]
+    pub fn from_env(shell: Shell, env: &HashMap<String, String>) -> Self {
+        let get = |name: &str| env.get(name).map(String::as_str).unwrap_or_default();
+        let mut names = Self::new(shell)
+            .with_aliases(get(ALIASES_ENV))
+            .with_functions(get(FUNCTIONS_ENV));
+        if let Some(path) = env.get("PATH") {
+            names = names.with_path(path);
+        }
+        names
+    }
+
+    /// Gets the names of the shell a command ran in
+    pub fn from_command(command: &Command) -> Self {
+        Self::from_env(command.shell.clone(), &command.env)
+    }
+
+    /// Adds aliases as printed by `alias`
+    pub fn with_aliases(mut self, text: &str) -> Self {
+        self.aliases.extend(parse_aliases(text));
+        self
+    }
+
+    /// Adds function names
+    pub fn with_functions(mut self, text: &str) -> Self {
+        self.functions.extend(parse_functions(text));
+        self
+    }
+
+    /// Looks executables up in a `PATH` other than this process' one
+    pub fn with_path(mut self, path: &str) -> Self {
+        let current = std::env::var("PATH").ok();
+        self.path = (current.as_deref() != Some(path)).then(|| path.to_string());
+        self
+    }
+
+    /// Resolves a name through the aliases, functions, builtins and finally `PATH`
+    #[allow(clippy::type_complexity)]
+    pub fn resolve(&self, name: &str) -> Option<Resolution> {
+        if let Some(value) = self.aliases.get(name) {
+            return Some(Resolution::Alias(value.clone()));
+        }
+        if self.functions.iter().any(|function| function == name) {
+            return Some(Resolution::Function);
+        }
+        if builtins(&self.shell).contains(&name) {
+            return Some(Resolution::Builtin);
+        }
+        match &self.path {
+            Some(path) => executables::find_in_path(path, name),
+            None => which(name),
+        }
+        .map(Resolution::Executable)
+    }
+}
+
+/// Resolves a name the way the shell of a command would, through the aliases and functions its
+/// hook exported, its builtins and finally its `PATH`
+#[allow(clippy::type_complexity)]
+pub fn resolve(name: &str, command: &Command) -> Option<Resolution> {
+    lookups::record(|| format!("resolved `{name}` in {}", command.shell));
+    ShellNames::from_command(command).resolve(name)
+}
+
+/// Checks if a name runs anything in the shell of a command
+pub fn is_available(name: &str, command: &Command) -> bool {
+    resolve(name, command).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_aliases() {
+        let bash = "alias g='git'\nalias ll='ls -l --color=auto'\n";
+        let aliases = parse_aliases(bash);
+        assert_eq!(aliases["g"], "git");
+        assert_eq!(aliases["ll"], "ls -l --color=auto");
+
+        let zsh = "g=git\nll='ls -l'\n";
+        assert_eq!(parse_aliases(zsh)["ll"], "ls -l");
+
+        let fish = "alias ll 'ls -l'\n";
+        assert_eq!(parse_aliases(fish)["ll"], "ls -l");
+        assert!(parse_aliases("\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        assert_eq!(
+            parse_functions("nvm\n__thefuck_precmd, fish_prompt"),
+            vec!["nvm", "__thefuck_precmd", "fish_prompt"]
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let names = ShellNames::new(Shell::Bash)
+            .with_aliases("alias ll='ls -l'\nalias cd='z'")
+            .with_functions("nvm");
+        assert_eq!(
+            names.resolve("ll"),
+            Some(Resolution::Alias("ls -l".to_string()))
+        );
+        assert_eq!(
+            names.resolve("cd"),
+            Some(Resolution::Alias("z".to_string()))
+        );
+        assert_eq!(names.resolve("nvm"), Some(Resolution::Function));
+        assert_eq!(names.resolve("shopt"), Some(Resolution::Builtin));
+        assert_eq!(names.resolve("thefuck-rs-missing"), None);
+
+        let fish = ShellNames::new(Shell::Fish);
+        assert_eq!(fish.resolve("shopt"), None);
+        assert_eq!(fish.resolve("string"), Some(Resolution::Builtin));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_in_command_env() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let tool = dir.path().join("thefuck-rs-tool");
+        std::fs::write(&tool, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let env = HashMap::from([
+            (ALIASES_ENV.to_string(), "alias g='git'".to_string()),
+            (FUNCTIONS_ENV.to_string(), "nvm".to_string()),
+            ("PATH".to_string(), dir.path().to_string_lossy().to_string()),
+        ]);
+        let command = Command::new("g st".to_string(), Shell::Bash).with_env(env);
+        assert_eq!(
+            resolve("g", &command),
+            Some(Resolution::Alias("git".to_string()))
+        );
+        assert!(is_available("nvm", &command));
+        assert_eq!(
+            resolve("thefuck-rs-tool", &command),
+            Some(Resolution::Executable(tool))
+        );
+        assert!(!is_available(
+            "g",
+            &Command::new("g".to_string(), Shell::Bash)
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+//! Extracts the corrections programs suggest in their error output.
+//!
+//! Most command line tools word their suggestions in one of a few ways: inline
+//! (``Did you mean `build`?``, `Did you mean "plan"?`, `Did you mean install?`,
+//! `Perhaps you meant logs.`), or as a heading followed by an indented or bulleted list:
+//!
+//! ```text
+//! The most similar command is
+//!         status
+//!
+//! Invalid choice: 'dynamdb', maybe you meant:
+//!
+//!   * dynamodb
+//! ```
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Markers that bullet-list items start with
+#[allow(clippy::type_complexity)]
+const BULLETS: &[char] = &['*', '-', '•'];
+
+/// Gets the suggestions made inline on a line, e.g. ``Did you mean `build`?``
+#[allow(clippy::type_complexity)]
+fn inline_suggestions(line: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(
+            r#"(?i)(?:did you mean|maybe you meant|perhaps you meant|a command with a similar name exists:)\s+(?:[`"']([^`"'\n]+)[`"']|([\w:.-]+?)(?:\?|\.?\s*$))"#,
+        )
+        .expect("valid regex")
+    });
+    regex
+        .captures_iter(line)
+        .filter_map(|captures| captures.get(1).or_else(|| captures.get(2)))
+        .map(|m| m.as_str())
+        .filter(|suggestion| !matches!(*suggestion, "this" | "these"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Checks if a line introduces a list of suggestions, e.g. `Did you mean one of these?`
+#[allow(clippy::type_complexity)]
+fn is_heading(line: &str) -> bool {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?i)did you mean|maybe you meant|most similar|similar commands?\b|commands are available",
+        )
+            .expect("valid regex")
+    });
+    regex.is_match(line)
+}
+
+/// Gets a list item without its indentation and bullet, if the line is one
+#[allow(clippy::type_complexity)]
+fn list_item(line: &str) -> Option<&str> {
+    let item = line.trim();
+    if item.is_empty() {
+        return None;
+    }
+    let unbulleted = item
+        .strip_prefix(BULLETS)
+        .filter(|rest| rest.starts_with(char::is_whitespace));
+    match unbulleted {
+        Some(rest) => Some(rest.trim_start()),
+        None => line.starts_with(char::is_whitespace).then_some(item),
+    }
+}
+
+/// Gets the suggestions a program made in its output, in the order it made them
+///
+/// Understands inline suggestions and indented or bulleted lists following a heading like
+/// "Did you mean this?", "The most similar commands are" or "maybe you meant:". Suggestions are
+/// returned as written, so a rule may still need to strip the program name from them.
+#[allow(clippy::type_complexity)]
+pub fn extract_suggestions(output: &str) -> Vec<String> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut suggestions: Vec<String> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let inline = inline_suggestions(line);
+        let found = if !inline.is_empty() {
+            inline
+        } else if is_heading(line) {
+            lines[index + 1..]
+                .iter()
+                .skip_while(|line| line.trim().is_empty())
+                .map_while(|line| list_item(line))
+                .map(str::to_string)
+                .collect()
+        } else {
+            continue;
+        };
+        for suggestion in found {
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_suggestions() {
+        assert_eq!(
+            extract_suggestions("error: no such command: `biuld`\n\n\tDid you mean `build`?\n"),
+            vec!["build"]
+        );
+        assert_eq!(
+            extract_suggestions(
+                "error: no such command: `tset`\n\n\
+                 \ta command with a similar name exists: `test`\n"
+            ),
+            vec!["test"]
+        );
+        assert_eq!(
+            extract_suggestions("Terraform has no command named \"pla\". Did you mean \"plan\"?\n"),
+            vec!["plan"]
+        );
+        assert_eq!(
+            extract_suggestions("Command 'instal' is not defined. Did you mean install?\n"),
+            vec!["install"]
+        );
+        assert_eq!(
+            extract_suggestions("Command \"buil\" not found. Did you mean \"pnpm run build\"?\n"),
+            vec!["pnpm run build"]
+        );
+        assert_eq!(
+            extract_suggestions(
+                " ▸    log is not a heroku command.\n ▸    Perhaps you meant logs.\n"
+            ),
+            vec!["logs"]
+        );
+        assert_eq!(
+            extract_suggestions("pg:infoo is not a heroku command.\nPerhaps you meant pg:info\n"),
+            vec!["pg:info"]
+        );
+    }
+
+    #[test]
+    fn test_listed_suggestions() {
+        let git = "git: 'stauts' is not a git command. See 'git --help'.\n\n\
+                   The most similar commands are\n\tstatus\n\tstage\n";
+        assert_eq!(extract_suggestions(git), vec!["status", "stage"]);
+
+        let docker = "docker: 'pss' is not a docker command.\n\
+                      See 'docker --help'\n\nDid you mean this?\n\tps\n\tpush\n";
+        assert_eq!(extract_suggestions(docker), vec!["ps", "push"]);
+
+        let aws = "usage: aws [options] <command> <subcommand>\n\n\
+                   Invalid choice: 'dynamdb', maybe you meant:\n\n  * dynamodb\n  * dynamodbstreams\n\n\
+                   aws: error: argument command: Invalid choice\n";
+        assert_eq!(
+            extract_suggestions(aws),
+            vec!["dynamodb", "dynamodbstreams"]
+        );
+
+        let az = "'providers' is misspelled or not recognized by the system.\n\n\
+                  The most similar choice to 'providers' is:\n    provider\n";
+        assert_eq!(extract_suggestions(az), vec!["provider"]);
+
+        let winget = "Unrecognized command: 'isntall'\n\n\
+                      The following commands are available:\n  install    Installs the given package\n\
+                      \x20 show       Shows information about a package\n\n";
+        assert_eq!(
+            extract_suggestions(winget),
+            vec![
+                "install    Installs the given package",
+                "show       Shows information about a package"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_suggestions() {
+        assert!(extract_suggestions("").is_empty());
+        assert!(extract_suggestions("Did you mean this?\n").is_empty());
+        assert!(extract_suggestions("Did you mean this?\nrun 'helm --help'\n").is_empty());
+        assert!(extract_suggestions("bash: sl: command not found\n").is_empty());
+        assert!(extract_suggestions("Did you mean to push?\n").is_empty());
+    }
+}
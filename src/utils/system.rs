@@ -0,0 +1,119 @@
+//! What rules need from the operating system: running programs and finding executables.
+//!
+//! Rules reach the system through [`system`] only, so the matching engine also runs where
+//! there's no process or `PATH` to speak of. Builds for wasm32 use [`NoSystem`], which runs
+//! nothing and finds nothing, and embedders can install their own with [`set_system`].
+
+use super::executables;
+use std::path::PathBuf;
+use std::process::Command as ProcessCommand;
+use std::sync::OnceLock;
+
+/// Runs programs and finds executables for the rules
+pub trait System: Send + Sync {
+    /// Runs a program, in a directory if given, and gets its standard output if it succeeded
+    #[allow(clippy::type_complexity)]
+    fn output(&self, program: &str, args: &[&str], dir: Option<&str>) -> Option<String>;
+
+    /// Gets the names of all executables on `PATH`, sorted and without duplicates
+    #[allow(clippy::type_complexity)]
+    fn executables(&self) -> &[String];
+
+    /// Finds an executable on `PATH`, or checks a path to one
+    #[allow(clippy::type_complexity)]
+    fn which(&self, program: &str) -> Option<PathBuf>;
+}
+
+/// The operating system the process runs on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NativeSystem;
+
+impl System for NativeSystem {
+    #[allow(clippy::type_complexity)]
+    fn output(&self, program: &str, args: &[&str], dir: Option<&str>) -> Option<String> {
+        let mut command = ProcessCommand::new(program);
+        command.args(args);
+        if let Some(dir) = dir {
+            command.current_dir(dir);
+        }
+        super::run_for_output(&mut command)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn executables(&self) -> &[String] {
+        executables::path_executables()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn which(&self, program: &str) -> Option<PathBuf> {
+        executables::find_executable(program)
+    }
+}
+
+/// A system without programs, e.g. a browser running the engine compiled to wasm32
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoSystem;
+
+impl System for NoSystem {
+    #[allow(clippy::type_complexity)]
+    fn output(&self, _program: &str, _args: &[&str], _dir: Option<&str>) -> Option<String> {
+        None
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn executables(&self) -> &[String] {
+        &[]
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn which(&self, _program: &str) -> Option<PathBuf> {
+        None
+    }
+}
+
+#[allow(clippy::type_complexity)]
+static SYSTEM: OnceLock<Box<dyn System>> = OnceLock::new();
+
+/// Sets the system rules use, returning `false` if one was already in use
+pub fn set_system(system: impl System + 'static) -> bool {
+    SYSTEM.set(Box::new(system)).is_ok()
+}
+
+/// Gets the system rules use, the native one unless built for wasm32 or set otherwise
+pub fn system() -> &'static dyn System {
+    SYSTEM
+        .get_or_init(|| {
+            if cfg!(target_arch = "wasm32") {
+                Box::new(NoSystem)
+            } else {
+                Box::new(NativeSystem)
+            }
+        })
+        .as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_system() {
+        assert_eq!(NoSystem.output("ls", &[], None), None);
+        assert!(NoSystem.executables().is_empty());
+        assert_eq!(NoSystem.which("ls"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_native_system() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = NativeSystem.output("pwd", &[], dir.path().to_str());
+        assert!(
+            output
+                .unwrap()
+                .trim()
+                .ends_with(dir.path().file_name().unwrap().to_string_lossy().as_ref())
+        );
+        assert_eq!(NativeSystem.output("false", &[], None), None);
+    }
+}